@@ -0,0 +1,361 @@
+//! Step by step terminal animations of the grid simulations. Each animated day turns its parsed
+//! input into a lazy iterator of frames, which a `Player` draws with ANSI colours at a given rate,
+//! pausing and stepping on commands read a line at a time.
+
+use std::fmt::{Display, Formatter};
+use std::io::{self, BufRead, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+use crate::{day10, day14, day15, day17, day18, day22, day23, day24};
+use crate::runner::RunError;
+use crate::solution::Solution;
+use crate::vector2::Vector2i;
+
+const MIN_FPS: f64 = 0.25;
+const MAX_FPS: f64 = 1000.0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Cell {
+    /// Outside of the simulated area.
+    Void,
+    Empty,
+    Wall,
+    Rock,
+    Sand,
+    Elf,
+    Blizzard(char),
+    Actor(char),
+    Trail(char),
+    Lit,
+    Covered,
+    Sensor,
+    Beacon,
+    Lava,
+    Pocket,
+}
+
+impl Cell {
+    pub fn glyph(self) -> char {
+        match self {
+            Cell::Void => ' ',
+            Cell::Empty => '.',
+            Cell::Wall => '#',
+            Cell::Rock => '#',
+            Cell::Sand => 'o',
+            Cell::Elf => '#',
+            Cell::Blizzard(glyph) | Cell::Actor(glyph) | Cell::Trail(glyph) => glyph,
+            Cell::Lit => '#',
+            Cell::Covered => '#',
+            Cell::Sensor => 'S',
+            Cell::Beacon => 'B',
+            Cell::Lava => '#',
+            Cell::Pocket => '*',
+        }
+    }
+
+    /// The name of the cell type, as palettes refer to it.
+    pub fn name(self) -> &'static str {
+        match self {
+            Cell::Void => "void",
+            Cell::Empty => "empty",
+            Cell::Wall => "wall",
+            Cell::Rock => "rock",
+            Cell::Sand => "sand",
+            Cell::Elf => "elf",
+            Cell::Blizzard(_) => "blizzard",
+            Cell::Actor(_) => "actor",
+            Cell::Trail(_) => "trail",
+            Cell::Lit => "lit",
+            Cell::Covered => "covered",
+            Cell::Sensor => "sensor",
+            Cell::Beacon => "beacon",
+            Cell::Lava => "lava",
+            Cell::Pocket => "pocket",
+        }
+    }
+
+    /// The SGR parameters the cell is drawn with.
+    fn style(self) -> &'static str {
+        match self {
+            Cell::Void | Cell::Empty => "2",
+            Cell::Wall => "37",
+            Cell::Rock => "90",
+            Cell::Sand => "33",
+            Cell::Elf => "32",
+            Cell::Blizzard(_) => "36",
+            Cell::Actor(_) => "1;31",
+            Cell::Trail(_) => "35",
+            Cell::Lit => "1;93",
+            Cell::Covered => "34",
+            Cell::Sensor => "1;32",
+            Cell::Beacon => "1;94",
+            Cell::Lava => "91",
+            Cell::Pocket => "95",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Frame {
+    pub step: usize,
+    pub cells: Vec<Vec<Cell>>,
+}
+
+impl Frame {
+    /// The cells from `min` to `max`, both included.
+    pub fn from_fn(step: usize, min: Vector2i, max: Vector2i, mut cell: impl FnMut(Vector2i) -> Cell) -> Frame {
+        let cells = (min.y..=max.y)
+            .map(|y| (min.x..=max.x).map(|x| cell(Vector2i{x, y})).collect())
+            .collect();
+
+        Frame { step, cells }
+    }
+
+    /// The frame with ANSI colours, switching style only where the cell type changes.
+    pub fn to_ansi(&self) -> String {
+        let mut ansi = String::new();
+
+        for row in &self.cells {
+            let mut style = None;
+            for cell in row {
+                if style != Some(cell.style()) {
+                    style = Some(cell.style());
+                    ansi.push_str(&format!("\x1b[0;{}m", cell.style()));
+                }
+                ansi.push(cell.glyph());
+            }
+            ansi.push_str("\x1b[0m\n");
+        }
+
+        ansi
+    }
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let rows: Vec<String> = self.cells.iter().map(|row| row.iter().map(|cell| cell.glyph()).collect()).collect();
+
+        f.write_str(&rows.join("\n"))
+    }
+}
+
+pub type Frames = Box<dyn Iterator<Item = Frame>>;
+
+pub trait Animation: Solution {
+    /// The frames of `part`, one per simulation step, ending where the part finds its answer.
+    fn frames(parsed: Self::Parsed, part: usize) -> Frames;
+}
+
+fn animate<A: Animation>(part: usize, input: &str) -> Result<Frames, RunError> {
+    if part == 0 || part > A::PARTS {
+        return Err(RunError::UnknownPart(A::DAY, part));
+    }
+
+    let parsed = A::parse(input.trim_end()).map_err(RunError::Parse)?;
+
+    Ok(A::frames(parsed, part))
+}
+
+pub fn frames(day: usize, part: usize, input: &str) -> Result<Frames, RunError> {
+    match day {
+        10 => animate::<day10::Day10>(part, input),
+        14 => animate::<day14::Day14>(part, input),
+        15 => animate::<day15::Day15>(part, input),
+        17 => animate::<day17::Day17>(part, input),
+        18 => animate::<day18::Day18>(part, input),
+        22 => animate::<day22::Day22>(part, input),
+        23 => animate::<day23::Day23>(part, input),
+        24 => animate::<day24::Day24>(part, input),
+        _ => Err(RunError::NoAnimation(day)),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Control {
+    Pause,
+    Step,
+    Faster,
+    Slower,
+    Quit,
+}
+
+impl Control {
+    /// `p` pauses or resumes, an empty line steps, `+` and `-` double or halve the rate and `q` quits.
+    pub fn parse(line: &str) -> Option<Control> {
+        match line.trim() {
+            "p" => Some(Control::Pause),
+            "" | "s" => Some(Control::Step),
+            "+" => Some(Control::Faster),
+            "-" => Some(Control::Slower),
+            "q" => Some(Control::Quit),
+            _ => None,
+        }
+    }
+}
+
+/// Controls read from stdin a line at a time, by a thread that lives until stdin closes.
+pub fn stdin_controls() -> Receiver<Control> {
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines().map_while(Result::ok) {
+            if let Some(control) = Control::parse(&line) {
+                if sender.send(control).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+
+    receiver
+}
+
+pub struct Player<W: Write> {
+    out: W,
+    fps: f64,
+    colour: bool,
+    paused: bool,
+}
+
+impl<W: Write> Player<W> {
+    pub fn new(out: W, fps: f64) -> Self {
+        Player { out, fps: fps.clamp(MIN_FPS, MAX_FPS), colour: true, paused: false }
+    }
+
+    /// Starts paused on the first frame.
+    pub fn paused(self) -> Self {
+        Player { paused: true, ..self }
+    }
+
+    /// Draws the glyphs only, for terminals without colours.
+    pub fn plain(self) -> Self {
+        Player { colour: false, ..self }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    fn draw(&mut self, frame: &Frame) -> io::Result<()> {
+        let grid = if self.colour { frame.to_ansi() } else { format!("{}\n", frame) };
+        let state = if self.paused { "paused".to_string() } else { format!("{} fps", self.fps) };
+
+        writeln!(self.out, "\x1b[H\x1b[2J{}step {} ({}) - p pause, enter step, +/- speed, q quit", grid, frame.step, state)?;
+        self.out.flush()
+    }
+
+    /// Plays `frames` until they run out or a quit arrives, returning how many were drawn.
+    /// Once `controls` disconnects the remaining frames play without pausing.
+    pub fn play(&mut self, frames: impl Iterator<Item = Frame>, controls: &Receiver<Control>) -> io::Result<usize> {
+        let mut drawn = 0;
+
+        for frame in frames {
+            self.draw(&frame)?;
+            drawn += 1;
+
+            loop {
+                let control = if self.paused {
+                    match controls.recv() {
+                        Ok(control) => control,
+                        Err(_) => {
+                            self.paused = false;
+                            continue;
+                        }
+                    }
+                } else {
+                    let delay = Duration::from_secs_f64(1.0 / self.fps);
+                    match controls.recv_timeout(delay) {
+                        Ok(control) => control,
+                        Err(RecvTimeoutError::Timeout) => break,
+                        Err(RecvTimeoutError::Disconnected) => {
+                            thread::sleep(delay);
+                            break;
+                        }
+                    }
+                };
+
+                match control {
+                    Control::Quit => return Ok(drawn),
+                    Control::Step => {
+                        self.paused = true;
+                        break;
+                    }
+                    Control::Pause if self.paused => {
+                        self.paused = false;
+                        break;
+                    }
+                    Control::Pause => self.paused = true,
+                    Control::Faster => self.fps = (self.fps * 2.0).min(MAX_FPS),
+                    Control::Slower => self.fps = (self.fps / 2.0).max(MIN_FPS),
+                }
+            }
+        }
+
+        Ok(drawn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use super::*;
+
+    fn count(day: usize, part: usize, fixture: &str) -> usize {
+        let input = fs::read_to_string(format!("src/day{}/{}.txt", day, fixture)).unwrap();
+
+        frames(day, part, &input).unwrap().count()
+    }
+
+    #[test]
+    fn frames_follow_the_simulations() {
+        assert_eq!(count(10, 2, "test_large"), 240);
+        assert_eq!(count(15, 2, "test_large"), 1);
+        assert_eq!(count(18, 2, "test_simple"), 8);
+        assert_eq!(count(14, 1, "test_simple"), 24);
+        assert_eq!(count(14, 2, "test_simple"), 93);
+        assert_eq!(count(17, 1, "test_simple"), 2022);
+        assert_eq!(count(23, 1, "test_simple"), 11);
+        assert_eq!(count(23, 2, "test_simple"), 21);
+        assert_eq!(count(24, 1, "test_simple"), 19);
+        assert_eq!(count(24, 2, "test_simple"), 55);
+        assert!(matches!(frames(1, 1, "1"), Err(RunError::NoAnimation(1))));
+        assert!(matches!(frames(14, 3, "498,4 -> 498,6"), Err(RunError::UnknownPart(14, 3))));
+    }
+
+    #[test]
+    fn renders_cells_with_colours() {
+        let frame = Frame::from_fn(3, Vector2i{x: 0, y: 0}, Vector2i{x: 2, y: 1}, |pos| match (pos.x, pos.y) {
+            (0, _) => Cell::Wall,
+            (1, 0) => Cell::Actor('E'),
+            _ => Cell::Empty,
+        });
+
+        assert_eq!(frame.to_string(), "#E.\n#..");
+        assert_eq!(frame.to_ansi().lines().next(), Some("\x1b[0;37m#\x1b[0;1;31mE\x1b[0;2m.\x1b[0m"));
+    }
+
+    #[test]
+    fn player_follows_controls() {
+        let frame = |step| Frame { step, cells: vec![vec![Cell::Sand]] };
+        let play = |player: Player<Vec<u8>>, controls: &[Control]| {
+            let (sender, receiver) = mpsc::channel();
+            controls.iter().for_each(|control| sender.send(*control).unwrap());
+            drop(sender);
+
+            let mut player = player;
+            player.play((0..4).map(frame), &receiver).unwrap()
+        };
+
+        assert_eq!(play(Player::new(Vec::new(), MAX_FPS), &[]), 4);
+        assert_eq!(play(Player::new(Vec::new(), MAX_FPS), &[Control::Faster, Control::Quit]), 1);
+        assert_eq!(play(Player::new(Vec::new(), MAX_FPS).paused(), &[Control::Step, Control::Step, Control::Quit]), 3);
+        assert_eq!(play(Player::new(Vec::new(), MAX_FPS).paused(), &[Control::Pause, Control::Quit]), 2);
+        assert_eq!(Control::parse(" + "), Some(Control::Faster));
+        assert_eq!(Control::parse("x"), None);
+
+        let mut player = Player::new(Vec::new(), MAX_FPS).plain();
+        player.play((7..8).map(frame), &mpsc::channel().1).unwrap();
+        assert!(String::from_utf8(player.into_inner()).unwrap().ends_with("o\nstep 7 (1000 fps) - p pause, enter step, +/- speed, q quit\n"));
+    }
+}
@@ -0,0 +1,78 @@
+use std::collections::BTreeMap;
+use serde::Deserialize;
+
+#[derive(Deserialize, Default, PartialEq, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    /// Slow fixtures are left out of the default test run.
+    #[serde(default)]
+    pub slow: bool,
+}
+
+impl Answers {
+    pub fn part(&self, part: usize) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Known answers by day and fixture name, as kept in `answers.toml`.
+#[derive(Default, Debug)]
+pub struct Manifest {
+    days: BTreeMap<usize, BTreeMap<String, Answers>>,
+}
+
+impl Manifest {
+    pub fn parse(manifest: &str) -> Result<Manifest, String> {
+        let days: BTreeMap<String, BTreeMap<String, Answers>> = toml::from_str(manifest)
+            .map_err(|error| format!("invalid answer manifest: {}", error))?;
+
+        let days = days.into_iter()
+            .map(|(key, fixtures)| match key.strip_prefix("day").and_then(|day| day.parse().ok()) {
+                Some(day) => Ok((day, fixtures)),
+                None => Err(format!("invalid answer manifest: '{}' is not a day", key)),
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Manifest { days })
+    }
+
+    pub fn get(&self, day: usize, fixture: &str) -> Option<&Answers> {
+        self.days.get(&day)?.get(fixture)
+    }
+
+    pub fn expected(&self, day: usize, fixture: &str, part: usize) -> Option<&str> {
+        self.get(day, fixture)?.part(part)
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = (usize, &str, &Answers)> {
+        self.days.iter().flat_map(|(day, fixtures)| fixtures.iter().map(move |(name, answers)| (*day, name.as_str(), answers)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_manifest() {
+        let manifest = Manifest::parse("[day4.test_simple]\npart1 = \"2\"\npart2 = \"4\"\n\n[day25.test_large]\nslow = true\npart1 = \"2=-1=0\"\n").unwrap();
+
+        assert_eq!(manifest.expected(4, "test_simple", 2), Some("4"));
+        assert_eq!(manifest.expected(25, "test_large", 2), None);
+        assert_eq!(manifest.expected(4, "test_large", 1), None);
+        assert!(manifest.get(25, "test_large").unwrap().slow);
+        assert_eq!(manifest.entries().map(|(day, name, _)| (day, name)).collect::<Vec<_>>(), vec![(4, "test_simple"), (25, "test_large")]);
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert!(Manifest::parse("[fourth.test_simple]\npart1 = \"2\"\n").is_err());
+        assert!(Manifest::parse("[day4.test_simple]\npart3 = \"2\"\n").is_err());
+    }
+}
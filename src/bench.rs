@@ -0,0 +1,179 @@
+use std::fmt::{Display, Formatter};
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use crate::solution::get_solver;
+
+// Slower stages are only reported when they lose at least this much, shorter ones are mostly noise.
+const MIN_REGRESSION_NS: u64 = 1_000_000;
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug)]
+pub struct DayTimings {
+    pub day: usize,
+    pub parse_ns: u64,
+    pub part1_ns: u64,
+    pub part2_ns: Option<u64>,
+}
+
+impl DayTimings {
+    pub fn new(day: usize, parse: Duration, part1: Duration, part2: Option<Duration>) -> Self {
+        DayTimings {
+            day,
+            parse_ns: parse.as_nanos() as u64,
+            part1_ns: part1.as_nanos() as u64,
+            part2_ns: part2.map(|part2| part2.as_nanos() as u64),
+        }
+    }
+
+    fn stages(&self) -> impl Iterator<Item = (&'static str, u64)> {
+        [("parse", Some(self.parse_ns)), ("part 1", Some(self.part1_ns)), ("part 2", self.part2_ns)]
+            .into_iter()
+            .filter_map(|(stage, time)| time.map(|time| (stage, time)))
+    }
+
+    // Keeps the fastest of several runs of the same day.
+    fn fastest(self, other: DayTimings) -> DayTimings {
+        DayTimings {
+            day: self.day,
+            parse_ns: self.parse_ns.min(other.parse_ns),
+            part1_ns: self.part1_ns.min(other.part1_ns),
+            part2_ns: self.part2_ns.zip(other.part2_ns).map(|(a, b)| a.min(b)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Default, Debug)]
+pub struct Report {
+    pub days: Vec<DayTimings>,
+}
+
+impl Report {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub fn from_json(json: &str) -> Result<Report, String> {
+        serde_json::from_str(json).map_err(|error| format!("invalid benchmark report: {}", error))
+    }
+
+    pub fn table(&self) -> String {
+        let mut table = format!("{:>3}  {:>10}  {:>10}  {:>10}", "day", "parse", "part 1", "part 2");
+
+        for timings in &self.days {
+            let part2 = timings.part2_ns.map_or("-".to_string(), format_nanos);
+
+            table += &format!(
+                "\n{:>3}  {:>10}  {:>10}  {:>10}",
+                timings.day, format_nanos(timings.parse_ns), format_nanos(timings.part1_ns), part2
+            );
+        }
+
+        table
+    }
+
+    /// Stages that got more than `threshold_percent` slower than in `baseline`.
+    pub fn regressions(&self, baseline: &Report, threshold_percent: f64) -> Vec<Regression> {
+        self.days.iter()
+            .filter_map(|current| baseline.days.iter().find(|old| old.day == current.day).map(|old| (old, current)))
+            .flat_map(|(old, current)| old.stages().zip(current.stages()).map(move |((stage, baseline_ns), (_, current_ns))| Regression {
+                day: current.day,
+                stage,
+                baseline_ns,
+                current_ns,
+            }))
+            .filter(|regression| {
+                regression.current_ns >= regression.baseline_ns + MIN_REGRESSION_NS
+                    && regression.current_ns as f64 > regression.baseline_ns as f64 * (1.0 + threshold_percent / 100.0)
+            })
+            .collect()
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct Regression {
+    pub day: usize,
+    pub stage: &'static str,
+    pub baseline_ns: u64,
+    pub current_ns: u64,
+}
+
+impl Display for Regression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let change = (self.current_ns as f64 / self.baseline_ns.max(1) as f64 - 1.0) * 100.0;
+
+        write!(f, "day {} {}: {} -> {} (+{:.0}%)", self.day, self.stage, format_nanos(self.baseline_ns), format_nanos(self.current_ns), change)
+    }
+}
+
+pub fn format_nanos(nanos: u64) -> String {
+    match nanos {
+        0..=999_999 => format!("{:.1} µs", nanos as f64 / 1e3),
+        1_000_000..=999_999_999 => format!("{:.2} ms", nanos as f64 / 1e6),
+        _ => format!("{:.2} s", nanos as f64 / 1e9),
+    }
+}
+
+pub fn timed<T>(function: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = function();
+
+    (result, start.elapsed())
+}
+
+/// Benchmarks one day on `input`, keeping the fastest time of each stage over `runs` runs.
+pub fn bench_day(day: usize, input: &str, runs: usize) -> Result<DayTimings, String> {
+    let solver = get_solver(day).ok_or(format!("there is no solution for day {}", day))?;
+
+    (0..runs.max(1))
+        .map(|_| panic::catch_unwind(AssertUnwindSafe(|| solver.bench(input)))
+            .map_err(|_| format!("day {} panicked", day))?
+            .map_err(|error| error.to_string()))
+        .reduce(|best, run| Ok(best?.fastest(run?)))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(days: &[(usize, u64, u64, Option<u64>)]) -> Report {
+        Report {
+            days: days.iter().map(|&(day, parse_ns, part1_ns, part2_ns)| DayTimings { day, parse_ns, part1_ns, part2_ns }).collect(),
+        }
+    }
+
+    #[test]
+    fn report_round_trips_through_json() {
+        let report = report(&[(4, 1_500, 20_000_000, Some(3_000_000_000)), (25, 10, 20, None)]);
+
+        assert_eq!(Report::from_json(&report.to_json()), Ok(report));
+        assert!(Report::from_json("{\"days\": 3}").is_err());
+    }
+
+    #[test]
+    fn table_formats_units() {
+        let table = report(&[(4, 1_500, 20_000_000, Some(3_000_000_000)), (25, 10, 20, None)]).table();
+
+        assert_eq!(table.lines().nth(1), Some("  4      1.5 µs    20.00 ms      3.00 s"));
+        assert_eq!(table.lines().nth(2), Some(" 25      0.0 µs      0.0 µs           -"));
+    }
+
+    #[test]
+    fn regressions_above_threshold() {
+        let baseline = report(&[(16, 100_000_000, 100_000_000, Some(100_000_000)), (17, 10, 10, Some(10))]);
+        let current = report(&[(16, 105_000_000, 150_000_000, Some(90_000_000)), (17, 500, 500, Some(500)), (18, 1, 1, None)]);
+        let regressions = current.regressions(&baseline, 20.0);
+
+        assert_eq!(regressions, vec![Regression { day: 16, stage: "part 1", baseline_ns: 100_000_000, current_ns: 150_000_000 }]);
+        assert_eq!(regressions[0].to_string(), "day 16 part 1: 100.00 ms -> 150.00 ms (+50%)");
+    }
+
+    #[test]
+    fn benches_a_day() {
+        let timings = bench_day(6, include_str!("day6/test_large.txt"), 2).unwrap();
+
+        assert_eq!(timings.day, 6);
+        assert!(timings.part2_ns.is_some());
+        assert!(bench_day(26, "", 1).is_err());
+    }
+}
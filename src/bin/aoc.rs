@@ -0,0 +1,695 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std::{env, fs, panic, process, thread};
+use advent_of_code_2022::animate::{self, Player};
+use advent_of_code_2022::answers::Manifest;
+use advent_of_code_2022::bench::{self, format_nanos, Report};
+use advent_of_code_2022::cache::Cache;
+use advent_of_code_2022::cleanup::{self, Objective};
+use advent_of_code_2022::image::{Export, Format, Palette};
+use advent_of_code_2022::inputs::InputStore;
+use advent_of_code_2022::mock_server::MockServer;
+use advent_of_code_2022::observer::{NoOp, Observer, ProgressBar};
+use advent_of_code_2022::runner::{self, Job, JobReport};
+use advent_of_code_2022::solution::get_solvers;
+use advent_of_code_2022::submit::{Client, Verdict};
+use advent_of_code_2022::transcript::{self, Traversal};
+use advent_of_code_2022::vfs::FileSystem;
+
+const USAGE: &str = "Usage:
+  aoc run --day <1-25> --part <1|2> [--input <path> | --user <name>] [--progress] [--no-cache | --refresh]
+          [--animate [--fps <n>] [--step] [--plain]]
+  aoc bench [--days <1,2,..>] [--runs <n>] [--json <path>] [--baseline <path>] [--threshold <percent>] [--fixtures <dir>]
+  aoc all [--fixture <name>] [--fixtures <dir>] [--answers <path>] [--threads <n>] [--timeout <seconds>] [--no-cache | --refresh]
+  aoc export --day <1-25> --part <1|2> --output <path.ppm|.png|.gif> [--input <path> | --user <name>]
+             [--cell <pixels>] [--palette <name=rrggbb,..>] [--fps <n>] [--every <n>]
+  aoc fs [--input <path> | --user <name>] [--format <tree|du|json>] [--path <directory>]
+  aoc cleanup [--input <path> | --user <name>] [--capacity <bytes>] [--free <bytes>] [--objective <bytes|items>]
+  aoc transcript [--input <path> | --user <name> | --generate <seed>] [--order <dfs|bfs|random[:seed]>]
+  aoc cache clear [--day <1-25>]
+  aoc submit --day <1-25> --part <1|2> [--answer <answer> | --user <name>] [--url <url>]
+  aoc mock-server [--address <host:port>] [--answers <path>] [--fixture <name>]
+
+Answers are cached in target/aoc-cache by input. --no-cache neither reads nor writes the cache,
+--refresh solves again and replaces the cached answers.
+
+--animate draws days 10, 14, 15, 17, 18, 22, 23 and 24 step by step, at 10 frames per second unless --fps says
+otherwise. While it plays, type p to pause or resume, an empty line to step, + or - to change the
+speed and q to quit, each followed by enter. --step starts paused and --plain leaves out colours.
+
+export draws the same frames as an image, the last frame for PPM and PNG and one frame out of
+every --every for GIF. Cells are --cell pixels wide, 4 by default, and --palette recolours cell
+types: void, empty, wall, rock, sand, elf, blizzard, actor, trail, lit, covered, sensor, beacon,
+lava and pocket.
+
+fs replays the day 7 terminal session and prints the file system it reveals, from --path or the
+root, as a tree, as du -a lines or as JSON.
+
+cleanup lists the directories and files to delete so that --free bytes are free on a disk of
+--capacity bytes, 30000000 out of 70000000 by default. It deletes as few bytes as possible, or
+with --objective items as few entries as possible.
+
+transcript prints a day 7 terminal session that lists the same file system, or the one generated
+from --generate, visiting directories depth first, breadth first or in a seeded random order.
+
+Without --input, the input is read from inputs/<user>/dayNN.txt, then inputs/dayNN.txt, then the
+bundled fixture. The user defaults to $AOC_USER. submit posts to --url or $AOC_URL, for example
+https://adventofcode.com/2022, with the session cookie in $AOC_SESSION.";
+
+const DEFAULT_FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+const DEFAULT_ANSWERS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers.toml");
+const DEFAULT_CACHE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/target/aoc-cache");
+const DEFAULT_INPUTS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/inputs");
+
+fn input_store() -> InputStore {
+    InputStore::new(DEFAULT_INPUTS, DEFAULT_FIXTURES)
+}
+
+/// The input at `path`, or else the one in the input store for `user`.
+fn read_input(path: Option<&str>, user: Option<&str>, day: usize) -> Result<String, String> {
+    match path {
+        Some(path) => fs::read_to_string(path).map_err(|error| format!("could not read {}: {}", path, error)),
+        None => {
+            let (path, input) = input_store().read(user, day)?;
+            eprintln!("using {}", path.display());
+
+            Ok(input)
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum CacheMode {
+    Use,
+    Refresh,
+    Off,
+}
+
+impl CacheMode {
+    /// Handles the cache flags, returning whether `argument` was one.
+    fn parse_flag(&mut self, argument: &str) -> bool {
+        match argument {
+            "--no-cache" => *self = CacheMode::Off,
+            "--refresh" => *self = CacheMode::Refresh,
+            _ => return false,
+        }
+
+        true
+    }
+
+    fn open(self) -> Option<Cache> {
+        match self {
+            CacheMode::Use => Some(Cache::new(DEFAULT_CACHE)),
+            CacheMode::Refresh => Some(Cache::new(DEFAULT_CACHE).refreshing()),
+            CacheMode::Off => None,
+        }
+    }
+}
+
+struct RunArguments {
+    day: usize,
+    part: usize,
+    input: Option<String>,
+    user: Option<String>,
+    progress: bool,
+    cache: CacheMode,
+    animation: Option<Animation>,
+}
+
+struct Animation {
+    fps: f64,
+    step: bool,
+    plain: bool,
+}
+
+fn parse_run_arguments(arguments: &[String]) -> Result<RunArguments, String> {
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut user = env::var("AOC_USER").ok();
+    let mut progress = false;
+    let mut cache = CacheMode::Use;
+    let mut animate = false;
+    let mut animation = Animation { fps: 10.0, step: false, plain: false };
+
+    let mut iterator = arguments.iter();
+    while let Some(argument) = iterator.next() {
+        match argument.as_str() {
+            "--progress" => progress = true,
+            "--animate" => animate = true,
+            "--step" => animation.step = true,
+            "--plain" => animation.plain = true,
+            _ if cache.parse_flag(argument) => {}
+            _ => {
+                let value = iterator.next().ok_or(format!("missing value for {}", argument))?;
+
+                match argument.as_str() {
+                    "--day" | "-d" => day = Some(value.parse::<usize>().map_err(|_| format!("invalid day '{}'", value))?),
+                    "--part" | "-p" => part = Some(value.parse::<usize>().map_err(|_| format!("invalid part '{}'", value))?),
+                    "--input" | "-i" => input = Some(value.clone()),
+                    "--user" | "-u" => user = Some(value.clone()),
+                    "--fps" => animation.fps = value.parse::<f64>().ok().filter(|fps| *fps > 0.0).ok_or(format!("invalid fps '{}'", value))?,
+                    other => return Err(format!("unknown argument '{}'", other)),
+                }
+            }
+        }
+    }
+
+    Ok(RunArguments {
+        day: day.ok_or("missing --day")?,
+        part: part.ok_or("missing --part")?,
+        input,
+        user,
+        progress,
+        cache,
+        animation: animate.then_some(animation),
+    })
+}
+
+struct BenchArguments {
+    days: Vec<usize>,
+    runs: usize,
+    json: Option<String>,
+    baseline: Option<String>,
+    threshold: f64,
+    fixtures: String,
+}
+
+fn parse_bench_arguments(arguments: &[String]) -> Result<BenchArguments, String> {
+    let mut bench_arguments = BenchArguments {
+        days: (1..=25).collect(),
+        runs: 1,
+        json: None,
+        baseline: None,
+        threshold: 25.0,
+        fixtures: DEFAULT_FIXTURES.to_string(),
+    };
+
+    let mut iterator = arguments.iter();
+    while let Some(argument) = iterator.next() {
+        let value = iterator.next().ok_or(format!("missing value for {}", argument))?;
+
+        match argument.as_str() {
+            "--days" | "-d" => bench_arguments.days = value
+                .split(',')
+                .map(|day| day.trim().parse::<usize>().map_err(|_| format!("invalid day '{}'", day)))
+                .collect::<Result<_, _>>()?,
+            "--runs" | "-r" => bench_arguments.runs = value.parse().map_err(|_| format!("invalid number of runs '{}'", value))?,
+            "--json" => bench_arguments.json = Some(value.clone()),
+            "--baseline" => bench_arguments.baseline = Some(value.clone()),
+            "--threshold" => bench_arguments.threshold = value.parse().map_err(|_| format!("invalid threshold '{}'", value))?,
+            "--fixtures" => bench_arguments.fixtures = value.clone(),
+            other => return Err(format!("unknown argument '{}'", other)),
+        }
+    }
+
+    Ok(bench_arguments)
+}
+
+fn bench(arguments: BenchArguments) -> Result<String, String> {
+    let baseline = match &arguments.baseline {
+        Some(path) => Some(Report::from_json(&fs::read_to_string(path).map_err(|error| format!("could not read {}: {}", path, error))?)?),
+        None => None,
+    };
+
+    let mut report = Report::default();
+    for day in arguments.days {
+        let path = format!("{}/day{}/test_large.txt", arguments.fixtures, day);
+
+        match fs::read_to_string(&path) {
+            Ok(input) => match bench::bench_day(day, &input, arguments.runs) {
+                Ok(timings) => report.days.push(timings),
+                Err(message) => eprintln!("day {}: {}", day, message),
+            },
+            Err(_) => eprintln!("day {}: skipped, no fixture at {}", day, path),
+        }
+    }
+
+    if let Some(path) = &arguments.json {
+        fs::write(path, report.to_json()).map_err(|error| format!("could not write {}: {}", path, error))?;
+    }
+
+    let regressions = baseline.map(|baseline| report.regressions(&baseline, arguments.threshold)).unwrap_or_default();
+    if !regressions.is_empty() {
+        let lines: Vec<_> = regressions.iter().map(|regression| regression.to_string()).collect();
+
+        println!("{}", report.table());
+        return Err(format!("{} regression(s) above {}%:\n{}", regressions.len(), arguments.threshold, lines.join("\n")));
+    }
+
+    Ok(report.table())
+}
+
+struct AllArguments {
+    fixture: String,
+    fixtures: String,
+    answers: String,
+    threads: usize,
+    timeout: Duration,
+    cache: CacheMode,
+}
+
+fn parse_all_arguments(arguments: &[String]) -> Result<AllArguments, String> {
+    let mut all_arguments = AllArguments {
+        fixture: "test_large".to_string(),
+        fixtures: DEFAULT_FIXTURES.to_string(),
+        answers: DEFAULT_ANSWERS.to_string(),
+        threads: thread::available_parallelism().map_or(1, |threads| threads.get()),
+        timeout: Duration::from_secs(60),
+        cache: CacheMode::Use,
+    };
+
+    let mut iterator = arguments.iter();
+    while let Some(argument) = iterator.next() {
+        if all_arguments.cache.parse_flag(argument) {
+            continue;
+        }
+
+        let value = iterator.next().ok_or(format!("missing value for {}", argument))?;
+
+        match argument.as_str() {
+            "--fixture" => all_arguments.fixture = value.clone(),
+            "--fixtures" => all_arguments.fixtures = value.clone(),
+            "--answers" => all_arguments.answers = value.clone(),
+            "--threads" | "-t" => all_arguments.threads = value.parse().map_err(|_| format!("invalid number of threads '{}'", value))?,
+            "--timeout" => all_arguments.timeout = value.parse::<f64>().ok()
+                .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+                .ok_or(format!("invalid timeout '{}'", value))?,
+            other => return Err(format!("unknown argument '{}'", other)),
+        }
+    }
+
+    Ok(all_arguments)
+}
+
+fn all(arguments: AllArguments) -> Result<String, String> {
+    let manifest = match fs::read_to_string(&arguments.answers) {
+        Ok(manifest) => Manifest::parse(&manifest)?,
+        Err(_) => {
+            eprintln!("no known answers at {}, answers will not be checked", arguments.answers);
+            Manifest::default()
+        }
+    };
+
+    let mut jobs = Vec::new();
+    let mut missing = Vec::new();
+    for solver in get_solvers() {
+        let path = format!("{}/day{}/{}.txt", arguments.fixtures, solver.day(), arguments.fixture);
+        let Ok(input) = fs::read_to_string(&path) else {
+            missing.extend((1..=solver.parts()).map(|part| JobReport::no_input(solver.day(), part, format!("no fixture at {}", path))));
+            continue;
+        };
+        let input: Arc<str> = input.into();
+
+        for part in 1..=solver.parts() {
+            let expected = manifest.expected(solver.day(), &arguments.fixture, part).map(str::to_string);
+
+            jobs.push(Job { day: solver.day(), part, input: Arc::clone(&input), expected });
+        }
+    }
+
+    // Panics are reported in the summary, the default hook would print them in between.
+    panic::set_hook(Box::new(|_| {}));
+
+    let start = Instant::now();
+    let mut reports = match arguments.cache.open() {
+        Some(cache) => runner::solve_all_cached(jobs, arguments.threads, arguments.timeout, &cache),
+        None => runner::solve_all(jobs, arguments.threads, arguments.timeout),
+    };
+    reports.extend(missing);
+    reports.sort_by_key(|report| (report.day, report.part));
+    let summary = format!("{}\nsolved in {}", runner::summary(&reports), format_nanos(start.elapsed().as_nanos() as u64));
+
+    // Solvers that timed out may still be running, they are stopped when the process exits.
+    let failures = reports.iter().filter(|report| report.is_correct() == Some(false)).count();
+    if failures > 0 {
+        println!("{}", summary);
+        return Err(format!("{} of {} job(s) did not pass", failures, reports.len()));
+    }
+
+    Ok(summary)
+}
+
+fn run(arguments: RunArguments) -> Result<String, String> {
+    let input = read_input(arguments.input.as_deref(), arguments.user.as_deref(), arguments.day)?;
+
+    if let Some(animation) = &arguments.animation {
+        return play(arguments.day, arguments.part, &input, animation);
+    }
+
+    let cache = arguments.cache.open();
+    if let Some(entry) = cache.as_ref().and_then(|cache| cache.get(arguments.day, arguments.part, &input)) {
+        eprintln!("cached answer, solved in {}", format_nanos(entry.nanos));
+        return Ok(entry.answer);
+    }
+
+    let start = Instant::now();
+    let answer = panic::catch_unwind(|| {
+        let mut observer: Box<dyn Observer> = if arguments.progress { Box::new(ProgressBar::stderr()) } else { Box::new(NoOp) };
+
+        runner::solve_observed(arguments.day, arguments.part, &input, observer.as_mut())
+    })
+        .map_err(|_| format!("day {} part {} panicked", arguments.day, arguments.part))?
+        .map_err(|error| error.to_string())?;
+
+    if let Some(cache) = cache {
+        if let Err(error) = cache.put(arguments.day, arguments.part, &input, &answer, start.elapsed()) {
+            eprintln!("could not cache the answer: {}", error);
+        }
+    }
+
+    Ok(answer)
+}
+
+fn play(day: usize, part: usize, input: &str, animation: &Animation) -> Result<String, String> {
+    let frames = animate::frames(day, part, input).map_err(|error| error.to_string())?;
+
+    let mut player = Player::new(std::io::stdout(), animation.fps);
+    if animation.step {
+        player = player.paused();
+    }
+    if animation.plain {
+        player = player.plain();
+    }
+
+    let drawn = panic::catch_unwind(panic::AssertUnwindSafe(|| player.play(frames, &animate::stdin_controls())))
+        .map_err(|_| format!("day {} part {} panicked", day, part))?
+        .map_err(|error| format!("could not draw: {}", error))?;
+
+    Ok(format!("{} frame(s)", drawn))
+}
+
+struct ExportArguments {
+    day: usize,
+    part: usize,
+    input: Option<String>,
+    user: Option<String>,
+    output: String,
+    export: Export,
+}
+
+fn parse_export_arguments(arguments: &[String]) -> Result<ExportArguments, String> {
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut user = env::var("AOC_USER").ok();
+    let mut output = None;
+    let mut export = Export::default();
+
+    let mut iterator = arguments.iter();
+    while let Some(argument) = iterator.next() {
+        let value = iterator.next().ok_or(format!("missing value for {}", argument))?;
+
+        match argument.as_str() {
+            "--day" | "-d" => day = Some(value.parse::<usize>().map_err(|_| format!("invalid day '{}'", value))?),
+            "--part" | "-p" => part = Some(value.parse::<usize>().map_err(|_| format!("invalid part '{}'", value))?),
+            "--input" | "-i" => input = Some(value.clone()),
+            "--user" | "-u" => user = Some(value.clone()),
+            "--output" | "-o" => output = Some(value.clone()),
+            "--cell" => export.cell_size = value.parse().ok().filter(|size| *size > 0).ok_or(format!("invalid cell size '{}'", value))?,
+            "--palette" => export.palette = Palette::default().with_overrides(value)?,
+            "--fps" => export.fps = value.parse::<f64>().ok().filter(|fps| *fps > 0.0).ok_or(format!("invalid fps '{}'", value))?,
+            "--every" => export.every = value.parse().ok().filter(|every| *every > 0).ok_or(format!("invalid frame step '{}'", value))?,
+            other => return Err(format!("unknown argument '{}'", other)),
+        }
+    }
+
+    Ok(ExportArguments {
+        day: day.ok_or("missing --day")?,
+        part: part.ok_or("missing --part")?,
+        input,
+        user,
+        output: output.ok_or("missing --output")?,
+        export,
+    })
+}
+
+fn export(arguments: ExportArguments) -> Result<String, String> {
+    let format = Format::from_path(std::path::Path::new(&arguments.output))
+        .ok_or(format!("cannot tell the format of {}, expected .ppm, .png or .gif", arguments.output))?;
+    let input = read_input(arguments.input.as_deref(), arguments.user.as_deref(), arguments.day)?;
+    let file = fs::File::create(&arguments.output).map_err(|error| format!("could not create {}: {}", arguments.output, error))?;
+
+    let drawn = panic::catch_unwind(|| arguments.export.write(arguments.day, arguments.part, &input, format, std::io::BufWriter::new(file)))
+        .map_err(|_| format!("day {} part {} panicked", arguments.day, arguments.part))?
+        .map_err(|error| error.to_string())?;
+
+    Ok(format!("wrote {} frame(s) to {}", drawn, arguments.output))
+}
+
+struct FsArguments {
+    input: Option<String>,
+    user: Option<String>,
+    format: String,
+    path: String,
+}
+
+fn parse_fs_arguments(arguments: &[String]) -> Result<FsArguments, String> {
+    let mut fs_arguments = FsArguments { input: None, user: env::var("AOC_USER").ok(), format: "tree".to_string(), path: "/".to_string() };
+
+    let mut iterator = arguments.iter();
+    while let Some(argument) = iterator.next() {
+        let value = iterator.next().ok_or(format!("missing value for {}", argument))?;
+
+        match argument.as_str() {
+            "--input" | "-i" => fs_arguments.input = Some(value.clone()),
+            "--user" | "-u" => fs_arguments.user = Some(value.clone()),
+            "--format" | "-f" => fs_arguments.format = value.clone(),
+            "--path" => fs_arguments.path = value.clone(),
+            other => return Err(format!("unknown argument '{}'", other)),
+        }
+    }
+
+    Ok(fs_arguments)
+}
+
+fn show_file_system(arguments: FsArguments) -> Result<String, String> {
+    let input = read_input(arguments.input.as_deref(), arguments.user.as_deref(), 7)?;
+    let fs = FileSystem::replay(&input).map_err(|error| error.to_string())?;
+    let id = fs.resolve(FileSystem::ROOT, &arguments.path).map_err(|error| error.to_string())?;
+
+    match arguments.format.as_str() {
+        "tree" => Ok(fs.render_tree(id)),
+        "du" => Ok(fs.render_du(id)),
+        "json" => Ok(fs.render_json(id)),
+        other => Err(format!("unknown format '{}', expected tree, du or json", other)),
+    }
+}
+
+struct CleanupArguments {
+    input: Option<String>,
+    user: Option<String>,
+    capacity: usize,
+    free: usize,
+    objective: Objective,
+}
+
+fn parse_cleanup_arguments(arguments: &[String]) -> Result<CleanupArguments, String> {
+    let mut cleanup_arguments = CleanupArguments { input: None, user: env::var("AOC_USER").ok(), capacity: 70000000, free: 30000000, objective: Objective::Bytes };
+
+    let mut iterator = arguments.iter();
+    while let Some(argument) = iterator.next() {
+        let value = iterator.next().ok_or(format!("missing value for {}", argument))?;
+
+        match argument.as_str() {
+            "--input" | "-i" => cleanup_arguments.input = Some(value.clone()),
+            "--user" | "-u" => cleanup_arguments.user = Some(value.clone()),
+            "--capacity" => cleanup_arguments.capacity = value.parse().map_err(|_| format!("invalid capacity '{}'", value))?,
+            "--free" => cleanup_arguments.free = value.parse().map_err(|_| format!("invalid free space '{}'", value))?,
+            "--objective" => cleanup_arguments.objective = value.parse()?,
+            other => return Err(format!("unknown argument '{}'", other)),
+        }
+    }
+
+    Ok(cleanup_arguments)
+}
+
+fn plan_cleanup(arguments: CleanupArguments) -> Result<String, String> {
+    let input = read_input(arguments.input.as_deref(), arguments.user.as_deref(), 7)?;
+    let fs = FileSystem::replay(&input).map_err(|error| error.to_string())?;
+    let plan = cleanup::plan(&fs, arguments.capacity, arguments.free, arguments.objective).map_err(|error| error.to_string())?;
+
+    let mut lines: Vec<String> = plan.deletions.iter().map(|id| format!("{}\t{}", fs.size(*id), fs.path(*id))).collect();
+    lines.push(format!("deleting {} entries and {} bytes frees {} of the {} bytes needed, up from {}",
+        plan.deletions.len(), plan.deleted, plan.free_after, arguments.free, plan.free_before));
+
+    Ok(lines.join("\n"))
+}
+
+struct TranscriptArguments {
+    input: Option<String>,
+    user: Option<String>,
+    generate: Option<u64>,
+    order: Traversal,
+}
+
+fn parse_transcript_arguments(arguments: &[String]) -> Result<TranscriptArguments, String> {
+    let mut transcript_arguments = TranscriptArguments { input: None, user: env::var("AOC_USER").ok(), generate: None, order: Traversal::DepthFirst };
+
+    let mut iterator = arguments.iter();
+    while let Some(argument) = iterator.next() {
+        let value = iterator.next().ok_or(format!("missing value for {}", argument))?;
+
+        match argument.as_str() {
+            "--input" | "-i" => transcript_arguments.input = Some(value.clone()),
+            "--user" | "-u" => transcript_arguments.user = Some(value.clone()),
+            "--generate" => transcript_arguments.generate = Some(value.parse().map_err(|_| format!("invalid seed '{}'", value))?),
+            "--order" => transcript_arguments.order = value.parse()?,
+            other => return Err(format!("unknown argument '{}'", other)),
+        }
+    }
+
+    Ok(transcript_arguments)
+}
+
+fn synthesize_transcript(arguments: TranscriptArguments) -> Result<String, String> {
+    let fs = match arguments.generate {
+        Some(seed) => transcript::generated(seed),
+        None => FileSystem::replay(&read_input(arguments.input.as_deref(), arguments.user.as_deref(), 7)?).map_err(|error| error.to_string())?,
+    };
+
+    Ok(transcript::synthesize(&fs, arguments.order))
+}
+
+fn parse_cache_arguments(arguments: &[String]) -> Result<Option<usize>, String> {
+    match arguments {
+        [command] if command == "clear" => Ok(None),
+        [command, flag, day] if command == "clear" && (flag == "--day" || flag == "-d") =>
+            Ok(Some(day.parse().map_err(|_| format!("invalid day '{}'", day))?)),
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+fn clear_cache(day: Option<usize>) -> Result<String, String> {
+    let removed = Cache::new(DEFAULT_CACHE).clear(day).map_err(|error| format!("could not clear {}: {}", DEFAULT_CACHE, error))?;
+
+    Ok(format!("removed {} cached answer(s)", removed))
+}
+
+struct SubmitArguments {
+    day: usize,
+    part: usize,
+    answer: Option<String>,
+    user: Option<String>,
+    url: Option<String>,
+}
+
+fn parse_submit_arguments(arguments: &[String]) -> Result<SubmitArguments, String> {
+    let mut day = None;
+    let mut part = None;
+    let mut submit_arguments = SubmitArguments { day: 0, part: 0, answer: None, user: env::var("AOC_USER").ok(), url: env::var("AOC_URL").ok() };
+
+    let mut iterator = arguments.iter();
+    while let Some(argument) = iterator.next() {
+        let value = iterator.next().ok_or(format!("missing value for {}", argument))?;
+
+        match argument.as_str() {
+            "--day" | "-d" => day = Some(value.parse::<usize>().map_err(|_| format!("invalid day '{}'", value))?),
+            "--part" | "-p" => part = Some(value.parse::<usize>().map_err(|_| format!("invalid part '{}'", value))?),
+            "--answer" | "-a" => submit_arguments.answer = Some(value.clone()),
+            "--user" | "-u" => submit_arguments.user = Some(value.clone()),
+            "--url" => submit_arguments.url = Some(value.clone()),
+            other => return Err(format!("unknown argument '{}'", other)),
+        }
+    }
+
+    submit_arguments.day = day.ok_or("missing --day")?;
+    submit_arguments.part = part.ok_or("missing --part")?;
+    Ok(submit_arguments)
+}
+
+fn submit(arguments: SubmitArguments) -> Result<String, String> {
+    let url = arguments.url.ok_or("missing --url, or set AOC_URL")?;
+    let answer = match arguments.answer {
+        Some(answer) => answer,
+        None => {
+            let input = read_input(None, arguments.user.as_deref(), arguments.day)?;
+
+            runner::solve(arguments.day, arguments.part, &input).map_err(|error| error.to_string())?
+        }
+    };
+
+    let client = match env::var("AOC_SESSION") {
+        Ok(session) => Client::new(&url).with_session(session),
+        Err(_) => Client::new(&url),
+    };
+    let verdict = client.submit(arguments.day, arguments.part, &answer).map_err(|error| error.to_string())?;
+
+    match verdict {
+        Verdict::Correct | Verdict::AlreadySolved => Ok(format!("{}: {}", answer, verdict)),
+        _ => Err(format!("{}: {}", answer, verdict)),
+    }
+}
+
+struct MockServerArguments {
+    address: String,
+    answers: String,
+    fixture: String,
+}
+
+fn parse_mock_server_arguments(arguments: &[String]) -> Result<MockServerArguments, String> {
+    let mut mock_server_arguments = MockServerArguments {
+        address: "127.0.0.1:8022".to_string(),
+        answers: DEFAULT_ANSWERS.to_string(),
+        fixture: "test_large".to_string(),
+    };
+
+    let mut iterator = arguments.iter();
+    while let Some(argument) = iterator.next() {
+        let value = iterator.next().ok_or(format!("missing value for {}", argument))?;
+
+        match argument.as_str() {
+            "--address" => mock_server_arguments.address = value.clone(),
+            "--answers" => mock_server_arguments.answers = value.clone(),
+            "--fixture" => mock_server_arguments.fixture = value.clone(),
+            other => return Err(format!("unknown argument '{}'", other)),
+        }
+    }
+
+    Ok(mock_server_arguments)
+}
+
+/// Serves the known answers of one fixture until interrupted.
+fn mock_server(arguments: MockServerArguments) -> Result<String, String> {
+    let manifest = Manifest::parse(&fs::read_to_string(&arguments.answers).map_err(|error| format!("could not read {}: {}", arguments.answers, error))?)?;
+    let answers = manifest.entries()
+        .filter(|(_, fixture, _)| *fixture == arguments.fixture)
+        .flat_map(|(day, _, answers)| (1..=2).filter_map(move |part| Some(((day, part), answers.part(part)?.to_string()))))
+        .collect();
+
+    let server = MockServer::start(&arguments.address, answers).map_err(|error| format!("could not listen on {}: {}", arguments.address, error))?;
+    eprintln!("answering submissions for {} at {}", arguments.fixture, server.url());
+    server.wait();
+
+    Ok(String::new())
+}
+
+fn main() {
+    let arguments: Vec<String> = env::args().skip(1).collect();
+
+    let result = match arguments.first().map(|command| command.as_str()) {
+        Some("run") => parse_run_arguments(&arguments[1..]).and_then(run),
+        Some("bench") => parse_bench_arguments(&arguments[1..]).and_then(bench),
+        Some("all") => parse_all_arguments(&arguments[1..]).and_then(all),
+        Some("export") => parse_export_arguments(&arguments[1..]).and_then(export),
+        Some("fs") => parse_fs_arguments(&arguments[1..]).and_then(show_file_system),
+        Some("cleanup") => parse_cleanup_arguments(&arguments[1..]).and_then(plan_cleanup),
+        Some("transcript") => parse_transcript_arguments(&arguments[1..]).and_then(synthesize_transcript),
+        Some("cache") => parse_cache_arguments(&arguments[1..]).and_then(clear_cache),
+        Some("submit") => parse_submit_arguments(&arguments[1..]).and_then(submit),
+        Some("mock-server") => parse_mock_server_arguments(&arguments[1..]).and_then(mock_server),
+        Some("help") | Some("--help") | Some("-h") => {
+            println!("{}", USAGE);
+            return;
+        }
+        _ => Err(USAGE.to_string()),
+    };
+
+    match result {
+        Ok(answer) => println!("{}", answer),
+        Err(message) => {
+            eprintln!("error: {}", message);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! Answers kept on disk by day, part and a hash of the input, so that slow days are only solved
+//! once per input. Every entry records the version of the solver that produced it, and entries
+//! from another version are treated as missing.
+
+use std::path::PathBuf;
+use std::time::Duration;
+use std::{fs, io};
+use serde::{Deserialize, Serialize};
+use crate::solution::get_solver;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct Entry {
+    pub version: u32,
+    pub answer: String,
+    pub nanos: u64,
+}
+
+impl Entry {
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_nanos(self.nanos)
+    }
+}
+
+pub struct Cache {
+    directory: PathBuf,
+    refresh: bool,
+}
+
+/// 64 bit FNV-1a, stable across Rust versions unlike `DefaultHasher`.
+pub fn hash(input: &str) -> u64 {
+    input.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+impl Cache {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Cache { directory: directory.into(), refresh: false }
+    }
+
+    /// A cache that never answers but still stores, to replace whatever it holds.
+    pub fn refreshing(self) -> Self {
+        Cache { refresh: true, ..self }
+    }
+
+    fn path(&self, day: usize, part: usize, input: &str) -> PathBuf {
+        self.directory.join(format!("day{}", day)).join(format!("part{}-{:016x}.json", part, hash(input.trim_end())))
+    }
+
+    /// The cached answer, if there is one from the current version of the solver.
+    pub fn get(&self, day: usize, part: usize, input: &str) -> Option<Entry> {
+        if self.refresh {
+            return None;
+        }
+
+        let version = get_solver(day)?.version();
+        let entry: Entry = serde_json::from_str(&fs::read_to_string(self.path(day, part, input)).ok()?).ok()?;
+
+        (entry.version == version).then_some(entry)
+    }
+
+    pub fn put(&self, day: usize, part: usize, input: &str, answer: &str, elapsed: Duration) -> io::Result<()> {
+        let Some(solver) = get_solver(day) else {
+            return Ok(());
+        };
+        let entry = Entry { version: solver.version(), answer: answer.to_string(), nanos: elapsed.as_nanos() as u64 };
+        let path = self.path(day, part, input);
+
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, serde_json::to_string(&entry).unwrap())
+    }
+
+    /// Removes the entries of `day`, or all of them, returning how many there were.
+    pub fn clear(&self, day: Option<usize>) -> io::Result<usize> {
+        let directories = match day {
+            Some(day) => vec![self.directory.join(format!("day{}", day))],
+            None => match fs::read_dir(&self.directory) {
+                Ok(entries) => entries.map(|entry| entry.map(|entry| entry.path())).collect::<io::Result<_>>()?,
+                Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(0),
+                Err(error) => return Err(error),
+            },
+        };
+
+        let mut removed = 0;
+        for directory in directories.into_iter().filter(|directory| directory.is_dir()) {
+            removed += fs::read_dir(&directory)?.count();
+            fs::remove_dir_all(directory)?;
+        }
+
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temporary_cache(name: &str) -> Cache {
+        let directory = std::env::temp_dir().join(format!("aoc-cache-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+
+        Cache::new(directory)
+    }
+
+    #[test]
+    fn stores_answers_by_input() {
+        let cache = temporary_cache("answers");
+        cache.put(1, 1, "1\n2\n", "3", Duration::from_millis(5)).unwrap();
+
+        let entry = cache.get(1, 1, "1\n2").unwrap();
+        assert_eq!((entry.answer.as_str(), entry.elapsed()), ("3", Duration::from_millis(5)));
+        assert_eq!(cache.get(1, 2, "1\n2"), None);
+        assert_eq!(cache.get(1, 1, "1\n3"), None);
+        assert_eq!(cache.get(2, 1, "1\n2"), None);
+
+        cache.put(2, 1, "A X", "4", Duration::ZERO).unwrap();
+        assert_eq!(cache.clear(Some(1)).unwrap(), 1);
+        assert_eq!(cache.get(1, 1, "1\n2"), None);
+        assert!(cache.get(2, 1, "A X").is_some());
+        assert_eq!(cache.clear(None).unwrap(), 1);
+        assert_eq!(cache.clear(None).unwrap(), 0);
+    }
+
+    #[test]
+    fn ignores_other_versions() {
+        let cache = temporary_cache("versions");
+        cache.put(6, 1, "abcd", "4", Duration::ZERO).unwrap();
+
+        let path = cache.path(6, 1, "abcd");
+        let stale = Entry { version: get_solver(6).unwrap().version() + 1, answer: "5".to_string(), nanos: 0 };
+        fs::write(&path, serde_json::to_string(&stale).unwrap()).unwrap();
+
+        assert_eq!(cache.get(6, 1, "abcd"), None);
+
+        let cache = cache.refreshing();
+        cache.put(6, 1, "abcd", "4", Duration::ZERO).unwrap();
+        assert_eq!(cache.get(6, 1, "abcd"), None);
+        assert_eq!(Cache::new(&cache.directory).get(6, 1, "abcd").map(|entry| entry.answer), Some("4".to_string()));
+        cache.clear(None).unwrap();
+    }
+
+    #[test]
+    fn bumped_solvers_miss_their_old_answers() {
+        let cache = temporary_cache("bumped");
+        let old = Entry { version: 1, answer: "0".to_string(), nanos: 0 };
+        for day in [1, 12] {
+            let path = cache.path(day, 1, "SbE");
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, serde_json::to_string(&old).unwrap()).unwrap();
+        }
+
+        // Day 12 used to answer 0 when E cannot be reached, day 1 has not changed since.
+        assert_eq!(cache.get(12, 1, "SbE"), None);
+        assert_eq!(cache.get(1, 1, "SbE").map(|entry| entry.answer), Some("0".to_string()));
+        cache.clear(None).unwrap();
+    }
+}
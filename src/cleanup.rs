@@ -0,0 +1,372 @@
+//! Plans which directories and files to delete from a day 7 file system to get enough free space,
+//! optimally for either the bytes or the number of entries deleted. Deleted entries never nest, and
+//! the root itself is never deleted.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use crate::vfs::{FileSystem, NodeId, NodeType};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Objective {
+    /// Deletes as few bytes as possible.
+    Bytes,
+    /// Deletes as few files and directories as possible, then as few bytes as possible.
+    Items,
+}
+
+impl FromStr for Objective {
+    type Err = String;
+
+    fn from_str(objective: &str) -> Result<Self, Self::Err> {
+        match objective {
+            "bytes" => Ok(Objective::Bytes),
+            "items" => Ok(Objective::Items),
+            _ => Err(format!("unknown objective '{}', expected bytes or items", objective)),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Plan {
+    /// The entries to delete, by path.
+    pub deletions: Vec<NodeId>,
+    pub deleted: usize,
+    pub free_before: usize,
+    pub free_after: usize,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum CleanupError {
+    OverCapacity { used: usize, capacity: usize },
+    NotEnoughSpace { required: usize, capacity: usize },
+}
+
+impl Display for CleanupError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CleanupError::OverCapacity { used, capacity } => write!(f, "{} bytes are used on a disk of {}", used, capacity),
+            CleanupError::NotEnoughSpace { required, capacity } => write!(f, "cannot free {} bytes on a disk of {}", required, capacity),
+        }
+    }
+}
+
+impl std::error::Error for CleanupError {}
+
+/// The deletions that leave at least `required` bytes free on a disk of `capacity` bytes.
+pub fn plan(fs: &FileSystem, capacity: usize, required: usize, objective: Objective) -> Result<Plan, CleanupError> {
+    let used = fs.size(FileSystem::ROOT);
+    if used > capacity {
+        return Err(CleanupError::OverCapacity { used, capacity });
+    }
+    if required > capacity {
+        return Err(CleanupError::NotEnoughSpace { required, capacity });
+    }
+
+    let free_before = capacity - used;
+    let need = required.saturating_sub(free_before);
+    let mut deletions = match (need, objective) {
+        (0, _) => vec![],
+        (_, Objective::Bytes) => fold_directories(fs, &fewest_bytes(fs, need)),
+        (_, Objective::Items) => fewest_items(fs, need),
+    };
+    deletions.sort_by_key(|id| fs.path(*id));
+
+    let deleted = deletions.iter().map(|id| fs.size(*id)).sum::<usize>();
+    Ok(Plan { deletions, deleted, free_before, free_after: free_before + deleted })
+}
+
+/// Deleting a directory frees exactly the bytes of its files, so the fewest bytes come from the
+/// subset of files whose sizes add up to the smallest sum of at least `need`.
+fn fewest_bytes(fs: &FileSystem, need: usize) -> HashSet<NodeId> {
+    let files: Vec<(NodeId, usize)> = fs.walk(FileSystem::ROOT)
+        .filter(|id| fs.node_type(*id) == NodeType::File)
+        .map(|id| (id, fs.size(id)))
+        .filter(|(_, size)| *size > 0)
+        .collect();
+    let sizes: Vec<usize> = files.iter().map(|(_, size)| *size).collect();
+    let total: usize = sizes.iter().sum();
+    let largest = sizes.iter().copied().max().unwrap_or(0);
+
+    // A smallest sum is less than a file above `need`, and what is kept is at most `total - need`,
+    // so the table only needs the smaller of the two ranges. When both are too large for a table,
+    // a search takes its place.
+    let chosen = if need + largest > MAX_SUMS && total - need + 1 > MAX_SUMS {
+        smallest_sum(&sizes, need)
+    } else if need + largest <= total - need + 1 {
+        let sums = SubsetSums::new(&sizes, need + largest);
+        let sum = (need..need + largest).find(|sum| sums.contains(*sum)).expect("all files together free enough");
+
+        sums.items(sum)
+    } else {
+        let sums = SubsetSums::new(&sizes, total - need + 1);
+        let kept_sum = (0..=total - need).rev().find(|sum| sums.contains(*sum)).unwrap_or(0);
+        let kept: HashSet<usize> = sums.items(kept_sum).into_iter().collect();
+
+        (0..files.len()).filter(|i| !kept.contains(i)).collect()
+    };
+
+    chosen.into_iter().map(|i| files[i].0).collect()
+}
+
+/// Replaces the files of every directory that loses all of its non-empty files by the directory.
+fn fold_directories(fs: &FileSystem, files: &HashSet<NodeId>) -> Vec<NodeId> {
+    // Every directory with the number of its non-empty files and how many of those are deleted.
+    let mut counts: HashMap<NodeId, (usize, usize)> = HashMap::new();
+    for id in fs.walk(FileSystem::ROOT).filter(|id| fs.node_type(*id) == NodeType::File && fs.size(*id) > 0) {
+        let deleted = files.contains(&id) as usize;
+        for ancestor in std::iter::successors(fs.parent(id), |parent| fs.parent(*parent)) {
+            let count = counts.entry(ancestor).or_default();
+            *count = (count.0 + 1, count.1 + deleted);
+        }
+    }
+
+    let mut deletions = vec![];
+    let mut stack: Vec<NodeId> = fs.children(FileSystem::ROOT).collect();
+    while let Some(id) = stack.pop() {
+        match counts.get(&id) {
+            _ if files.contains(&id) => deletions.push(id),
+            Some((all, deleted)) if all == deleted => deletions.push(id),
+            Some((_, deleted)) if *deleted > 0 => stack.extend(fs.children(id)),
+            _ => {}
+        }
+    }
+
+    deletions
+}
+
+/// No `k` entries free more than the `k` largest entries of the root, which gives the fewest
+/// entries. A bounded search then finds the fewest bytes that many non-nested entries can free.
+fn fewest_items(fs: &FileSystem, need: usize) -> Vec<NodeId> {
+    let mut top: Vec<NodeId> = fs.children(FileSystem::ROOT).collect();
+    top.sort_by_key(|id| std::cmp::Reverse(fs.size(*id)));
+
+    let count = (1..=top.len())
+        .find(|count| top[..*count].iter().map(|id| fs.size(*id)).sum::<usize>() >= need)
+        .expect("all entries together free enough");
+
+    let mut candidates: Vec<(usize, NodeId)> = fs.walk(FileSystem::ROOT)
+        .skip(1)
+        .map(|id| (fs.size(id), id))
+        .filter(|(size, _)| *size > 0)
+        .collect();
+    candidates.sort_by(|(size, id), (other_size, other_id)| other_size.cmp(size).then(id.cmp(other_id)));
+
+    let mut search = Search { fs, need, candidates, chosen: vec![], best: (top[..count].iter().map(|id| fs.size(*id)).sum(), top[..count].to_vec()) };
+    search.run(0, 0, count);
+
+    search.best.1
+}
+
+struct Search<'a> {
+    fs: &'a FileSystem,
+    need: usize,
+    /// Every entry but the root, largest first.
+    candidates: Vec<(usize, NodeId)>,
+    chosen: Vec<NodeId>,
+    best: (usize, Vec<NodeId>),
+}
+
+impl Search<'_> {
+    fn is_nested(&self, id: NodeId) -> bool {
+        self.chosen.iter().any(|chosen| {
+            let is_under = |node: NodeId, ancestor: NodeId| std::iter::successors(Some(node), |node| self.fs.parent(*node)).any(|node| node == ancestor);
+
+            is_under(id, *chosen) || is_under(*chosen, id)
+        })
+    }
+
+    fn run(&mut self, start: usize, sum: usize, left: usize) {
+        for i in start..self.candidates.len() {
+            let (size, id) = self.candidates[i];
+
+            // The candidates only get smaller, so none of the rest can make up the difference.
+            if sum + size * left < self.need {
+                break;
+            }
+            if sum + size >= self.best.0 || self.is_nested(id) {
+                continue;
+            }
+
+            self.chosen.push(id);
+            if sum + size >= self.need {
+                self.best = (sum + size, self.chosen.clone());
+            } else if left > 1 {
+                self.run(i + 1, sum + size, left - 1);
+            }
+            self.chosen.pop();
+        }
+    }
+}
+
+/// The most sums a [SubsetSums] table is made for, which takes about 16 MB.
+const MAX_SUMS: usize = 1 << 22;
+
+/// The items of a subset of `sizes` adding up to the smallest sum of at least `need`, which all of
+/// them together must reach, for when the sums are too many for a table.
+fn smallest_sum(sizes: &[usize], need: usize) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by_key(|item| std::cmp::Reverse(sizes[*item]));
+
+    let mut rest = vec![0; order.len() + 1];
+    for i in (0..order.len()).rev() {
+        rest[i] = rest[i + 1] + sizes[order[i]];
+    }
+
+    let mut search = SumSearch { sizes, need, best: (rest[0], order.clone()), order, rest, chosen: vec![] };
+    search.run(0, 0);
+
+    search.best.1
+}
+
+struct SumSearch<'a> {
+    sizes: &'a [usize],
+    need: usize,
+    /// The items, largest first.
+    order: Vec<usize>,
+    /// What the items from every position in `order` on add up to.
+    rest: Vec<usize>,
+    chosen: Vec<usize>,
+    best: (usize, Vec<usize>),
+}
+
+impl SumSearch<'_> {
+    fn run(&mut self, start: usize, sum: usize) {
+        for i in start..self.order.len() {
+            // Nothing beats freeing exactly enough, and the rest cannot make up the difference.
+            if self.best.0 == self.need || sum + self.rest[i] < self.need {
+                break;
+            }
+
+            let item = self.order[i];
+            let size = self.sizes[item];
+            if sum + size >= self.best.0 {
+                continue;
+            }
+
+            self.chosen.push(item);
+            if sum + size >= self.need {
+                self.best = (sum + size, self.chosen.clone());
+            } else {
+                self.run(i + 1, sum + size);
+            }
+            self.chosen.pop();
+        }
+    }
+}
+
+/// The sums that subsets of `sizes` add up to below `limit`, remembering for every sum the first
+/// item that reached it, which is enough to take the subset apart again.
+struct SubsetSums<'a> {
+    sizes: &'a [usize],
+    reachable: Vec<u64>,
+    first: Vec<u32>,
+}
+
+impl<'a> SubsetSums<'a> {
+    fn new(sizes: &'a [usize], limit: usize) -> Self {
+        let words = limit.div_ceil(64);
+        let mut reachable = vec![0u64; words];
+        let mut first = vec![u32::MAX; limit];
+        reachable[0] = 1;
+
+        for (item, size) in sizes.iter().enumerate() {
+            let (shift_words, shift_bits) = (size / 64, size % 64);
+
+            // Going down, every word is shifted in from lower words that still hold the old sums.
+            for word in (shift_words..words).rev() {
+                let low = word - shift_words;
+                let mut shifted = reachable[low] << shift_bits;
+                if shift_bits > 0 && low > 0 {
+                    shifted |= reachable[low - 1] >> (64 - shift_bits);
+                }
+                if word + 1 == words && !limit.is_multiple_of(64) {
+                    shifted &= (1 << (limit % 64)) - 1;
+                }
+
+                let mut new = shifted & !reachable[word];
+                reachable[word] |= new;
+                while new != 0 {
+                    first[word * 64 + new.trailing_zeros() as usize] = item as u32;
+                    new &= new - 1;
+                }
+            }
+        }
+
+        SubsetSums { sizes, reachable, first }
+    }
+
+    fn contains(&self, sum: usize) -> bool {
+        self.reachable.get(sum / 64).is_some_and(|word| word >> (sum % 64) & 1 == 1)
+    }
+
+    /// The items of a subset adding up to `sum`, which must be reachable.
+    fn items(&self, mut sum: usize) -> Vec<usize> {
+        let mut items = vec![];
+
+        while sum > 0 {
+            let item = self.first[sum] as usize;
+            items.push(item);
+            sum -= self.sizes[item];
+        }
+
+        items
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(fs: &FileSystem, plan: &Plan) -> Vec<String> {
+        plan.deletions.iter().map(|id| fs.path(*id)).collect()
+    }
+
+    #[test]
+    fn objectives_pick_different_deletions() {
+        let fs = FileSystem::replay("$ ls\n100 a\ndir d\n$ cd d\n$ ls\n40 b\n21 c\n5 x").unwrap();
+
+        let plan = plan(&fs, 200, 94, Objective::Bytes).unwrap();
+        assert_eq!((paths(&fs, &plan), plan.deleted, plan.free_before, plan.free_after), (vec!["/d/b".to_string(), "/d/c".to_string()], 61, 34, 95));
+
+        let plan = super::plan(&fs, 200, 94, Objective::Items).unwrap();
+        assert_eq!((paths(&fs, &plan), plan.deleted), (vec!["/d".to_string()], 66));
+
+        let plan = super::plan(&fs, 200, 100, Objective::Bytes).unwrap();
+        assert_eq!((paths(&fs, &plan), plan.deleted), (vec!["/d".to_string()], 66));
+
+        let plan = super::plan(&fs, 200, 180, Objective::Items).unwrap();
+        assert_eq!((paths(&fs, &plan), plan.deleted), (vec!["/a".to_string(), "/d".to_string()], 166));
+
+        assert_eq!(super::plan(&fs, 200, 30, Objective::Bytes).unwrap().deletions, []);
+        assert_eq!(super::plan(&fs, 200, 201, Objective::Bytes), Err(CleanupError::NotEnoughSpace { required: 201, capacity: 200 }));
+        assert_eq!(super::plan(&fs, 100, 0, Objective::Items), Err(CleanupError::OverCapacity { used: 166, capacity: 100 }));
+    }
+
+    #[test]
+    fn frees_just_enough_on_the_example() {
+        let fs = FileSystem::replay(include_str!("day7/test_simple.txt")).unwrap();
+
+        for objective in [Objective::Bytes, Objective::Items] {
+            let plan = plan(&fs, 70000000, 30000000, objective).unwrap();
+            assert_eq!((paths(&fs, &plan), plan.free_after), (vec!["/c.dat".to_string()], 30122991));
+        }
+
+        // Keeping rather than deleting is the smaller table here.
+        let plan = plan(&fs, 48381165, 48351165, Objective::Bytes).unwrap();
+        assert_eq!((paths(&fs, &plan), plan.free_after), (vec!["/a/g".to_string(), "/a/h.lst".to_string(), "/b.txt".to_string(), "/c.dat".to_string(), "/d".to_string()], 48381165 - 29700));
+    }
+
+    #[test]
+    fn search_finds_the_smallest_sums_of_the_table() {
+        let sizes = [14848514, 8504156, 29116, 2557, 62596, 584, 4060174, 8033020, 5626152, 7214296];
+        let total: usize = sizes.iter().sum();
+        let sums = SubsetSums::new(&sizes, total + 1);
+
+        for need in [1, 585, 3000, 62596, 100000, 4060175, 24933642, total - 584, total] {
+            let smallest = (need..=total).find(|sum| sums.contains(*sum)).unwrap();
+
+            assert_eq!(smallest_sum(&sizes, need).into_iter().map(|item| sizes[item]).sum::<usize>(), smallest);
+        }
+    }
+}
@@ -0,0 +1,221 @@
+//! Cranes that rearrange the day 5 stacks of crates, and a simulator that keeps every state the
+//! stacks go through so that moves can be undone and redone.
+
+use std::fmt::{Display, Formatter};
+use std::num::NonZeroUsize;
+
+/// Every stack of crates, bottom first.
+pub type Stacks = Vec<Vec<char>>;
+
+/// How many crates to move, from which stack and to which, counting stacks from 1 like the puzzle.
+pub type Move = (usize, usize, usize);
+
+pub trait Crane {
+    /// The order crates land in, bottom first, given the order they had on top of their stack.
+    fn land(&self, lifted: Vec<char>) -> Vec<char>;
+}
+
+/// Lifts one crate at a time, which turns the moved crates upside down.
+#[derive(Clone, Copy, Debug)]
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn land(&self, mut lifted: Vec<char>) -> Vec<char> {
+        lifted.reverse();
+        lifted
+    }
+}
+
+/// Lifts all the moved crates at once, which keeps their order.
+#[derive(Clone, Copy, Debug)]
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn land(&self, lifted: Vec<char>) -> Vec<char> {
+        lifted
+    }
+}
+
+/// Lifts up to a number of crates at once, keeping the order within each lift.
+#[derive(Clone, Copy, Debug)]
+pub struct Batched(pub NonZeroUsize);
+
+impl Crane for Batched {
+    fn land(&self, lifted: Vec<char>) -> Vec<char> {
+        lifted.rchunks(self.0.get()).flatten().copied().collect()
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum CraneError {
+    NoSuchStack { step: usize, stack: usize, stacks: usize },
+    NotEnoughCrates { step: usize, stack: usize, wanted: usize, held: usize },
+}
+
+impl CraneError {
+    /// The number of the move that cannot be made, counting from 1.
+    pub fn step(&self) -> usize {
+        match self {
+            CraneError::NoSuchStack { step, .. } | CraneError::NotEnoughCrates { step, .. } => *step,
+        }
+    }
+}
+
+impl Display for CraneError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CraneError::NoSuchStack { step, stack, stacks } =>
+                write!(f, "move {} uses stack {}, but there are only stacks 1 to {}", step, stack, stacks),
+            CraneError::NotEnoughCrates { step, stack, wanted, held } =>
+                write!(f, "move {} takes {} crates from stack {}, which only holds {}", step, wanted, stack, held),
+        }
+    }
+}
+
+impl std::error::Error for CraneError {}
+
+/// Whether `step`, the move with that number counting from 1, can be made on stacks of `heights`.
+fn check_move(step: usize, heights: &[usize], (count, from, to): Move) -> Result<(), CraneError> {
+    if let Some(stack) = [from, to].into_iter().find(|stack| *stack == 0 || *stack > heights.len()) {
+        return Err(CraneError::NoSuchStack { step, stack, stacks: heights.len() });
+    }
+    if heights[from - 1] < count {
+        return Err(CraneError::NotEnoughCrates { step, stack: from, wanted: count, held: heights[from - 1] });
+    }
+
+    Ok(())
+}
+
+/// Whether all of `moves` can be made in turn. Every crane leaves as many crates on each stack.
+pub fn check(stacks: &Stacks, moves: &[Move]) -> Result<(), CraneError> {
+    let mut heights: Vec<usize> = stacks.iter().map(|stack| stack.len()).collect();
+
+    for (i, &(count, from, to)) in moves.iter().enumerate() {
+        check_move(i + 1, &heights, (count, from, to))?;
+        heights[from - 1] -= count;
+        heights[to - 1] += count;
+    }
+
+    Ok(())
+}
+
+pub struct Simulator<C: Crane> {
+    crane: C,
+    /// The stacks before the first move and after every move since, including undone moves.
+    history: Vec<Stacks>,
+    current: usize,
+}
+
+impl<C: Crane> Simulator<C> {
+    pub fn new(crane: C, stacks: Stacks) -> Self {
+        Simulator { crane, history: vec![stacks], current: 0 }
+    }
+
+    pub fn stacks(&self) -> &Stacks {
+        &self.history[self.current]
+    }
+
+    /// How many moves were made and not undone.
+    pub fn steps(&self) -> usize {
+        self.current
+    }
+
+    /// Every state so far, the current one at `steps()` followed by any that `redo` brings back.
+    pub fn history(&self) -> &[Stacks] {
+        &self.history
+    }
+
+    /// Makes a move, which can then no longer redo what was undone. A failed move changes nothing.
+    pub fn apply(&mut self, (count, from, to): Move) -> Result<&Stacks, CraneError> {
+        let mut stacks = self.stacks().clone();
+        let heights: Vec<usize> = stacks.iter().map(|stack| stack.len()).collect();
+        check_move(self.current + 1, &heights, (count, from, to))?;
+
+        let lifted = stacks[from - 1].split_off(heights[from - 1] - count);
+        stacks[to - 1].extend(self.crane.land(lifted));
+
+        self.history.truncate(self.current + 1);
+        self.history.push(stacks);
+        self.current += 1;
+
+        Ok(self.stacks())
+    }
+
+    /// Makes every move in turn, stopping at the first that cannot be made.
+    pub fn run(&mut self, moves: impl IntoIterator<Item = Move>) -> Result<&Stacks, CraneError> {
+        for command in moves {
+            self.apply(command)?;
+        }
+
+        Ok(self.stacks())
+    }
+
+    /// Goes back a move, returning whether there was one.
+    pub fn undo(&mut self) -> bool {
+        let undone = self.current > 0;
+        self.current -= undone as usize;
+
+        undone
+    }
+
+    /// Makes the last undone move again, returning whether there was one.
+    pub fn redo(&mut self) -> bool {
+        let redone = self.current + 1 < self.history.len();
+        self.current += redone as usize;
+
+        redone
+    }
+
+    /// The crate on top of each stack, leaving out empty stacks.
+    pub fn tops(&self) -> String {
+        self.stacks().iter().filter_map(|stack| stack.last()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stacks() -> Stacks {
+        vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]
+    }
+
+    #[test]
+    fn cranes_land_crates_in_their_own_order() {
+        let lifted = vec!['a', 'b', 'c', 'd', 'e'];
+
+        assert_eq!(CrateMover9000.land(lifted.clone()), ['e', 'd', 'c', 'b', 'a']);
+        assert_eq!(CrateMover9001.land(lifted.clone()), ['a', 'b', 'c', 'd', 'e']);
+        assert_eq!(Batched(NonZeroUsize::new(2).unwrap()).land(lifted.clone()), ['d', 'e', 'b', 'c', 'a']);
+        assert_eq!(Batched(NonZeroUsize::new(1).unwrap()).land(lifted.clone()), CrateMover9000.land(lifted));
+    }
+
+    #[test]
+    fn undoes_and_redoes_moves() {
+        let mut simulator = Simulator::new(CrateMover9000, stacks());
+        simulator.run([(1, 2, 1), (3, 1, 3)]).unwrap();
+        assert_eq!((simulator.tops(), simulator.steps()), ("CZ".to_string(), 2));
+
+        assert!(simulator.undo());
+        assert_eq!(simulator.stacks(), &vec![vec!['Z', 'N', 'D'], vec!['M', 'C'], vec!['P']]);
+        assert!(simulator.undo() && !simulator.undo());
+        assert!(simulator.redo() && simulator.redo() && !simulator.redo());
+        assert_eq!(simulator.stacks()[2], ['P', 'D', 'N', 'Z']);
+
+        simulator.undo();
+        simulator.apply((2, 2, 3)).unwrap();
+        assert_eq!((simulator.history().len(), simulator.redo()), (3, false));
+        assert_eq!(simulator.tops(), "DM");
+    }
+
+    #[test]
+    fn reports_impossible_moves() {
+        let mut simulator = Simulator::new(CrateMover9001, stacks());
+
+        assert_eq!(simulator.run([(2, 1, 2), (6, 2, 3)]), Err(CraneError::NotEnoughCrates { step: 2, stack: 2, wanted: 6, held: 5 }));
+        assert_eq!(simulator.steps(), 1);
+        assert_eq!(simulator.apply((1, 4, 1)), Err(CraneError::NoSuchStack { step: 2, stack: 4, stacks: 3 }));
+        assert_eq!(check(&stacks(), &[(1, 0, 1)]), Err(CraneError::NoSuchStack { step: 1, stack: 0, stacks: 3 }));
+        assert_eq!(check(&stacks(), &[(1, 3, 1), (1, 3, 2)]).unwrap_err().to_string(), "move 2 takes 1 crates from stack 3, which only holds 0");
+    }
+}
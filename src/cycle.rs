@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A sequence that repeats every `length` steps once `start` steps have been taken.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Value of a metric after `step` steps, given its value after every step up to at least one full cycle.
+    pub fn extrapolate(&self, history: &[i64], step: usize) -> i64 {
+        if step < history.len() {
+            return history[step];
+        }
+
+        let per_cycle = history[self.start + self.length] - history[self.start];
+        let cycles = (step - self.start) / self.length;
+        let offset = (step - self.start) % self.length;
+
+        history[self.start + offset] + cycles as i64 * per_cycle
+    }
+}
+
+/// Finds a cycle by remembering every fingerprint, for simulations that are driven step by step.
+pub struct CycleDetector<K> {
+    seen: HashMap<K, usize>,
+    history: Vec<i64>,
+}
+
+impl<K: Hash + Eq> CycleDetector<K> {
+    pub fn new() -> Self {
+        CycleDetector {
+            seen: HashMap::new(),
+            history: Vec::new(),
+        }
+    }
+
+    /// Records the state after the next step, the first call being the state before any step.
+    pub fn record(&mut self, fingerprint: K, metric: i64) -> Option<Cycle> {
+        let step = self.history.len();
+        self.history.push(metric);
+
+        self.seen.insert(fingerprint, step).map(|start| Cycle { start, length: step - start })
+    }
+
+    pub fn extrapolate(&self, cycle: Cycle, step: usize) -> i64 {
+        cycle.extrapolate(&self.history, step)
+    }
+}
+
+impl<K: Hash + Eq> Default for CycleDetector<K> {
+    fn default() -> Self {
+        CycleDetector::new()
+    }
+}
+
+/// Brent's algorithm, using constant memory. Never returns if the sequence does not eventually repeat.
+pub fn brent<S: Clone, K: PartialEq>(initial: S, mut step: impl FnMut(&S) -> S, mut fingerprint: impl FnMut(&S) -> K) -> Cycle {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = fingerprint(&initial);
+    let mut hare = step(&initial);
+    let mut hare_fingerprint = fingerprint(&hare);
+
+    while hare_fingerprint != tortoise {
+        if power == length {
+            tortoise = hare_fingerprint;
+            power *= 2;
+            length = 0;
+        }
+
+        hare = step(&hare);
+        hare_fingerprint = fingerprint(&hare);
+        length += 1;
+    }
+
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    while fingerprint(&tortoise) != fingerprint(&hare) {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn next(value: &u64) -> u64 {
+        (value * value + 1) % 255
+    }
+
+    #[test]
+    fn detectors_agree() {
+        let mut detector = CycleDetector::new();
+        let mut value = 3;
+        let cycle = loop {
+            if let Some(cycle) = detector.record(value, 0) {
+                break cycle;
+            }
+            value = next(&value);
+        };
+
+        assert_eq!(brent(3, next, |value| *value), cycle);
+        assert_eq!(brent(0, |value| (value + 1) % 5, |value| *value), Cycle { start: 0, length: 5 });
+    }
+
+    #[test]
+    fn extrapolation() {
+        // 0, 1, 2, then 5, 7, 9 repeating, gaining 10 per cycle.
+        let history = [0, 1, 2, 5, 7, 9, 15];
+        let cycle = Cycle { start: 3, length: 3 };
+
+        assert_eq!(cycle.extrapolate(&history, 1), 1);
+        assert_eq!(cycle.extrapolate(&history, 7), 17);
+        assert_eq!(cycle.extrapolate(&history, 3 + 3 * 1000 + 2), 10009);
+    }
+}
@@ -1,22 +1,51 @@
-fn get_max_calories(calories_string: &str, amount: usize) -> i32 {
-    let mut calorie_lists: Vec<i32> = calories_string
-        .split("\n\n")
-        .map(|elf_list_string| get_calories_of_elf(elf_list_string))
-        .collect();
+use crate::error::{parse_number, ParseError};
+use crate::observer::Observer;
+use crate::parse;
+use crate::solution::Solution;
+
+fn parse_calories(calories_string: &str) -> Result<Vec<i32>, ParseError> {
+    parse::blocks(calories_string)
+        .map(|elf_list_string| get_calories_of_elf(calories_string, elf_list_string))
+        .collect()
+}
 
+fn get_largest_sum(mut calorie_lists: Vec<i32>, amount: usize) -> i32 {
     calorie_lists.sort();
     calorie_lists.reverse();
 
     return calorie_lists.into_iter().take(amount).sum()
 }
 
-fn get_calories_of_elf(elf_calories_string: &str) -> i32 {
+fn get_calories_of_elf(calories_string: &str, elf_calories_string: &str) -> Result<i32, ParseError> {
     return elf_calories_string
         .split("\n")
-        .map(|calorie_string| calorie_string.parse::<i32>().unwrap_or(0))
+        .filter(|calorie_string| !calorie_string.trim().is_empty())
+        .map(|calorie_string| parse_number::<i32>(Day1::DAY, calories_string, calorie_string))
         .sum();
 }
 
+pub(crate) struct Day1;
+
+impl Solution for Day1 {
+    const DAY: usize = 1;
+
+    type Parsed = Vec<i32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_calories(input)
+    }
+
+    fn part1(calories: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer1 {
+        get_largest_sum(calories, 1)
+    }
+
+    fn part2(calories: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer2 {
+        get_largest_sum(calories, 3)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -38,7 +67,7 @@ mod tests {
 9000
 
 10000";
-        assert_eq!(get_max_calories(calories_string, 1), 24000);
+        assert_eq!(get_largest_sum(parse_calories(calories_string).unwrap(), 1), 24000);
     }
 
     #[test]
@@ -58,11 +87,11 @@ mod tests {
 9000
 
 100000";
-        assert_eq!(get_max_calories(calories_string, 1), 100000);
+        assert_eq!(get_largest_sum(parse_calories(calories_string).unwrap(), 1), 100000);
     }
 
     #[test]
-    fn calorie_count_invalid_ignored() {
+    fn calorie_count_invalid_is_error() {
         let calories_string = "
 1000
 2000
@@ -80,13 +109,15 @@ mod tests {
 100000
 blabla
 ";
-        assert_eq!(get_max_calories(calories_string, 1), 100000);
+        let error = parse_calories(calories_string).unwrap_err();
+
+        assert_eq!((error.line, error.column, error.text.as_str()), (16, 1, "blabla"));
     }
 
     #[test]
     fn calorie_count_nothing_found_max_is_0() {
         let calories_string = "";
-        assert_eq!(get_max_calories(calories_string, 1), 0);
+        assert_eq!(get_largest_sum(parse_calories(calories_string).unwrap(), 1), 0);
     }
 
     #[test]
@@ -107,6 +138,6 @@ blabla
 
 100000
 ";
-        assert_eq!(get_max_calories(calories_string, 2), 124000);
+        assert_eq!(get_largest_sum(parse_calories(calories_string).unwrap(), 2), 124000);
     }
 }
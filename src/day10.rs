@@ -6,23 +6,33 @@ use std::cell::{Ref, RefCell, RefMut};
 use std::ops::{Deref, Range};
 use std::{cmp, ops};
 use itertools::Itertools;
+use crate::animate::{Animation, Cell, Frame, Frames};
+use crate::error::{parse_number, ParseError};
+use crate::observer::Observer;
+use crate::solution::Solution;
+use crate::vector2::Vector2i;
 
-fn execute_program(program_string: &str) -> Vec<i32> {
+fn execute_program(program_string: &str) -> Result<Vec<i32>, ParseError> {
     program_string
         .lines()
-        .fold(vec![1], |mut cycles, line | {
-            cycles.append(&mut execute_line(line, *cycles.last().unwrap()));
-            cycles
+        .try_fold(vec![1], |mut cycles, line | {
+            let mut line_cycles = execute_line(line, *cycles.last().unwrap())
+                .map_err(|error| error.relative_to(program_string, line))?;
+            cycles.append(&mut line_cycles);
+            Ok(cycles)
         })
 }
 
-fn execute_line(program_line: &str, current_x: i32) -> Vec<i32> {
+fn execute_line(program_line: &str, current_x: i32) -> Result<Vec<i32>, ParseError> {
     let mut tokens = program_line.split(" ");
 
     return match tokens.next().unwrap() {
-        "noop" => vec![current_x],
-        "addx" => vec![current_x, current_x + tokens.next().unwrap().parse::<i32>().unwrap()],
-        _ => panic!()
+        "noop" => Ok(vec![current_x]),
+        "addx" => {
+            let operand = tokens.next().ok_or_else(|| ParseError::missing(Day10::DAY, program_line, "expected a number"))?;
+            Ok(vec![current_x, current_x + parse_number::<i32>(Day10::DAY, program_line, operand)?])
+        },
+        instruction => Err(ParseError::new(Day10::DAY, program_line, instruction, "unknown instruction"))
     }
 }
 
@@ -45,6 +55,51 @@ fn to_pixel(i: usize, el: i32, line_width: usize) -> &'static str {
     if (i_int % line_width_int - 1)  <= el && el <= (i_int % line_width_int + 1)  {"#"} else { "." }
 }
 
+/// The picture as the CRT draws it, one pixel per cycle. The last value is the register after the
+/// last cycle, so it is not drawn.
+fn crt_frames(cycles: Vec<i32>, line_width: usize) -> Frames {
+    let drawn = cycles.len().saturating_sub(1);
+    let max = Vector2i{x: line_width as i32 - 1, y: drawn.div_ceil(line_width) as i32 - 1};
+
+    Box::new((1..=drawn).map(move |step| Frame::from_fn(step, Vector2i{x: 0, y: 0}, max, |pos| {
+        let i = pos.y as usize * line_width + pos.x as usize;
+
+        match i < step && i < drawn {
+            false => Cell::Void,
+            true if to_pixel(i, cycles[i], line_width) == "#" => Cell::Lit,
+            true => Cell::Empty,
+        }
+    })))
+}
+
+pub(crate) struct Day10;
+
+impl Solution for Day10 {
+    const DAY: usize = 10;
+
+    type Parsed = Vec<i32>;
+    type Answer1 = i32;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        execute_program(input)
+    }
+
+    fn part1(cycles: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer1 {
+        count_signal_strength(cycles)
+    }
+
+    fn part2(cycles: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer2 {
+        crt(cycles, 40)
+    }
+}
+
+impl Animation for Day10 {
+    fn frames(cycles: Self::Parsed, _part: usize) -> Frames {
+        crt_frames(cycles, 40)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{fs};
@@ -52,17 +107,17 @@ mod tests {
 
     #[test]
     fn simple_test() {
-        assert_eq!(count_signal_strength(execute_program(fs::read_to_string("src/day10/test_simple.txt").unwrap().as_str())), 13140);
+        assert_eq!(count_signal_strength(execute_program(fs::read_to_string("src/day10/test_simple.txt").unwrap().as_str()).unwrap()), 13140);
     }
 
     #[test]
     fn large_test() {
-        assert_eq!(count_signal_strength(execute_program(fs::read_to_string("src/day10/test_large.txt").unwrap().as_str())), 13140);
+        assert_eq!(count_signal_strength(execute_program(fs::read_to_string("src/day10/test_large.txt").unwrap().as_str()).unwrap()), 13920);
     }
 
     #[test]
     fn simple_test_2() {
-        assert_eq!(crt(execute_program(fs::read_to_string("src/day10/test_simple.txt").unwrap().as_str()), 40),
+        assert_eq!(crt(execute_program(fs::read_to_string("src/day10/test_simple.txt").unwrap().as_str()).unwrap(), 40),
                    "##..##..##..##..##..##..##..##..##..##..
 ###...###...###...###...###...###...###.
 ####....####....####....####....####....
@@ -74,6 +129,15 @@ mod tests {
     }
 
     #[test]
-    fn large_test_2() { println!("{}", crt(execute_program(fs::read_to_string("src/day10/test_large.txt").unwrap().as_str()), 40));
+    fn large_test_2() {
+        assert_eq!(crt(execute_program(fs::read_to_string("src/day10/test_large.txt").unwrap().as_str()).unwrap(), 40),
+                   "####..##..#....#..#.###..#....####...##.
+#....#..#.#....#..#.#..#.#....#.......#.
+###..#....#....####.###..#....###.....#.
+#....#.##.#....#..#.#..#.#....#.......#.
+#....#..#.#....#..#.#..#.#....#....#..#.
+####..###.####.#..#.###..####.#.....##..
+."
+        );
     }
 }
\ No newline at end of file
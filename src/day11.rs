@@ -6,8 +6,12 @@ use std::cell::{Ref, RefCell, RefMut};
 use std::ops::{Deref, Range};
 use std::{cmp, ops};
 use itertools::Itertools;
+use crate::error::{parse_number, ParseError};
+use crate::observer::Observer;
+use crate::parse;
+use crate::solution::Solution;
 
-struct Monkey {
+pub(crate) struct Monkey {
     items: Vec<i64>,
     operation: OperationExpression,
     test: ModuloTest,
@@ -60,71 +64,79 @@ impl ModuloTest {
     }
 }
 
-fn parse_monkies(monkies_string: &str) -> Vec<Monkey> {
-    monkies_string
-        .split("\n\n")
-        .map(|monkey_string| parse_monkey(monkey_string))
+fn parse_monkies(monkies_string: &str) -> Result<Vec<Monkey>, ParseError> {
+    if monkies_string.trim().is_empty() {
+        return Err(ParseError::missing(Day11::DAY, monkies_string, "expected at least one monkey"));
+    }
+
+    parse::blocks(monkies_string)
+        .map(|monkey_string| parse_monkey(monkies_string, monkey_string))
         .collect()
 }
 
-fn parse_monkey(monkey_string: &str) -> Monkey {
+fn next_line<'a>(monkey_lines: &mut impl Iterator<Item = &'a str>, monkies_string: &str, monkey_string: &str, prefix: &str) -> Result<&'a str, ParseError> {
+    let line = monkey_lines
+        .next()
+        .ok_or_else(|| ParseError::new(Day11::DAY, monkies_string, &monkey_string[monkey_string.len()..], format!("expected '{}'", prefix.trim())))?;
+
+    parse::prefixed(Day11::DAY, monkies_string, line, prefix)
+}
+
+fn parse_monkey(monkies_string: &str, monkey_string: &str) -> Result<Monkey, ParseError> {
     let mut monkey_lines = monkey_string
         .lines()
-        .skip(1)
         .map(|line| line.trim());
+    let mut next_line = |prefix| next_line(&mut monkey_lines, monkies_string, monkey_string, prefix);
 
-    let items = monkey_lines
-        .next()
-        .unwrap()
-        .replace("Starting items: ", "")
-        .split(",")
-        .map(|number| number.trim().parse::<i64>().unwrap())
-        .collect::<Vec<_>>();
+    next_line("Monkey ")?;
 
-    let operation = parse_operation(monkey_lines.next().unwrap());
-    let test = parse_test(monkey_lines.next().unwrap());
+    let items = parse::comma_numbers(Day11::DAY, monkies_string, next_line("Starting items: ")?)?;
+    let operation = parse_operation(monkies_string, next_line("Operation: new = ")?)?;
+    let test = parse_test(monkies_string, next_line("Test: divisible by ")?)?;
 
-    let true_result: usize = monkey_lines.next().unwrap().replace("If true: throw to monkey ", "").parse().unwrap();
-    let false_result: usize = monkey_lines.next().unwrap().replace("If false: throw to monkey ", "").parse().unwrap();
+    let true_result: usize = parse_number(Day11::DAY, monkies_string, next_line("If true: throw to monkey ")?)?;
+    let false_result: usize = parse_number(Day11::DAY, monkies_string, next_line("If false: throw to monkey ")?)?;
 
-    Monkey{
+    Ok(Monkey{
         items,
         operation,
         test,
         true_result,
         false_result,
         inspected_count: 0
-    }
+    })
 }
 
-fn parse_test(test_string: &str) -> ModuloTest {
-    ModuloTest {number: test_string.replace("Test: divisible by ", "").parse().unwrap()}
+fn parse_test(monkies_string: &str, test_string: &str) -> Result<ModuloTest, ParseError> {
+    match parse_number(Day11::DAY, monkies_string, test_string)? {
+        0 => Err(ParseError::new(Day11::DAY, monkies_string, test_string, "cannot divide by zero")),
+        number => Ok(ModuloTest {number}),
+    }
 }
 
-fn parse_operation(operation_string: &str) -> OperationExpression {
-    let string = operation_string.replace("Operation: new = ", "");
-    let mut operation_tokens = string.split(" ");
+fn parse_operation(monkies_string: &str, operation_string: &str) -> Result<OperationExpression, ParseError> {
+    let [left, operator, right] = parse::fields(Day11::DAY, monkies_string, operation_string, "{} {} {}")?;
 
-    let left = parse_number_token(operation_tokens.next().unwrap());
-    let operator: fn(i64, i64) -> i64 = match operation_tokens.next().unwrap() {
+    let left = parse_number_token(monkies_string, left)?;
+    let operator: fn(i64, i64) -> i64 = match operator {
         "*" => |a,b| a * b,
         "+" => |a,b| a + b,
-        _ => panic!()
+        token => return Err(ParseError::new(Day11::DAY, monkies_string, token, "expected * or +"))
     };
-    let right = parse_number_token(operation_tokens.next().unwrap());
+    let right = parse_number_token(monkies_string, right)?;
 
-    OperationExpression {
+    Ok(OperationExpression {
         left,
         right,
         operator
-    }
+    })
 }
 
-fn parse_number_token(number_token: &str) -> Box<dyn Expression> {
-    match number_token {
+fn parse_number_token(monkies_string: &str, number_token: &str) -> Result<Box<dyn Expression>, ParseError> {
+    Ok(match number_token {
         "old" => Box::new(VariableExpression {}),
-        number => Box::new(LiteralExpression{literal: number.parse().unwrap()})
-    }
+        number => Box::new(LiteralExpression{literal: parse_number(Day11::DAY, monkies_string, number)?})
+    })
 }
 
 fn execute_monkies(mut monkies: Vec<Monkey>, rounds: usize, divisor: i64) -> usize {
@@ -162,6 +174,29 @@ fn execute_monkies(mut monkies: Vec<Monkey>, rounds: usize, divisor: i64) -> usi
 }
 
 
+pub(crate) struct Day11;
+
+impl Solution for Day11 {
+    const DAY: usize = 11;
+    const VERSION: u32 = 2;
+
+    type Parsed = Vec<Monkey>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_monkies(input)
+    }
+
+    fn part1(monkies: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer1 {
+        execute_monkies(monkies, 20, 3)
+    }
+
+    fn part2(monkies: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer2 {
+        execute_monkies(monkies, 10000, 1)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{fs};
@@ -169,21 +204,26 @@ mod tests {
 
     #[test]
     fn simple_test() {
-        assert_eq!(execute_monkies(parse_monkies(fs::read_to_string("src/day11/test_simple.txt").unwrap().as_str()), 20, 3), 10605);
+        assert_eq!(execute_monkies(parse_monkies(fs::read_to_string("src/day11/test_simple.txt").unwrap().as_str()).unwrap(), 20, 3), 10605);
     }
 
     #[test]
     fn large_test() {
-        assert_eq!(execute_monkies(parse_monkies(fs::read_to_string("src/day11/test_large.txt").unwrap().as_str()), 20, 3), 56350);
+        assert_eq!(execute_monkies(parse_monkies(fs::read_to_string("src/day11/test_large.txt").unwrap().as_str()).unwrap(), 20, 3), 56350);
     }
 
     #[test]
     fn simple_test_2() {
-        assert_eq!(execute_monkies(parse_monkies(fs::read_to_string("src/day11/test_simple.txt").unwrap().as_str()), 10000,1), 2713310158);
+        assert_eq!(execute_monkies(parse_monkies(fs::read_to_string("src/day11/test_simple.txt").unwrap().as_str()).unwrap(), 10000,1), 2713310158);
     }
 
     #[test]
     fn large_test_2() {
-        assert_eq!(execute_monkies(parse_monkies(fs::read_to_string("src/day11/test_large.txt").unwrap().as_str()), 10000,1), 13954061248);
+        assert_eq!(execute_monkies(parse_monkies(fs::read_to_string("src/day11/test_large.txt").unwrap().as_str()).unwrap(), 10000,1), 13954061248);
+    }
+
+    #[test]
+    fn empty_input_is_a_parse_error() {
+        assert_eq!(parse_monkies("").err().map(|error| error.message), Some("expected at least one monkey".to_string()));
     }
-}
\ No newline at end of file
+}
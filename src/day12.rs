@@ -5,148 +5,155 @@ use regex::{Captures, Match, Regex};
 use std::cell::{Ref, RefCell, RefMut};
 use std::ops::{Deref, Range};
 use std::{cmp, ops};
-use std::cmp::Reverse;
 use itertools::Itertools;
-use priority_queue::PriorityQueue;
-use crate::vector2::Vector2;
-
-type Path = Vec<Vector2>;
+use crate::grid::{self, DenseGrid};
+use crate::vector2::Vector2i;
+use crate::search::bfs;
+use crate::error::{ParseError, SolveError};
+use crate::observer::Observer;
+use crate::solution::Solution;
 
 struct Square {
-    path: Option<Path>,
     height: usize,
 }
 
-struct Grid {
-    squares: Vec<Vec<Square>>,
-    starting_positions: Vec<Vector2>,
-    stop: Vector2
+pub(crate) struct Grid {
+    squares: DenseGrid<Square>,
+    starting_positions: Vec<Vector2i>,
+    stop: Vector2i
 }
 
-fn parse_grid(grid_string: &str, count_a: bool) -> Grid{
+fn parse_grid(grid_string: &str) -> Result<Grid, ParseError> {
     let mut start = Vec::new();
-    let mut stop = Vector2{x: 0, y: 0};
+    let mut stop = None;
 
-    for (x, line) in grid_string.lines().enumerate() {
-        if let Some(y) = line.chars().position(|c| c == 'S') {
-            start.push(Vector2{x ,y});
+    for (y, line) in grid_string.lines().enumerate() {
+        let y = y as i32;
+        if let Some(x) = line.chars().position(|c| c == 'S') {
+            start.push(Vector2i{x: x as i32, y});
         }
 
-        if count_a {
-            start.append(&mut line.chars().enumerate().filter(|(y, el)| *el == 'a').map(|(y, el)| Vector2{x,y}).collect())
-        }
-        if let Some(y) = line.chars().position( |c| c == 'E'){
-            stop = Vector2{x,y};
+        if let Some(x) = line.chars().position( |c| c == 'E'){
+            stop = Some(Vector2i{x: x as i32, y});
         }
     }
 
-    let squares = grid_string
-        .lines()
-        .map(|line| line
-            .chars()
-            .map(|char| Square {
-                height: char_to_height(char),
-                path: None
-            })
-            .collect()
-        )
-        .collect();
-
-    Grid {
+    let squares = grid::Grid::try_parse(Day12::DAY, grid_string, |char| char_to_height(char)
+        .map(|height| Some(Square { height }))
+        .ok_or("expected a height between a and z"))?;
+
+    if start.is_empty() {
+        return Err(ParseError::missing(Day12::DAY, grid_string, "expected a starting square S"));
+    }
+
+    Ok(Grid {
         starting_positions: start,
-        stop,
+        stop: stop.ok_or_else(|| ParseError::missing(Day12::DAY, grid_string, "expected a best signal square E"))?,
         squares
-    }
+    })
 }
 
-fn char_to_height(char: char) -> usize {
-    let mut char_to_find = char;
-    if char.is_uppercase(){
-        char_to_find = 'z';
-    }
+fn add_lowest_starting_positions(mut grid: Grid) -> Grid {
+    grid.starting_positions.extend(grid.squares.iter().filter(|(_, square)| square.height == 0).map(|(pos, _)| pos));
 
-    return ('a'..='z').position(|c| c == char_to_find).unwrap();
+    grid
 }
 
-fn get_shortest_path(mut grid: Grid) -> usize {
+fn char_to_height(char: char) -> Option<usize> {
+    let char_to_find = match char {
+        'S' => 'a',
+        'E' => 'z',
+        _ => char,
+    };
 
-    let mut priority_queue: PriorityQueue<Vector2, Reverse<usize>> = PriorityQueue::new();
-
-    for start in grid.starting_positions{
-        grid.squares[start.x][start.y].path = Some(vec![]);
-        priority_queue.push(start, Reverse(1));
-    }
+    return ('a'..='z').position(|c| c == char_to_find);
+}
 
-    while let Some((position, length)) = priority_queue.pop() {
-        if position == grid.stop {
-            return length.0
-        }
+/// The fewest steps from any starting position to E, if E can be reached at all.
+fn get_shortest_path(grid: Grid) -> Option<usize> {
+    let squares = &grid.squares;
 
-        let dimensions = Vector2{x: grid.squares.len(), y: grid.squares[0].len()};
+    let result = bfs(
+        grid.starting_positions.iter().copied(),
+        |position| {
+            let max_height = squares.get(*position).unwrap().height + 1;
 
-        let neighbour_positions: Vec<_> = get_neighbouring_positions(position, dimensions)
-            .into_iter()
-            .filter(|pos| grid.squares[pos.x][pos.y].path.is_none() || grid.squares[pos.x][pos.y].path.as_ref().unwrap().len() > length.0)
-            .filter(|pos| grid.squares[pos.x][pos.y].height <= grid.squares[position.x][position.y].height + 1)
-            .collect();
+            squares.neighbours4(*position)
+                .filter(move |(_, square)| square.height <= max_height)
+                .map(|(pos, _)| pos)
+        },
+        |position| *position == grid.stop,
+    );
 
-        for neighbour_position in neighbour_positions {
-            let mut path =  grid.squares[position.x][position.y].path.as_ref().unwrap().clone();
-            path.push(neighbour_position);
-            let len = path.len();
-            grid.squares[neighbour_position.x][neighbour_position.y].path = Some(path);
+    result.map(|result| result.cost)
+}
 
-            priority_queue.push(neighbour_position, Reverse(len));
-        }
-    }
+fn steps_to_best_signal(grid: Grid) -> Result<usize, SolveError> {
+    get_shortest_path(grid).ok_or_else(|| SolveError::new(Day12::DAY, "the best signal square E cannot be reached"))
+}
 
-    return 0;
+pub(crate) struct Day12;
 
-}
+impl Solution for Day12 {
+    const DAY: usize = 12;
+    const VERSION: u32 = 2;
 
-fn get_neighbouring_positions(pos: Vector2, grid_size: Vector2) -> Vec<Vector2>{
-    let mut positions = Vec::new();
-    if pos.x > 0 {
-        positions.push(pos - Vector2{x: 1,y: 0})
-    }
+    type Parsed = Grid;
+    type Answer1 = Result<usize, SolveError>;
+    type Answer2 = Result<usize, SolveError>;
 
-    if pos.y > 0 {
-        positions.push(pos - Vector2{x: 0,y: 1})
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_grid(input)
     }
 
-    if pos.x + 1 < grid_size.x {
-        positions.push(pos + Vector2{x: 1,y: 0})
+    fn part1(grid: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer1 {
+        steps_to_best_signal(grid)
     }
 
-    if pos.y + 1 < grid_size.y {
-        positions.push(pos + Vector2{x: 0,y: 1})
+    fn part2(grid: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer2 {
+        steps_to_best_signal(add_lowest_starting_positions(grid))
     }
-
-    positions
 }
 
 #[cfg(test)]
 mod tests {
     use std::{fs};
+    use crate::observer::NoOp;
+    use crate::runner::RunError;
+    use crate::solution::Solver;
     use super::*;
 
+    fn part1(path: &str) -> Result<usize, SolveError> {
+        Day12::part1(Day12::parse(&fs::read_to_string(path).unwrap()).unwrap(), &mut NoOp)
+    }
+
+    fn part2(path: &str) -> Result<usize, SolveError> {
+        Day12::part2(Day12::parse(&fs::read_to_string(path).unwrap()).unwrap(), &mut NoOp)
+    }
+
     #[test]
     fn simple_test() {
-        assert_eq!(get_shortest_path(parse_grid(fs::read_to_string("src/day12/test_simple.txt").unwrap().as_str(), false)), 31);
+        assert_eq!(part1("src/day12/test_simple.txt"), Ok(31));
     }
 
     #[test]
     fn large_test() {
-        assert_eq!(get_shortest_path(parse_grid(fs::read_to_string("src/day12/test_large.txt").unwrap().as_str(), false)), 504);
+        assert_eq!(part1("src/day12/test_large.txt"), Ok(504));
     }
 
     #[test]
     fn simple_test_2() {
-        assert_eq!(get_shortest_path(parse_grid(fs::read_to_string("src/day12/test_simple.txt").unwrap().as_str(), true)), 29);
+        assert_eq!(part2("src/day12/test_simple.txt"), Ok(29));
     }
 
     #[test]
     fn large_test_2() {
-        assert_eq!(get_shortest_path(parse_grid(fs::read_to_string("src/day12/test_large.txt").unwrap().as_str(), true)), 500);
+        assert_eq!(part2("src/day12/test_large.txt"), Ok(500));
+    }
+
+    #[test]
+    fn unreachable_signal_is_an_error() {
+        assert!(Day12::part2(Day12::parse("SbE").unwrap(), &mut NoOp).is_err());
+        assert!(matches!(Day12.solve(1, "SbE", &mut NoOp), Err(RunError::Solve(SolveError { day: 12, .. }))));
     }
-}
\ No newline at end of file
+}
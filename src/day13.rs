@@ -12,6 +12,9 @@ use itertools::Itertools;
 use priority_queue::PriorityQueue;
 use crate::day13::CompareResult::{Continue, Right, Wrong, ListEnd};
 use crate::vector2::Vector2;
+use crate::error::{parse_number, ParseError};
+use crate::observer::Observer;
+use crate::solution::Solution;
 
 #[derive(Eq, PartialEq)]
 enum ListElement {
@@ -51,7 +54,7 @@ impl PartialOrd for StringLine {
 impl Ord for StringLine {
     fn cmp(&self, right: &Self) -> Ordering {
 
-        match compare_lists(recursive_list_parser(self.to_line()).0, recursive_list_parser(right.to_line()).0) {
+        match compare_lists(parse_list(self.to_line()), parse_list(right.to_line())) {
             ListEnd => Ordering::Equal,
             Continue => Ordering::Equal,
             Right => Ordering::Less,
@@ -68,37 +71,45 @@ enum CompareResult {
     Wrong,
 }
 
-fn recursive_list_parser(string: &str) -> (Vec<ListElement>, &str) {
+fn recursive_list_parser(string: &str) -> Result<(Vec<ListElement>, &str), ParseError> {
     let mut list = Vec::new();
     let mut iterator = string.chars();
-    let mut number_str = String::from("");;
+    let mut number_str = "";
 
     while let Some(char) = iterator.next(){
+        let position = string.len() - iterator.as_str().len() - char.len_utf8();
+
         match char {
             '[' => {
-                let (mut parsed_list, remaining_string) = recursive_list_parser(iterator.as_str());
+                let inner = iterator.as_str();
+                let (parsed_list, remaining_string) = recursive_list_parser(inner).map_err(|error| error.relative_to(string, inner))?;
                 iterator = remaining_string.chars();
                 list.push(ListElement::LIST(parsed_list))
             },
             ']' => {
                 if !number_str.is_empty(){
-                    list.push(ListElement::INT(number_str.parse::<i32>().unwrap() as i32))
+                    list.push(ListElement::INT(parse_number(Day13::DAY, string, number_str)?))
                 }
-                return (list, iterator.clone().as_str())
+                return Ok((list, iterator.as_str()))
             },
             ',' => {
                 if !number_str.is_empty() {
-                    list.push(ListElement::INT(number_str.parse::<i32>().unwrap() as i32));
-                    number_str.clear();
+                    list.push(ListElement::INT(parse_number(Day13::DAY, string, number_str)?));
+                    number_str = "";
                 }
             },
-            c => {
-                number_str.push(c);
+            _ => {
+                number_str = &string[position - number_str.len()..position + char.len_utf8()];
             }
         }
     }
 
-    return (list, "");
+    Ok((list, ""))
+}
+
+/// The elements of a packet that `parse_packets` accepted.
+fn parse_list(packet: &str) -> Vec<ListElement> {
+    recursive_list_parser(packet).expect("packets are checked while parsing").0
 }
 
 
@@ -161,23 +172,63 @@ fn compare_element(left: ListElement, right: ListElement) -> CompareResult {
     }
 }
 
-fn count_right_orders(lists_string: &str) -> usize {
-    lists_string
-        .split("\n\n")
+fn parse_packets(lists_string: &str) -> Result<Vec<String>, ParseError> {
+    let packets: Vec<_> = lists_string
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| validate_packet(line)
+            .and_then(|_| recursive_list_parser(line))
+            .map(|_| line.to_string())
+            .map_err(|error| error.relative_to(lists_string, line)))
+        .collect::<Result<_, _>>()?;
+
+    if packets.len() % 2 != 0 {
+        return Err(ParseError::missing(Day13::DAY, lists_string, "expected packets in pairs"));
+    }
+
+    Ok(packets)
+}
+
+fn validate_packet(line: &str) -> Result<(), ParseError> {
+    let mut depth = 0;
+
+    for (i, char) in line.char_indices() {
+        let fragment = &line[i..i + char.len_utf8()];
+        match char {
+            '[' => depth += 1,
+            ']' if depth > 0 => depth -= 1,
+            ',' | '0'..='9' if depth > 0 => {},
+            _ => return Err(ParseError::new(Day13::DAY, line, fragment, "unexpected character in packet")),
+        }
+
+        if depth == 0 && i + 1 < line.len() {
+            return Err(ParseError::new(Day13::DAY, line, &line[i + 1..], "unexpected text after packet"));
+        }
+    }
+
+    match depth {
+        0 => Ok(()),
+        _ => Err(ParseError::missing(Day13::DAY, line, "expected ']'")),
+    }
+}
+
+fn count_right_orders(packets: Vec<String>) -> usize {
+    packets
+        .chunks(2)
         .enumerate()
-        .map(|(i, lists_string)| {
-            let mut lists = lists_string.lines().map(|line| recursive_list_parser(line));
-            (i, compare_lists(lists.next().unwrap().0, lists.next().unwrap().0))
+        .map(|(i, pair)| {
+            let mut lists = pair.iter().map(|line| parse_list(line));
+            (i, compare_lists(lists.next().unwrap(), lists.next().unwrap()))
         })
         .filter(|(i, result)| *result == Right)
         .map(|(i, result)| i + 1)
         .sum()
 }
 
-fn sort(lists_string: &str) -> usize {
-    let mut lines: Vec<_> = lists_string
-        .lines()
-        .filter(|line| !line.is_empty())
+fn sort(packets: Vec<String>) -> usize {
+    let mut lines: Vec<_> = packets
+        .iter()
+        .map(|line| line.as_str())
         .collect();
 
     lines.push("[[6]]");
@@ -193,6 +244,29 @@ fn sort(lists_string: &str) -> usize {
     return (sorted.iter().position(|StringLine::Line(line)| line == "[[2]]").unwrap() + 1) * (sorted.iter().position(|StringLine::Line(line)| line == "[[6]]").unwrap() + 1)
 }
 
+pub(crate) struct Day13;
+
+impl Solution for Day13 {
+    const DAY: usize = 13;
+    const VERSION: u32 = 2;
+
+    type Parsed = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_packets(input)
+    }
+
+    fn part1(packets: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer1 {
+        count_right_orders(packets)
+    }
+
+    fn part2(packets: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer2 {
+        sort(packets)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{fs};
@@ -200,21 +274,28 @@ mod tests {
 
     #[test]
     fn simple_test() {
-        assert_eq!(count_right_orders(fs::read_to_string("src/day13/test_simple.txt").unwrap().as_str()), 13);
+        assert_eq!(count_right_orders(parse_packets(fs::read_to_string("src/day13/test_simple.txt").unwrap().as_str()).unwrap()), 13);
     }
 
     #[test]
     fn larges_test() {
-        assert_eq!(count_right_orders(fs::read_to_string("src/day13/test_large.txt").unwrap().as_str()), 6395);
+        assert_eq!(count_right_orders(parse_packets(fs::read_to_string("src/day13/test_large.txt").unwrap().as_str()).unwrap()), 6395);
     }
 
     #[test]
     fn simple_test_2() {
-        assert_eq!(sort(fs::read_to_string("src/day13/test_simple.txt").unwrap().as_str()), 140);
+        assert_eq!(sort(parse_packets(fs::read_to_string("src/day13/test_simple.txt").unwrap().as_str()).unwrap()), 140);
     }
 
     #[test]
     fn larges_test_2() {
-        assert_eq!(sort(fs::read_to_string("src/day13/test_large.txt").unwrap().as_str()), 24921);
+        assert_eq!(sort(parse_packets(fs::read_to_string("src/day13/test_large.txt").unwrap().as_str()).unwrap()), 24921);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn numbers_out_of_range_are_parse_errors() {
+        let error = parse_packets("[1]\n[[2,99999999999]]").unwrap_err();
+
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 5, "99999999999"));
+    }
+}
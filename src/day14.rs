@@ -12,26 +12,31 @@ use std::ptr::replace;
 use itertools::Itertools;
 use priority_queue::PriorityQueue;
 use crate::day14::GridObject::{Rock, Sand};
-use crate::vector2::Vector2;
+use crate::vector2::{Vector2, Vector2i};
+use crate::animate::{Animation, Cell, Frame, Frames};
+use crate::error::{parse_number, ParseError};
+use crate::observer::Observer;
+use crate::parse;
+use crate::solution::Solution;
 
 #[derive(Eq, PartialEq)]
-enum GridObject {
+pub(crate) enum GridObject {
     Sand,
     Rock,
 }
 
-type Grid = HashMap<Vector2, GridObject>;
+type Grid = HashMap<Vector2<usize>, GridObject>;
 
-fn string_to_vector2(string: &str) -> Vector2 {
-    let mut values = string.split(",");
+fn string_to_vector2(rocks_string: &str, string: &str) -> Result<Vector2<usize>, ParseError> {
+    let [x, y] = parse::fields(Day14::DAY, rocks_string, string, "{},{}")?;
 
-    Vector2 {
-        x: values.next().unwrap().parse().unwrap(),
-        y: values.next().unwrap().parse().unwrap()
-    }
+    Ok(Vector2 {
+        x: parse_number(Day14::DAY, rocks_string, x)?,
+        y: parse_number(Day14::DAY, rocks_string, y)?
+    })
 }
 
-fn drop_sand(pos: Vector2, grid: &Grid, deepest_y: usize, with_floor: bool) -> Option<Vector2> {
+fn drop_sand(pos: Vector2<usize>, grid: &Grid, deepest_y: usize, with_floor: bool) -> Option<Vector2<usize>> {
     let down_position = pos + Vector2{x: 0, y: 1};
     let down_left_position = down_position - Vector2{x: 1, y: 0};
     let down_right_position = down_position + Vector2{x: 1, y: 0};
@@ -52,15 +57,15 @@ fn drop_sand(pos: Vector2, grid: &Grid, deepest_y: usize, with_floor: bool) -> O
     }
 }
 
-fn parse_rocks(rocks_string: &str) -> (Grid, usize){
+fn parse_rocks(rocks_string: &str) -> Result<(Grid, usize), ParseError> {
     let mut grid = HashMap::new();
 
     let mut deepest_y = 0;
 
     for line in rocks_string.lines() {
         for window in line.split(" -> ").collect::<Vec<_>>().windows(2) {
-            let pos1 = string_to_vector2(window[0]);
-            let pos2 = string_to_vector2(window[1]);
+            let pos1 = string_to_vector2(rocks_string, window[0])?;
+            let pos2 = string_to_vector2(rocks_string, window[1])?;
 
             for x in min(pos1.x, pos2.x)..=max(pos1.x, pos2.x) {
                 for y in min(pos1.y, pos2.y)..=max(pos1.y, pos2.y)  {
@@ -71,10 +76,10 @@ fn parse_rocks(rocks_string: &str) -> (Grid, usize){
         }
     }
 
-    (grid, deepest_y)
+    Ok((grid, deepest_y))
 }
 
-fn count_drops_until((mut grid, deepest_y): (Grid, usize), pos: Vector2) -> usize {
+fn count_drops_until((mut grid, deepest_y): (Grid, usize), pos: Vector2<usize>) -> usize {
     for i in 1.. {
         match drop_sand(pos, &grid, deepest_y, false) {
             None => return i - 1,
@@ -87,7 +92,7 @@ fn count_drops_until((mut grid, deepest_y): (Grid, usize), pos: Vector2) -> usiz
     panic!()
 }
 
-fn count_drops_until_blocked((mut grid, deepest_y): (Grid, usize), pos: Vector2) -> usize {
+fn count_drops_until_blocked((mut grid, deepest_y): (Grid, usize), pos: Vector2<usize>) -> usize {
     for i in 1.. {
         match drop_sand(pos, &grid, deepest_y, true) {
             None => panic!(),
@@ -104,6 +109,67 @@ fn count_drops_until_blocked((mut grid, deepest_y): (Grid, usize), pos: Vector2)
     panic!()
 }
 
+/// One frame per grain that comes to rest, wide enough for the whole pile when there is a floor.
+fn sand_frames((mut grid, deepest_y): (Grid, usize), source: Vector2<usize>, with_floor: bool) -> Frames {
+    let floor_y = deepest_y + 2;
+    let (min_x, max_x, max_y) = if with_floor {
+        (source.x.saturating_sub(floor_y), source.x + floor_y, floor_y)
+    } else {
+        let xs = grid.keys().map(|pos| pos.x).chain([source.x]);
+        (xs.clone().min().unwrap_or(source.x).saturating_sub(1), xs.max().unwrap_or(source.x) + 1, deepest_y)
+    };
+    let mut blocked = false;
+
+    Box::new((1..).map_while(move |step| {
+        if blocked {
+            return None;
+        }
+
+        let position = drop_sand(source, &grid, deepest_y, with_floor)?;
+        grid.insert(position, Sand);
+        blocked = position == source;
+
+        let frame = Frame::from_fn(step, Vector2i{x: min_x as i32, y: 0}, Vector2i{x: max_x as i32, y: max_y as i32}, |pos| {
+            match grid.get(&Vector2{x: pos.x as usize, y: pos.y as usize}) {
+                Some(Rock) => Cell::Rock,
+                Some(Sand) => Cell::Sand,
+                None if with_floor && pos.y as usize == floor_y => Cell::Rock,
+                None => Cell::Empty,
+            }
+        });
+
+        Some(frame)
+    }))
+}
+
+pub(crate) struct Day14;
+
+impl Solution for Day14 {
+    const DAY: usize = 14;
+
+    type Parsed = (Grid, usize);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_rocks(input)
+    }
+
+    fn part1(rocks: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer1 {
+        count_drops_until(rocks, Vector2{x: 500, y: 0})
+    }
+
+    fn part2(rocks: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer2 {
+        count_drops_until_blocked(rocks, Vector2{x: 500, y: 0})
+    }
+}
+
+impl Animation for Day14 {
+    fn frames(rocks: Self::Parsed, part: usize) -> Frames {
+        sand_frames(rocks, Vector2{x: 500, y: 0}, part == 2)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{fs};
@@ -111,21 +177,21 @@ mod tests {
 
     #[test]
     fn simple_test() {
-        assert_eq!(count_drops_until(parse_rocks(fs::read_to_string("src/day14/test_simple.txt").unwrap().as_str()), Vector2{x: 500, y: 0}), 24);
+        assert_eq!(count_drops_until(parse_rocks(fs::read_to_string("src/day14/test_simple.txt").unwrap().as_str()).unwrap(), Vector2{x: 500, y: 0}), 24);
     }
 
     #[test]
     fn large_test() {
-        assert_eq!(count_drops_until(parse_rocks(fs::read_to_string("src/day14/test_large.txt").unwrap().as_str()), Vector2{x: 500, y: 0}), 644);
+        assert_eq!(count_drops_until(parse_rocks(fs::read_to_string("src/day14/test_large.txt").unwrap().as_str()).unwrap(), Vector2{x: 500, y: 0}), 644);
     }
 
     #[test]
     fn simple_test_2() {
-        assert_eq!(count_drops_until_blocked(parse_rocks(fs::read_to_string("src/day14/test_simple.txt").unwrap().as_str()), Vector2{x: 500, y: 0}), 93);
+        assert_eq!(count_drops_until_blocked(parse_rocks(fs::read_to_string("src/day14/test_simple.txt").unwrap().as_str()).unwrap(), Vector2{x: 500, y: 0}), 93);
     }
 
     #[test]
     fn large_test_2() {
-        assert_eq!(count_drops_until_blocked(parse_rocks(fs::read_to_string("src/day14/test_large.txt").unwrap().as_str()), Vector2{x: 500, y: 0}), 27324);
+        assert_eq!(count_drops_until_blocked(parse_rocks(fs::read_to_string("src/day14/test_large.txt").unwrap().as_str()).unwrap(), Vector2{x: 500, y: 0}), 27324);
     }
 }
\ No newline at end of file
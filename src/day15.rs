@@ -12,7 +12,12 @@ use itertools::Itertools;
 use priority_queue::PriorityQueue;
 use regex::SetMatches;
 use crate::day15::GridObject::{Beacon, NoBeacon, Sensor};
-use crate::vector2::{Vector2, Vector2i};
+use crate::vector2::{Vector2, Vector2i, Vector2i64};
+use crate::animate::{Animation, Cell, Frame, Frames};
+use crate::error::{parse_number, ParseError};
+use crate::observer::Observer;
+use crate::parse;
+use crate::solution::Solution;
 
 #[derive(Eq, PartialEq)]
 enum GridObject {
@@ -23,46 +28,52 @@ enum GridObject {
 
 type Sensors = Vec<(Vector2i, Vector2i)>;
 
-fn parse_grid(sensor_lines: &str) -> Sensors {
+const COVERAGE_CELLS: i32 = 160;
+
+fn parse_grid(sensor_lines: &str) -> Result<Sensors, ParseError> {
+    if sensor_lines.trim().is_empty() {
+        return Err(ParseError::missing(Day15::DAY, sensor_lines, "expected at least one sensor"));
+    }
+
     sensor_lines.lines().map(|line| {
-        let split_regex = regex::Regex::new(r"Sensor at x=|, y=|: closest beacon is at x=|, y=").unwrap();
-        let mut vector_elements = split_regex.split(line).filter(|str| !str.is_empty()).map(|el| el.parse().unwrap());
+        let [sensor_x, sensor_y, beacon_x, beacon_y] = parse::fields(Day15::DAY, sensor_lines, line, "Sensor at x={}, y={}: closest beacon is at x={}, y={}")?
+            .map(|value| parse_number(Day15::DAY, sensor_lines, value));
 
         let sensor = Vector2i {
-            x: vector_elements.next().unwrap(),
-            y: vector_elements.next().unwrap(),
+            x: sensor_x?,
+            y: sensor_y?,
         };
 
         let beacon = Vector2i {
-            x: vector_elements.next().unwrap(),
-            y: vector_elements.next().unwrap(),
+            x: beacon_x?,
+            y: beacon_y?,
         };
 
 
-        (sensor, beacon)
+        Ok((sensor, beacon))
     }).collect()
 }
 
 fn get_position_at_distance(sensor: Vector2i, y_line: i32, distance: i32) -> Vec<i32> {
-    (0..=distance).map(|x| [sensor.x - x, sensor.x + x]).flatten().collect()
+    let reach = distance - (sensor.y - y_line).abs();
+
+    (0..=reach).flat_map(|x| [sensor.x - x, sensor.x + x]).collect()
 }
 
 
 fn count_invalids(beacons_and_sensors: Sensors, y: i32) -> usize {
     let beacons: Vec<_> = beacons_and_sensors.iter().map(|(_, beacon)| *beacon).collect();
-    let sensors: Vec<_> = beacons_and_sensors.iter().map(|(sensor, _)| *sensor).collect();
 
+    // A sensor's own position cannot hold a beacon either, so it counts.
     beacons_and_sensors.iter().map(|(sensor, beacon)| {
         let distance = sensor.get_manhattan_distance(*beacon);
 
-        get_position_at_distance(*sensor, y, distance).into_iter().filter(|x| !sensors.contains(&Vector2i { x: *x, y }) && !beacons.contains(&Vector2i { x: *x, y }))
+        get_position_at_distance(*sensor, y, distance).into_iter().filter(|x| !beacons.contains(&Vector2i { x: *x, y }))
     }).flatten().unique().count()
 }
 
 
 fn get_outer_edge(sensor: Vector2i, bounds: Vector2i, distance: i32) -> HashSet<Vector2i> {
-    println!("Getting outer edge for {}, {}", sensor.x, sensor.y);
-
     (max(0, sensor.x - distance)..=max(bounds.x, sensor.x + distance)).map(|x| {
         let y_abs = distance - (sensor.x - x).abs();
 
@@ -71,50 +82,129 @@ fn get_outer_edge(sensor: Vector2i, bounds: Vector2i, distance: i32) -> HashSet<
 
 }
 
-fn get_only_valid(beacons_and_sensors: Sensors, bounds: Vector2i) -> i64 {
+fn get_only_valid(beacons_and_sensors: Sensors, bounds: Vector2i, observer: &mut dyn Observer) -> i64 {
+    let sensor_count = beacons_and_sensors.len();
     let edge_positions = beacons_and_sensors
         .iter()
-        .map(|(sensor, beacon)| {
+        .enumerate()
+        .flat_map(|(i, (sensor, beacon))| {
+            observer.progress(i, Some(sensor_count));
             let distance = sensor.get_manhattan_distance(*beacon) + 1;
 
             get_outer_edge(*sensor, bounds, distance)
         })
-        .flatten()
         .unique();
 
-    let position = edge_positions
+    let position: Vector2i64 = edge_positions
         .filter(|pos| pos.x >= 0 && pos.y >= 0 && pos.x <= bounds.x && pos.y <= bounds.y)
         .filter(|pos| beacons_and_sensors.iter().all(|(sensor, beacon)| sensor.get_manhattan_distance(*pos) > sensor.get_manhattan_distance(*beacon)))
         .next()
-        .unwrap();
+        .unwrap()
+        .cast();
+
+    position.x * 4000000 + position.y
+}
+
+/// What the sensors cover around them and their beacons, sampled at the centre of each cell so that
+/// large inputs fit in about `COVERAGE_CELLS` cells a side. The covered cells on `row` are marked.
+fn coverage_frames(sensors: Sensors, row: Option<i32>) -> Frames {
+    let points: Vec<Vector2i> = sensors.iter().flat_map(|(sensor, beacon)| [*sensor, *beacon]).collect();
+    let min = Vector2i{x: points.iter().map(|pos| pos.x).min().unwrap_or(0), y: points.iter().map(|pos| pos.y).min().unwrap_or(0)};
+    let max = Vector2i{x: points.iter().map(|pos| pos.x).max().unwrap_or(0), y: points.iter().map(|pos| pos.y).max().unwrap_or(0)};
+    let scale = (max.x - min.x).max(max.y - min.y) / COVERAGE_CELLS + 1;
+    let block = |pos: Vector2i| Vector2i{x: (pos.x - min.x) / scale, y: (pos.y - min.y) / scale};
+
+    let mut marks = HashMap::new();
+    for (_, beacon) in &sensors {
+        marks.insert(block(*beacon), Cell::Beacon);
+    }
+    for (sensor, _) in &sensors {
+        marks.insert(block(*sensor), Cell::Sensor);
+    }
+
+    let radii: Vec<_> = sensors.iter().map(|(sensor, beacon)| (*sensor, sensor.get_manhattan_distance(*beacon))).collect();
+    let row = row.filter(|row| (min.y..=max.y).contains(row)).map(|row| (row - min.y) / scale);
+
+    let frame = Frame::from_fn(0, Vector2i{x: 0, y: 0}, block(max), |pos| {
+        let centre = Vector2i{x: min.x + pos.x * scale + scale / 2, y: min.y + pos.y * scale + scale / 2};
+        let covered = radii.iter().any(|(sensor, radius)| sensor.get_manhattan_distance(centre) <= *radius);
+
+        match (marks.get(&pos), covered) {
+            (Some(mark), _) => *mark,
+            (None, true) if Some(pos.y) == row => Cell::Trail('#'),
+            (None, true) => Cell::Covered,
+            (None, false) => Cell::Empty,
+        }
+    });
+
+    Box::new(std::iter::once(frame))
+}
+
+pub(crate) struct Day15;
+
+impl Solution for Day15 {
+    const DAY: usize = 15;
+    const VERSION: u32 = 2;
 
-    println!("Found {} {}", position.x, position.y);
-    position.x as i64 * 4000000 + position.y as i64
+    type Parsed = Sensors;
+    type Answer1 = usize;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_grid(input)
+    }
+
+    fn part1(sensors: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer1 {
+        count_invalids(sensors, 2000000)
+    }
+
+    fn part2(sensors: Self::Parsed, observer: &mut dyn Observer) -> Self::Answer2 {
+        get_only_valid(sensors, Vector2i{x: 4000000, y: 4000000}, observer)
+    }
+}
+
+impl Animation for Day15 {
+    fn frames(sensors: Self::Parsed, part: usize) -> Frames {
+        coverage_frames(sensors, (part == 1).then_some(2000000))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::{fs};
+    use crate::generate::{self, Rng};
+    use crate::observer::NoOp;
+    use crate::reference;
     use super::*;
 
     #[test]
     fn simple_test() {
-        assert_eq!(count_invalids(parse_grid(include_str!("day15/test_simple.txt")), 10), 26);
+        assert_eq!(count_invalids(parse_grid(include_str!("day15/test_simple.txt")).unwrap(), 10), 26);
     }
 
     #[test]
     fn large_test() {
-        assert_eq!(count_invalids(parse_grid(include_str!("day15/test_large.txt")), 2000000), 26);
+        assert_eq!(count_invalids(parse_grid(include_str!("day15/test_large.txt")).unwrap(), 2000000), 4886370);
     }
 
     #[test]
     fn simple_test_2() {
-        assert_eq!(get_only_valid(parse_grid(include_str!("day15/test_simple.txt")), Vector2i{x: 20, y: 20}), 56000011);
+        assert_eq!(get_only_valid(parse_grid(include_str!("day15/test_simple.txt")).unwrap(), Vector2i{x: 20, y: 20}, &mut NoOp), 56000011);
+    }
+
+    #[test]
+    fn matches_reference_on_small_squares() {
+        for seed in 0..20 {
+            let input = generate::sensors_with_gap(&mut Rng::new(seed), 20);
+            let result = get_only_valid(parse_grid(&input).unwrap(), Vector2i{x: 20, y: 20}, &mut NoOp);
+
+            assert_eq!(Some(result), reference::tuning_frequency(&input, 20), "seed {}:\n{}", seed, input);
+        }
     }
 
     #[test]
     fn large_test_2() {
-        assert_eq!(get_only_valid(parse_grid(include_str!("day15/test_large.txt")), Vector2i{x: 4000000, y: 4000000}), 11374534948438);
+        assert_eq!(get_only_valid(parse_grid(include_str!("day15/test_large.txt")).unwrap(), Vector2i{x: 4000000, y: 4000000}, &mut NoOp), 11374534948438);
     }
 
     // #[test]
@@ -131,4 +221,9 @@ mod tests {
     // fn large_test_2() {
     //     assert_eq!(count_drops_until_blocked(parse_rocks(fs::read_to_string("src/day14/test_large.txt").unwrap().as_str()), Vector2{x: 500, y: 0}), 27324);
     // }
-}
\ No newline at end of file
+
+    #[test]
+    fn empty_input_is_a_parse_error() {
+        assert_eq!(parse_grid("").unwrap_err().message, "expected at least one sensor");
+    }
+}
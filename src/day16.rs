@@ -1,9 +1,14 @@
 use std::cell::{Cell, Ref};
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{HashMap, HashSet};
 use itertools::Itertools;
+use crate::error::{parse_number, ParseError};
+use crate::search::bfs_distances;
+use crate::observer::Observer;
+use crate::parse;
+use crate::solution::Solution;
 
 #[derive(PartialEq, Eq, Clone, Hash)]
-struct Edge {
+pub(crate) struct Edge {
     to: String,
     cost: usize,
     flow_rate: usize,
@@ -18,22 +23,22 @@ struct Node {
 type Graph = HashMap<String, Node>;
 type RefinedGraph = HashMap<String, Vec<Edge>>;
 
-fn parse_graphs(sensor_lines: &str) -> RefinedGraph {
+fn parse_graphs(sensor_lines: &str) -> Result<RefinedGraph, ParseError> {
     let mut graph: Graph = HashMap::new();
+    let mut edge_names = Vec::new();
 
     for line in sensor_lines.lines() {
-        let split_regex = regex::Regex::new(r"Valve | has flow rate=|; tunnels lead to valves |; tunnel leads to valve ").unwrap();
-        let mut elements = split_regex
-            .split(line)
-            .map(|str| str.trim())
-            .filter(|str| !str.is_empty());
-
-        let name: String = elements.next().unwrap().parse().unwrap();
-        let flow_rate_str = elements.next().unwrap();
-        let flow_rate = flow_rate_str.parse().unwrap();
-        let edges = elements.next().unwrap().split(",").map(|str| str.trim().parse().unwrap()).collect();
-
-        println!("Added: {}", name);
+        let [name, flow_rate, tunnels] = parse::fields(Day16::DAY, sensor_lines, line, "Valve {} has flow rate={}; {}")?;
+        let tunnels = ["tunnels lead to valves ", "tunnel leads to valve "]
+            .iter()
+            .find_map(|prefix| tunnels.strip_prefix(prefix))
+            .ok_or_else(|| ParseError::new(Day16::DAY, sensor_lines, tunnels, "expected 'tunnels lead to valves'"))?;
+
+        let name: String = name.to_string();
+        let flow_rate = parse_number(Day16::DAY, sensor_lines, flow_rate)?;
+        let edge_list: Vec<&str> = parse::comma_list(tunnels).collect();
+        let edges = edge_list.iter().map(|str| str.to_string()).collect();
+        edge_names.extend(edge_list);
 
         graph.insert(name.clone(), Node {
             flow_rate,
@@ -43,33 +48,25 @@ fn parse_graphs(sensor_lines: &str) -> RefinedGraph {
 
     }
 
-    refine_graph(graph)
-}
-
-fn get_paths_from(name: String, graph: &Graph) -> Vec<Edge> {
-    let mut visited_nodes: HashMap<String, usize> = HashMap::new();
-    let mut paths: VecDeque<Vec<String>> = VecDeque::new();
-
-    paths.push_front(vec![name]);
+    if let Some(unknown) = edge_names.into_iter().find(|name| !graph.contains_key(*name)) {
+        return Err(ParseError::new(Day16::DAY, sensor_lines, unknown, "unknown valve"));
+    }
 
-    while let Some(path) = paths.pop_back() {
-        let current = path.last().unwrap();
+    if !graph.contains_key("AA") {
+        return Err(ParseError::missing(Day16::DAY, sensor_lines, "expected a valve named AA"));
+    }
 
-        for next in graph[current].edges.iter() {
-            if !visited_nodes.contains_key(next) {
-                let mut new_path = path.clone();
-                new_path.push(next.clone());
+    Ok(refine_graph(graph))
+}
 
-                visited_nodes.insert(next.clone(), new_path.len());
-                paths.push_front(new_path);
-            }
-        }
-    }
+fn get_paths_from(name: String, graph: &Graph) -> Vec<Edge> {
+    let distances = bfs_distances(name.as_str(), |current| graph[*current].edges.iter().map(String::as_str));
 
-    visited_nodes.into_iter().map(|(key, path_len)| Edge {
-        to: key.clone(),
-        cost: path_len,
-        flow_rate: graph[&key].flow_rate
+    // Reaching a valve and opening it both take a minute.
+    distances.into_iter().filter(|(key, _)| *key != name).map(|(key, distance)| Edge {
+        to: key.to_string(),
+        cost: distance + 1,
+        flow_rate: graph[key].flow_rate
     }).filter(|edge| edge.flow_rate > 0).collect()
 }
 
@@ -128,28 +125,51 @@ fn get_best_path(mut paths: Vec<Vec<Edge>>, max_cost: usize) -> usize {
     paths.iter().map(|path| get_path_score(path, max_cost)).max().unwrap()
 }
 
-fn get_combinations_for(paths: Vec<Vec<Edge>>, graph: &RefinedGraph, max_cost: usize) -> usize {
+fn get_combinations_for(paths: Vec<Vec<Edge>>, graph: &RefinedGraph, max_cost: usize, observer: &mut dyn Observer) -> usize {
     let amount = paths.len();
 
     paths
         .into_iter()
         .enumerate()
         .map(|(i, path)| {
-            println!("Processed {}/{}", i, amount);
+            observer.progress(i, Some(amount));
             get_path_score(&path, max_cost) + get_best_path(get_all_paths(graph, max_cost, &path.iter().map(|el| el.to.clone()).collect()), max_cost)
         })
         .max().unwrap()
 
 }
 
+pub(crate) struct Day16;
+
+impl Solution for Day16 {
+    const DAY: usize = 16;
+
+    type Parsed = RefinedGraph;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_graphs(input)
+    }
+
+    fn part1(graph: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer1 {
+        get_best_path(get_all_paths(&graph, 30, &vec![]), 30)
+    }
+
+    fn part2(graph: Self::Parsed, observer: &mut dyn Observer) -> Self::Answer2 {
+        get_combinations_for(get_all_paths(&graph, 26, &vec![]), &graph, 26, observer)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{fs};
+    use crate::observer::NoOp;
     use super::*;
 
     #[test]
     fn simple_test() {
-        let refined_graph = parse_graphs(include_str!("day16/test_simple.txt"));
+        let refined_graph = parse_graphs(include_str!("day16/test_simple.txt")).unwrap();
 
         assert_eq!(get_best_path(get_all_paths(&refined_graph, 30, &vec![]), 30), 1651);
 
@@ -157,23 +177,23 @@ mod tests {
 
     #[test]
     fn large_test() {
-        let refined_graph = parse_graphs(include_str!("day16/test_large.txt"));
+        let refined_graph = parse_graphs(include_str!("day16/test_large.txt")).unwrap();
 
         assert_eq!(get_best_path(get_all_paths(&refined_graph, 30, &vec![]), 30), 2253);
     }
 
     #[test]
     fn simple_test_2() {
-        let refined_graph = parse_graphs(include_str!("day16/test_simple.txt"));
+        let refined_graph = parse_graphs(include_str!("day16/test_simple.txt")).unwrap();
 
-        assert_eq!(get_combinations_for(get_all_paths(&refined_graph, 26, &vec![]), &refined_graph, 26), 1707);
+        assert_eq!(get_combinations_for(get_all_paths(&refined_graph, 26, &vec![]), &refined_graph, 26, &mut NoOp), 1707);
 
     }
 
     #[test]
     fn large_test_2() {
-        let refined_graph = parse_graphs(include_str!("day16/test_large.txt"));
+        let refined_graph = parse_graphs(include_str!("day16/test_large.txt")).unwrap();
 
-        assert_eq!(get_combinations_for(get_all_paths(&refined_graph, 26, &vec![]), &refined_graph, 26), 2838);
+        assert_eq!(get_combinations_for(get_all_paths(&refined_graph, 26, &vec![]), &refined_graph, 26, &mut NoOp), 2838);
     }
 }
\ No newline at end of file
@@ -1,15 +1,23 @@
 use std::borrow::Borrow;
 use std::cmp::max;
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::{Display, Formatter};
+use std::collections::{HashSet, VecDeque};
 use std::iter::repeat;
 use std::ops::Add;
 use itertools::{interleave, Itertools};
 use crate::vector2::{Vector2, Vector2i, Vector2i64};
+use crate::animate::{Animation, Cell, Frame, Frames};
+use crate::cycle::CycleDetector;
+use crate::error::ParseError;
+use crate::observer::Observer;
+use crate::parse;
+use crate::solution::Solution;
 
 type Shape = Vec<Vector2i64>;
 type Jet = Vector2i64;
 
-type SeenShapes = HashMap<(Vec<Vector2i64>, usize, usize), (i64, usize)>;
+const TOP_ROWS: i64 = 32;
+const FRAME_ROWS: i32 = 24;
 
 struct Tunnel {
     grid: HashSet<Vector2i64>,
@@ -22,10 +30,13 @@ impl Tunnel {
         pos.x >= 0 && pos.x < self.width && pos.y >= 0 && !self.grid.contains(&pos)
     }
 
-    fn print(&self) {
-        let str = (0..15).rev().map(|y| (0..7).map(|x| if(self.grid.contains(&Vector2i64{x, y})){"#"} else {"."}).join("")).join("\n");
-
-        println!("{}", str)
+    // The settled rows near the top, which is all a falling shape can still reach.
+    fn top_rows(&self) -> Vec<u8> {
+        (0..TOP_ROWS)
+            .map(|offset| (0..self.width)
+                .filter(|x| self.grid.contains(&Vector2i64{x: *x, y: self.height - offset}))
+                .fold(0, |row, x| row | 1 << x))
+            .collect()
     }
 
     fn insert(&mut self, vector: Vector2i64) {
@@ -36,6 +47,16 @@ impl Tunnel {
     }
 }
 
+impl Display for Tunnel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let rows = (self.height - 14..=self.height + 1).rev()
+            .map(|y| (0..self.width).map(|x| if self.grid.contains(&Vector2i64{x, y}) {"#"} else {"."}).join(""))
+            .join("\n");
+
+        f.write_str(&rows)
+    }
+}
+
 struct LoopingIterator<T> {
     vector: Vec<T>,
     next: usize,
@@ -53,26 +74,24 @@ impl<T: Clone> Iterator for LoopingIterator<T> {
 }
 
 
-fn parse_jets(jets: &str) -> Vec<Vector2i64> {
-    jets.chars()
-        .map(|char| match char {
-            '<' => Vector2i64 { x: -1, y: 0 },
-            '>' => Vector2i64 { x: 1, y: 0 },
-            _ => panic!()
-        })
-        .collect()
+fn parse_jets(jets: &str) -> Result<Vec<Vector2i64>, ParseError> {
+    if jets.is_empty() {
+        return Err(ParseError::missing(Day17::DAY, jets, "expected at least one jet"));
+    }
+
+    parse::chars(Day17::DAY, jets, jets, |char| match char {
+        '<' => Ok(Vector2i64 { x: -1, y: 0 }),
+        '>' => Ok(Vector2i64 { x: 1, y: 0 }),
+        _ => Err("expected < or >")
+    })
 }
 
-fn move_shape(shape: Shape, jet: Vector2i64, tunnel: &mut Tunnel, i: usize) -> Option<Shape> {
+fn move_shape(shape: Shape, jet: Vector2i64, tunnel: &mut Tunnel) -> Option<Shape> {
     let mut new_shape = shape
         .clone()
         .into_iter()
         .map(|pos| pos.add(jet));
 
-    if(i < 10) {
-        println!("{}, {}", jet.x, jet.y);
-    }
-
     if new_shape.clone().any(|pos| !tunnel.is_free(pos)) {
         if jet.y < 0 {
             for pos in shape {
@@ -89,19 +108,22 @@ fn move_shape(shape: Shape, jet: Vector2i64, tunnel: &mut Tunnel, i: usize) -> O
     return Some(new_shape.collect());
 }
 
-fn drop_shape(shape: Shape, movements: &mut dyn Iterator<Item=Vector2i64>, tunnel: &mut Tunnel, i: usize) {
-    let mut resulting_shape = Some(shape.into_iter().map(|pos| pos.add(Vector2i64 { x: 2, y: tunnel.height + 4 })).collect());
-    while resulting_shape.is_some() {
+/// Drops a shape until it settles, returning where it came to rest.
+fn drop_shape(shape: Shape, movements: &mut dyn Iterator<Item=Vector2i64>, tunnel: &mut Tunnel) -> Shape {
+    let mut resulting_shape: Shape = shape.into_iter().map(|pos| pos.add(Vector2i64 { x: 2, y: tunnel.height + 4 })).collect();
+    loop {
         for mov in [movements.next().unwrap(), Vector2i64{x: 0, y: -1}]{
-            resulting_shape = move_shape(resulting_shape.unwrap(), mov, tunnel, i)
+            match move_shape(resulting_shape.clone(), mov, tunnel) {
+                Some(moved) => resulting_shape = moved,
+                None => return resulting_shape,
+            }
         }
     }
 }
 
-fn drop_shapes(shapes: Vec<Shape>, jets: Vec<Jet>, dimension: i64, amount: usize) -> i64 {
-    let mut seen_shapes: SeenShapes = HashMap::new();
-    let len_jet = jets.len();
-    let len_snake = shapes.len();
+fn drop_shapes(shapes: Vec<Shape>, jets: Vec<Jet>, dimension: i64, amount: usize, observer: &mut dyn Observer) -> i64 {
+    let mut detector = CycleDetector::new();
+    let len_shapes = shapes.len();
     let mut jet_iterator = LoopingIterator { vector: jets, next: 0 };
     let shape_iterator = LoopingIterator { vector: shapes, next: 0 };
     let mut tunnel = Tunnel {
@@ -110,47 +132,58 @@ fn drop_shapes(shapes: Vec<Shape>, jets: Vec<Jet>, dimension: i64, amount: usize
         height: -1,
     };
 
-    for (i, shape) in shape_iterator.take(amount).enumerate() {
-        if(i < 10) {
-            println!("------ {} ------", i);
-            tunnel.print();
-        }
+    detector.record((0, 0, tunnel.top_rows()), 0);
 
-        if(i % 10000 == 0) {
-            println!("Completed {}/{}", i, amount)
-        }
-
-        drop_shape(shape, &mut jet_iterator, &mut tunnel, i);
+    for (i, shape) in shape_iterator.take(amount).enumerate() {
+        observer.progress(i, Some(amount));
+        drop_shape(shape, &mut jet_iterator, &mut tunnel);
+        observer.frame(&tunnel);
 
-        let last_two_rows: Vec<Vec<_>> = (0..dimension).map(|x| (0..=1).map(|y_offset| Vector2i64{x, y: tunnel.height - y_offset}).collect()).collect();
+        let fingerprint = ((i + 1) % len_shapes, jet_iterator.next, tunnel.top_rows());
 
-        if(i == (2471 + 449)) {
-            let a = "a";
+        if let Some(cycle) = detector.record(fingerprint, tunnel.height + 1) {
+            return detector.extrapolate(cycle, amount);
         }
+    }
 
-        if last_two_rows.iter().all(|positions| !tunnel.is_free(positions[0]) || !tunnel.is_free(positions[1])) {
-            let to_obj: (Vec<_>, usize, usize) = (last_two_rows.into_iter().map(|positions| positions[0].add(Vector2i64{x: 0, y: -tunnel.height})).collect(), (jet_iterator.next) % len_jet, i % len_snake);
-
-            println!("Completed {}/{}, shape is suitable.", i, amount);
+    tunnel.height + 1
 
-            if seen_shapes.contains_key(&to_obj) {
-                println!("Found");
+}
 
-                let segment_height = (tunnel.height - seen_shapes.get(&to_obj).unwrap().0);
-                let segment_count = (amount - i) / (i - seen_shapes.get(&to_obj).unwrap().1);
-                let total_height = segment_count * segment_height as usize;
-                let remaining_after_segment = (amount - i) % (i - seen_shapes.get(&to_obj).unwrap().1);
-                let a = "";
-            }
-            else {
-                println!("Inserted {}", to_obj.1);
-                seen_shapes.insert(to_obj, (tunnel.height, i));
-            }
+/// The top of the tunnel after a shape settled, with that shape highlighted.
+fn tunnel_frame(tunnel: &Tunnel, settled: &Shape, step: usize) -> Frame {
+    let top = tunnel.height + 1;
+
+    Frame::from_fn(step, Vector2i{x: -1, y: 0}, Vector2i{x: tunnel.width as i32, y: FRAME_ROWS - 1}, |pos| {
+        let world = Vector2i64{x: pos.x as i64, y: top - pos.y as i64};
+
+        if world.y < -1 {
+            Cell::Void
+        } else if world.y == -1 || world.x < 0 || world.x >= tunnel.width {
+            Cell::Wall
+        } else if settled.contains(&world) {
+            Cell::Actor('@')
+        } else if tunnel.grid.contains(&world) {
+            Cell::Rock
+        } else {
+            Cell::Empty
         }
-    }
+    })
+}
 
-    tunnel.height + 1
+fn rock_frames(shapes: Vec<Shape>, jets: Vec<Jet>, dimension: i64, amount: usize) -> Frames {
+    let mut jet_iterator = LoopingIterator { vector: jets, next: 0 };
+    let mut tunnel = Tunnel {
+        grid: HashSet::new(),
+        width: dimension,
+        height: -1,
+    };
+
+    Box::new(LoopingIterator { vector: shapes, next: 0 }.take(amount).enumerate().map(move |(i, shape)| {
+        let settled = drop_shape(shape, &mut jet_iterator, &mut tunnel);
 
+        tunnel_frame(&tunnel, &settled, i + 1)
+    }))
 }
 
 fn get_shapes() -> Vec<Shape> {
@@ -180,36 +213,67 @@ fn get_shapes() -> Vec<Shape> {
         .flatten().collect()).collect()
 }
 
+pub(crate) struct Day17;
+
+impl Solution for Day17 {
+    const DAY: usize = 17;
+
+    type Parsed = Vec<Jet>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_jets(input)
+    }
+
+    fn part1(jets: Self::Parsed, observer: &mut dyn Observer) -> Self::Answer1 {
+        drop_shapes(get_shapes(), jets, 7, 2022, observer)
+    }
+
+    fn part2(jets: Self::Parsed, observer: &mut dyn Observer) -> Self::Answer2 {
+        drop_shapes(get_shapes(), jets, 7, 1000000000000, observer)
+    }
+}
+
+impl Animation for Day17 {
+    fn frames(jets: Self::Parsed, part: usize) -> Frames {
+        let amount = if part == 1 { 2022 } else { 1000000000000 };
+
+        rock_frames(get_shapes(), jets, 7, amount)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{fs};
+    use crate::observer::NoOp;
     use super::*;
 
     #[test]
     fn simple_test() {
-        let jets = parse_jets(include_str!("day17/test_simple.txt"));
+        let jets = parse_jets(include_str!("day17/test_simple.txt")).unwrap();
         let shapes = get_shapes();
-        assert_eq!(drop_shapes(shapes, jets, 7, 2022), 3068);
+        assert_eq!(drop_shapes(shapes, jets, 7, 2022, &mut NoOp), 3068);
     }
 
     #[test]
     fn large_test() {
-        let jets = parse_jets(include_str!("day17/test_large.txt"));
+        let jets = parse_jets(include_str!("day17/test_large.txt")).unwrap();
 
-        assert_eq!(drop_shapes(get_shapes(), jets, 7, 2022), 3219);
+        assert_eq!(drop_shapes(get_shapes(), jets, 7, 2022, &mut NoOp), 3219);
     }
 
     #[test]
     fn simple_test_2() {
-        let jets = parse_jets(include_str!("day17/test_simple.txt"));
+        let jets = parse_jets(include_str!("day17/test_simple.txt")).unwrap();
         let shapes = get_shapes();
-        assert_eq!(drop_shapes(shapes, jets, 7, 1000000000000), 3068);
+        assert_eq!(drop_shapes(shapes, jets, 7, 1000000000000, &mut NoOp), 1514285714288);
     }
 
     #[test]
     fn large_test_2() {
-        let jets = parse_jets(include_str!("day17/test_large.txt"));
+        let jets = parse_jets(include_str!("day17/test_large.txt")).unwrap();
 
-        assert_eq!(drop_shapes(get_shapes(), jets, 7, 1000000000000), 0);
+        assert_eq!(drop_shapes(get_shapes(), jets, 7, 1000000000000, &mut NoOp), 1582758620701);
     }
 }
\ No newline at end of file
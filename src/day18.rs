@@ -1,43 +1,38 @@
 use std::collections::HashSet;
-use std::ops::Add;
-use crate::vector3::Vector3;
+use crate::animate::{Animation, Cell, Frame, Frames};
+use crate::vector2::Vector2i;
+use crate::vector3::{BoundingBox, Vector3};
+use crate::error::{parse_number, ParseError};
+use crate::observer::Observer;
+use crate::parse;
+use crate::solution::Solution;
 
 type CubePosition = Vector3<i32>;
 
-fn parse_positions(positions_string: &str) -> HashSet<CubePosition> {
+fn parse_positions(positions_string: &str) -> Result<HashSet<CubePosition>, ParseError> {
     positions_string.lines().map(|line| {
-        let mut elements = line.split(",").map(|str| str.parse::<i32>().unwrap());
-
-        Vector3 {
-            x: elements.next().unwrap(),
-            y: elements.next().unwrap(),
-            z: elements.next().unwrap(),
-        }
+        let [x, y, z] = parse::fields(Day18::DAY, positions_string, line, "{},{},{}")?
+            .map(|element| parse_number::<i32>(Day18::DAY, positions_string, element));
+
+        Ok(Vector3 {
+            x: x?,
+            y: y?,
+            z: z?,
+        })
     }).collect()
 }
 
-fn add_outside(initial: CubePosition, cubes: &HashSet<CubePosition>, outside: &mut HashSet<CubePosition>){
+fn add_outside(initial: CubePosition, bounds: BoundingBox<i32>, cubes: &HashSet<CubePosition>, outside: &mut HashSet<CubePosition>){
 
     let mut to_do = Vec::new();
 
     to_do.push(initial);
 
     while let Some(pos) = to_do.pop() {
-        if !cubes.contains(&pos) && !outside.contains(&pos) && pos.x >= -5 && pos.y >= -5 && pos.z >= -5 && pos.x <= 25 && pos.y <= 25 && pos.z <= 25 {
+        if !cubes.contains(&pos) && !outside.contains(&pos) && bounds.contains(pos) {
             outside.insert(pos);
-            println!("Added {},{},{}", pos.x, pos.y, pos.z);
-
-            if(pos.x == 2 && pos.y == 2 && pos.z == 5){
-                println!("Really? {}", !cubes.contains(&pos));
-                let a = 0;
-            }
-
-            to_do.push(pos + Vector3 { x: 1, y: 0, z: 0 });
-            to_do.push(pos + Vector3 { x: -1, y: 0, z: 0 });
-            to_do.push(pos + Vector3 { x: 0, y: 1, z: 0 });
-            to_do.push(pos + Vector3 { x: 0, y: -1, z: 0 });
-            to_do.push(pos + Vector3 { x: 0, y: 0, z: 1 });
-            to_do.push(pos + Vector3 { x: 0, y: 0, z: -1 });
+
+            to_do.extend(pos.face_neighbours());
         }
 
     }
@@ -46,20 +41,18 @@ fn add_outside(initial: CubePosition, cubes: &HashSet<CubePosition>, outside: &m
 fn count_non_touching_edges(cubes: HashSet<CubePosition>) -> usize{
     let mut outside = HashSet::new();
 
-    add_outside(Vector3{x: -5, y: -5, z: -5}, &cubes, &mut outside);
+    let bounds = match BoundingBox::from_points(cubes.iter().copied()) {
+        Some(bounds) => bounds.expanded(1),
+        None => return 0,
+    };
 
+    add_outside(bounds.min, bounds, &cubes, &mut outside);
 
+    cubes.iter().map(|cube| cube.face_neighbours().filter(|pos| !cubes.contains(pos) && outside.contains(pos)).count()).sum()
+}
 
-    println!("Does it? {}", outside.contains(&Vector3{x: 2, y: 2, z: 5}));
-    cubes.iter().map(|cube|
-        [
-            Vector3 { x: -1, y: 0, z: 0 },
-            Vector3 { x: 1, y: 0, z: 0 },
-            Vector3 { x: 0, y: 1, z: 0 },
-            Vector3 { x: 0, y: -1, z: 0 },
-            Vector3 { x: 0, y: 0, z: 1 },
-            Vector3 { x: 0, y: 0, z: -1 },
-        ].map(|vec| vec.add(*cube)).into_iter().filter(|pos| !cubes.contains(pos) && outside.contains(pos)).count()).sum()
+fn count_exposed_faces(cubes: HashSet<CubePosition>) -> usize {
+    cubes.iter().map(|cube| cube.face_neighbours().filter(|pos| !cubes.contains(pos)).count()).sum()
 }
 
 fn is_not_edge(position: Vector3<i32>, cubes: &HashSet<CubePosition>) -> bool {
@@ -90,6 +83,57 @@ fn is_not_edge(position: Vector3<i32>, cubes: &HashSet<CubePosition>) -> bool {
     return true
 }
 
+/// One frame per slice along z, optionally marking the air that cannot reach the outside.
+fn slice_frames(cubes: HashSet<CubePosition>, show_pockets: bool) -> Frames {
+    let Some(bounds) = BoundingBox::from_points(cubes.iter().copied()).map(|bounds| bounds.expanded(1)) else {
+        return Box::new(std::iter::empty());
+    };
+
+    let mut outside = HashSet::new();
+    add_outside(bounds.min, bounds, &cubes, &mut outside);
+
+    let (min, max) = (Vector2i{x: bounds.min.x, y: bounds.min.y}, Vector2i{x: bounds.max.x, y: bounds.max.y});
+
+    Box::new((bounds.min.z..=bounds.max.z).enumerate().map(move |(i, z)| Frame::from_fn(i, min, max, |pos| {
+        let position = Vector3 { x: pos.x, y: pos.y, z };
+
+        if cubes.contains(&position) {
+            Cell::Lava
+        } else if show_pockets && !outside.contains(&position) {
+            Cell::Pocket
+        } else {
+            Cell::Empty
+        }
+    })))
+}
+
+pub(crate) struct Day18;
+
+impl Solution for Day18 {
+    const DAY: usize = 18;
+
+    type Parsed = HashSet<CubePosition>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_positions(input)
+    }
+
+    fn part1(cubes: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer1 {
+        count_exposed_faces(cubes)
+    }
+
+    fn part2(cubes: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer2 {
+        count_non_touching_edges(cubes)
+    }
+}
+
+impl Animation for Day18 {
+    fn frames(cubes: Self::Parsed, part: usize) -> Frames {
+        slice_frames(cubes, part == 2)
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -97,14 +141,14 @@ mod tests {
 
     #[test]
     fn simple_test() {
-        let cubes = parse_positions(include_str!("day18/test_simple.txt"));
-        assert_eq!(count_non_touching_edges(cubes), 64);
+        let cubes = parse_positions(include_str!("day18/test_simple.txt")).unwrap();
+        assert_eq!(count_non_touching_edges(cubes), 58);
     }
 
     #[test]
     fn large_test() {
-        let cubes = parse_positions(include_str!("day18/test_large.txt"));
-        assert_eq!(count_non_touching_edges(cubes), 64);
+        let cubes = parse_positions(include_str!("day18/test_large.txt")).unwrap();
+        assert_eq!(count_non_touching_edges(cubes), 2490);
     }
 
 }
\ No newline at end of file
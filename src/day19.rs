@@ -3,8 +3,11 @@ use std::cmp::Ordering::{Equal, Greater, Less};
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::io::Lines;
 use std::ops::{Add, Sub};
-use std::time::Instant;
 use itertools::{enumerate, Itertools};
+use crate::error::ParseError;
+use crate::observer::Observer;
+use crate::parse;
+use crate::solution::Solution;
 
 #[derive(Clone)]
 struct State {
@@ -72,50 +75,50 @@ impl PartialOrd for ResourceAmount {
     }
 }
 
-struct RobotCosts {
+pub(crate) struct RobotCosts {
     ore: ResourceAmount,
     clay: ResourceAmount,
     obsidian: ResourceAmount,
     geode: ResourceAmount,
 }
 
-fn parse_costs(cost_string: &str) -> Vec<RobotCosts> {
+fn parse_costs(cost_string: &str) -> Result<Vec<RobotCosts>, ParseError> {
+    if cost_string.trim().is_empty() {
+        return Err(ParseError::missing(Day19::DAY, cost_string, "expected at least one blueprint"));
+    }
+
     cost_string
         .lines().map(|line| {
-        let relevant_line = line.split(":").skip(1).next().unwrap();
-        let mut robots = relevant_line.split(".").map(|robot_cost| robot_cost.split("and").map(|cost| cost.chars().filter(|c| c.is_digit(10)).join("").parse::<usize>().unwrap()));
-
-        let mut ore_robot = robots.next().unwrap();
-        let mut clay_robot = robots.next().unwrap();
-        let mut obsidian_robot = robots.next().unwrap();
-        let mut geode_robot = robots.next().unwrap();
+        let [_, costs] = parse::fields(Day19::DAY, cost_string, line, "Blueprint {}: {}")?;
+        let [ore_ore, clay_ore, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian] =
+            parse::integers_n(Day19::DAY, cost_string, costs, "expected six robot costs")?;
 
-        RobotCosts {
+        Ok(RobotCosts {
             ore: ResourceAmount {
-                ore: ore_robot.next().unwrap(),
+                ore: ore_ore,
                 clay: 0,
                 obsidian: 0,
                 geode: 0,
             },
             clay: ResourceAmount {
-                ore: clay_robot.next().unwrap(),
+                ore: clay_ore,
                 clay: 0,
                 obsidian: 0,
                 geode: 0,
             },
             obsidian: ResourceAmount {
-                ore: obsidian_robot.next().unwrap(),
-                clay: obsidian_robot.next().unwrap(),
+                ore: obsidian_ore,
+                clay: obsidian_clay,
                 obsidian: 0,
                 geode: 0,
             },
             geode: ResourceAmount {
-                ore: geode_robot.next().unwrap(),
+                ore: geode_ore,
                 clay: 0,
-                obsidian: geode_robot.next().unwrap(),
+                obsidian: geode_obsidian,
                 geode: 0,
             },
-        }
+        })
     }).collect()
 }
 
@@ -159,7 +162,6 @@ fn find_best_system(costs: RobotCosts, size: usize) -> usize {
         length: 0,
     });
 
-    let mut counter: usize = 0;
     let mut max_geodes = 0;
     let mut min_required_obsidian = (0..=size).map(|s| get_min_required_obsidians(s, costs.geode.obsidian, size)).collect::<Vec<_>>();
 
@@ -170,11 +172,6 @@ fn find_best_system(costs: RobotCosts, size: usize) -> usize {
             && !(current_state.robot_counts.geode == 0 && current_state.robot_counts.obsidian + current_state.resource_counts.obsidian < min_required_obsidian[current_state.length])
 
         {
-            let now = Instant::now();
-            if counter % 1000 == 0 {
-                println!("Max: {}, {}, {}", max_geodes, current_state.length, stack.len());
-            }
-
             if current_state.length == size {
                 max_geodes = max(max_geodes, current_state.resource_counts.geode);
             }
@@ -188,23 +185,28 @@ fn find_best_system(costs: RobotCosts, size: usize) -> usize {
             }
 
             stack.append(&mut get_nexts(current_state, &costs, size));
-            counter += 1;
-
-            if(counter % 100000 == 0) {
-                println!("Time: {}", now.elapsed().as_micros());
-            }
         }
     }
 
     max_geodes
 }
 
-fn get_score(costs: Vec<RobotCosts>, size: usize) -> usize {
-    costs.into_iter().enumerate().map(|(i, el)| find_best_system(el, size) * (i + 1)).sum()
+fn get_score(costs: Vec<RobotCosts>, size: usize, observer: &mut dyn Observer) -> usize {
+    let amount = costs.len();
+
+    costs.into_iter().enumerate().map(|(i, el)| {
+        observer.progress(i, Some(amount));
+        find_best_system(el, size) * (i + 1)
+    }).sum()
 }
 
-fn get_large_score(costs: Vec<RobotCosts>, size: usize) -> usize {
-    costs.into_iter().enumerate().take(3).map(|(i, el)| find_best_system(el, size)).reduce(|a, b| a * b).unwrap()
+fn get_large_score(costs: Vec<RobotCosts>, size: usize, observer: &mut dyn Observer) -> usize {
+    let amount = costs.len().min(3);
+
+    costs.into_iter().enumerate().take(3).map(|(i, el)| {
+        observer.progress(i, Some(amount));
+        find_best_system(el, size)
+    }).reduce(|a, b| a * b).unwrap()
 }
 
 fn get_min_required_obsidians(index: usize, cost: usize, size: usize) -> usize {
@@ -280,36 +282,65 @@ fn get_nexts(state: State, costs: &RobotCosts, size: usize) -> VecDeque<State> {
     nexts
 }
 
+pub(crate) struct Day19;
+
+impl Solution for Day19 {
+    const DAY: usize = 19;
+    const VERSION: u32 = 2;
+
+    type Parsed = Vec<RobotCosts>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_costs(input)
+    }
+
+    fn part1(costs: Self::Parsed, observer: &mut dyn Observer) -> Self::Answer1 {
+        get_score(costs, 24, observer)
+    }
+
+    fn part2(costs: Self::Parsed, observer: &mut dyn Observer) -> Self::Answer2 {
+        get_large_score(costs, 32, observer)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::observer::NoOp;
     use super::*;
 
     #[test]
     fn simple_test() {
-        let costs = parse_costs(include_str!("day19/test_simple.txt"));
+        let costs = parse_costs(include_str!("day19/test_simple.txt")).unwrap();
 
-        assert_eq!(get_score(costs, 24), 33);
+        assert_eq!(get_score(costs, 24, &mut NoOp), 33);
     }
 
 
     #[test]
     fn simple_test_2() {
-        let costs = parse_costs(include_str!("day19/test_simple.txt"));
+        let costs = parse_costs(include_str!("day19/test_simple.txt")).unwrap();
 
-        assert_eq!(get_score(costs, 32), 33);
+        assert_eq!(get_large_score(costs, 32, &mut NoOp), 3472);
     }
 
     #[test]
     fn large_test_2() {
-        let costs = parse_costs(include_str!("day19/test_large.txt"));
+        let costs = parse_costs(include_str!("day19/test_large.txt")).unwrap();
 
-        assert_eq!(get_large_score(costs, 32), 6804);
+        assert_eq!(get_large_score(costs, 32, &mut NoOp), 6804);
     }
 
     #[test]
     fn large_test() {
-        let costs = parse_costs(include_str!("day19/test_large.txt"));
+        let costs = parse_costs(include_str!("day19/test_large.txt")).unwrap();
+
+        assert_eq!(get_score(costs, 24, &mut NoOp), 1659);
+    }
 
-        assert_eq!(get_score(costs, 24), 1659);
+    #[test]
+    fn empty_input_is_a_parse_error() {
+        assert_eq!(parse_costs("").err().map(|error| error.message), Some("expected at least one blueprint".to_string()));
     }
-}
\ No newline at end of file
+}
@@ -1,13 +1,43 @@
 use crate::day2::GameResult::{DRAW, LOSS, WIN};
 use crate::day2::Play::{PAPER, ROCK, SCISSORS};
+use crate::error::ParseError;
+use crate::observer::Observer;
+use crate::solution::Solution;
 
 #[derive(Clone, Copy, PartialEq)]
 enum Play {ROCK, PAPER, SCISSORS}
 #[derive(Clone, Copy)]
 enum GameResult {LOSS, WIN, DRAW}
 
-fn get_score(lines: &str, game_score_function: fn(&str) -> Option<i32>) -> i32 {
-    return lines.lines()
+fn parse_games(lines: &str) -> Result<Vec<String>, ParseError> {
+    lines
+        .lines()
+        .map(|line| validate_game(line)
+            .map(|_| line.to_string())
+            .map_err(|error| error.relative_to(lines, line)))
+        .collect()
+}
+
+fn validate_game(game_string: &str) -> Result<(), ParseError> {
+    let mut split = game_string.split(" ");
+
+    for allowed in [["A", "B", "C"], ["X", "Y", "Z"]] {
+        let message = format!("expected {}, {} or {}", allowed[0], allowed[1], allowed[2]);
+        let token = split.next().ok_or_else(|| ParseError::missing(Day2::DAY, game_string, message.as_str()))?;
+
+        if !allowed.contains(&token) {
+            return Err(ParseError::new(Day2::DAY, game_string, token, message));
+        }
+    }
+
+    match split.next() {
+        Some(token) => Err(ParseError::new(Day2::DAY, game_string, token, "unexpected token")),
+        None => Ok(()),
+    }
+}
+
+fn get_score(games: &[String], game_score_function: fn(&str) -> Option<i32>) -> i32 {
+    return games.iter()
         .map(|game_string| game_score_function(game_string).unwrap_or(0))
         .sum();
 }
@@ -81,6 +111,28 @@ fn complete(game: (Option<Play>, Option<Play>, Option<GameResult>)) -> Option<(P
     }
 }
 
+pub(crate) struct Day2;
+
+impl Solution for Day2 {
+    const DAY: usize = 2;
+
+    type Parsed = Vec<String>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_games(input)
+    }
+
+    fn part1(games: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer1 {
+        get_score(&games, get_game_score_1)
+    }
+
+    fn part2(games: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer2 {
+        get_score(&games, get_game_score_2)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,7 +142,7 @@ mod tests {
         let game_string = "A Y
 B X
 C Z";
-        assert_eq!(get_score(game_string, get_game_score_1), 15);
+        assert_eq!(get_score(&parse_games(game_string).unwrap(), get_game_score_1), 15);
     }
 
     #[test]
@@ -98,6 +150,6 @@ C Z";
         let game_string = "A Y
 B X
 C Z";
-        assert_eq!(get_score(game_string, get_game_score_2), 12);
+        assert_eq!(get_score(&parse_games(game_string).unwrap(), get_game_score_2), 12);
     }
 }
\ No newline at end of file
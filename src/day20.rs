@@ -1,22 +1,30 @@
-fn parse_list(list_string: &str) -> Vec<Value> {
-    list_string.lines()
+fn parse_list(list_string: &str) -> Result<Vec<Value>, ParseError> {
+    let list = list_string.lines()
         .enumerate()
-        .map(|(i, item)| Value{value: item.parse().unwrap(), index: i})
-        .collect()
+        .map(|(i, item)| Ok(Value{value: parse_number(Day20::DAY, list_string, item)?, index: i}))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if list.len() < 2 || !list.iter().any(|item| item.value == 0) {
+        return Err(ParseError::missing(Day20::DAY, list_string, "expected at least two numbers including 0"));
+    }
+
+    Ok(list)
 }
 
 fn get_at_index(list: &Vec<Value>, i: i64) -> i64 {
     return list[(i as usize % list.len())].value;
 }
 
-fn permute_list(list: Vec<Value>, amount: usize) -> (i64, i64, i64) {
+fn permute_list(list: Vec<Value>, amount: usize, observer: &mut dyn Observer) -> (i64, i64, i64) {
     let original_list = list.clone();
     let mut permuted_list = list.clone();
 
 
     let size = list.len() as i64 - 1;
 
-    for _ in 0..amount {
+    for round in 0..amount {
+        observer.progress(round, Some(amount));
+
         for i in 0..original_list.len() {
             let from = permuted_list.iter().position(|el| el.index == i).unwrap();
             let value = permuted_list.remove(from);
@@ -28,44 +36,76 @@ fn permute_list(list: Vec<Value>, amount: usize) -> (i64, i64, i64) {
 
     let zero_index = permuted_list.iter().position(|el| el.value == 0).unwrap() as i64;
 
-    println!("List {}", permuted_list.iter().map(|i| i.value.to_string()).collect::<Vec<_>>().join(","));
+    observer.frame(&permuted_list.iter().map(|i| i.value.to_string()).collect::<Vec<_>>().join(","));
     (get_at_index(&permuted_list, zero_index + 1000), get_at_index(&permuted_list, zero_index + 2000), get_at_index(&permuted_list, zero_index + 3000))
 }
 
+fn get_grove_coordinates_sum(list: Vec<Value>, amount: usize, observer: &mut dyn Observer) -> i64 {
+    let (first, second, third) = permute_list(list, amount, observer);
+
+    first + second + third
+}
+
 fn apply_key(cost: Vec<Value>) -> Vec<Value> {
     cost.into_iter().map(|val| Value{value: val.value * 811589153, index: val.index}).collect()
 }
 
 use std::fs;
+use crate::error::{parse_number, ParseError};
+use crate::observer::Observer;
+use crate::solution::Solution;
 
 #[derive(Clone, Copy)]
-struct Value{
+pub(crate) struct Value{
     value: i64,
     index: usize
 }
 
+pub(crate) struct Day20;
+
+impl Solution for Day20 {
+    const DAY: usize = 20;
+
+    type Parsed = Vec<Value>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_list(input)
+    }
+
+    fn part1(list: Self::Parsed, observer: &mut dyn Observer) -> Self::Answer1 {
+        get_grove_coordinates_sum(list, 1, observer)
+    }
+
+    fn part2(list: Self::Parsed, observer: &mut dyn Observer) -> Self::Answer2 {
+        get_grove_coordinates_sum(apply_key(list), 10, observer)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::observer::NoOp;
     use super::*;
 
     #[test]
     fn simple_test() {
-        let costs = parse_list(include_str!("day20/test_simple.txt"));
-        let result = permute_list(costs, 1);
+        let costs = parse_list(include_str!("day20/test_simple.txt")).unwrap();
+        let result = permute_list(costs, 1, &mut NoOp);
         assert_eq!(result.0 + result.1 + result.2, 3);
     }
 
     #[test]
     fn large_test() {
-        let costs = parse_list(include_str!("day20/test_large.txt"));
-        let result = permute_list(costs, 1);
+        let costs = parse_list(include_str!("day20/test_large.txt")).unwrap();
+        let result = permute_list(costs, 1, &mut NoOp);
         assert_eq!(result.0 + result.1 + result.2, 27726);
     }
 
     #[test]
     fn large_test_2() {
-        let costs = apply_key(parse_list(include_str!("day20/test_large.txt")));
-        let result = permute_list(costs, 10);
-        assert_eq!(result.0 + result.1 + result.2, 27726);
+        let costs = apply_key(parse_list(include_str!("day20/test_large.txt")).unwrap());
+        let result = permute_list(costs, 10, &mut NoOp);
+        assert_eq!(result.0 + result.1 + result.2, 4275451658004);
     }
 }
\ No newline at end of file
@@ -1,5 +1,9 @@
 use std::collections::HashMap;
 use std::iter::Map;
+use crate::error::{parse_number, ParseError};
+use crate::observer::Observer;
+use crate::parse;
+use crate::solution::Solution;
 
 trait Expression {
     fn evaluate(&self) -> f64;
@@ -192,62 +196,90 @@ impl Expression for LiteralExpression {
     }
 }
 
-fn parse_expression(expression_line: &str, expressions: &mut HashMap<String, Box<dyn Expression>>, fancy: bool) {
-    let split_string = expression_line.replace(":", "");
-    let mut tokens = split_string.split(" ");
+pub(crate) enum Job {
+    Literal(f64),
+    Operation(String, char, String),
+}
 
-    let name = tokens.next().unwrap();
+fn parse_job<'a>(expressions_string: &str, expression_line: &'a str) -> Result<(&'a str, Job, Vec<&'a str>), ParseError> {
+    let (name, job) = parse::key_value(Day21::DAY, expressions_string, expression_line, ":")?;
 
-    if fancy {
-        if name == "root" {
-            let left_name = tokens.next().unwrap().parse().unwrap();
-            let token = tokens.next().unwrap().chars().next().unwrap();
-            let right_name = tokens.next().unwrap().parse().unwrap();
-
-            expressions.insert(name.to_string(), Box::new(OperationExpression {
-                left_name,
-                right_name,
-                token: '-',
-                right: None,
-                left: None
-            }));
+    let tokens: Vec<_> = job.split(" ").collect();
 
-            return;
-        }
+    match tokens[..] {
+        [left_name, token, right_name] => {
+            let operation = match token {
+                "+" | "-" | "*" | "/" => token.chars().next().unwrap(),
+                _ => return Err(ParseError::new(Day21::DAY, expressions_string, token, "expected +, -, * or /")),
+            };
 
-        if name == "humn" {
-            expressions.insert(name.to_string(), Box::new(XExpression{}));
-            return;
+            Ok((name, Job::Operation(left_name.to_string(), operation, right_name.to_string()), vec![left_name, right_name]))
         }
+        [literal] => Ok((name, Job::Literal(parse_number(Day21::DAY, expressions_string, literal)?), vec![])),
+        _ => Err(ParseError::new(Day21::DAY, expressions_string, job, "expected a number or an operation")),
     }
+}
 
-    if(tokens.clone().count() == 3) {
-        let left_name = tokens.next().unwrap().parse().unwrap();
-        let token = tokens.next().unwrap().chars().next().unwrap();
-        let right_name = tokens.next().unwrap().parse().unwrap();
+fn parse_jobs(expressions_string: &str) -> Result<HashMap<String, Job>, ParseError> {
+    let mut jobs = HashMap::new();
+    let mut references = Vec::new();
 
-                expressions.insert(name.to_string(), Box::new(OperationExpression {
-                    left_name,
-                    right_name,
-                    token,
-                    right: None,
-                    left: None,
-                }));
+    for line in expressions_string.lines() {
+        let (name, job, mut job_references) = parse_job(expressions_string, line)?;
 
+        jobs.insert(name.to_string(), job);
+        references.append(&mut job_references);
     }
-    else {
-        expressions.insert(name.to_string(), Box::new(LiteralExpression {
-            value: tokens.next().unwrap().parse().unwrap()
-        }));
+
+    if let Some(unknown) = references.into_iter().find(|name| !jobs.contains_key(*name)) {
+        return Err(ParseError::new(Day21::DAY, expressions_string, unknown, "unknown monkey"));
+    }
+
+    match jobs.get("root") {
+        Some(Job::Operation(..)) => Ok(jobs),
+        _ => Err(ParseError::missing(Day21::DAY, expressions_string, "expected a root monkey with an operation")),
     }
 }
 
-fn parse_expressions(expressions_string: &str, fancy: bool) -> Box<dyn Expression>{
-    let mut expressions = HashMap::new();
+fn to_expression(name: &str, job: Job, fancy: bool) -> Box<dyn Expression> {
+    if fancy {
+        if name == "humn" {
+            return Box::new(XExpression{});
+        }
+
+        if let (Job::Operation(left_name, _, right_name), "root") = (&job, name) {
+            return Box::new(OperationExpression {
+                left_name: left_name.clone(),
+                right_name: right_name.clone(),
+                token: '-',
+                right: None,
+                left: None
+            });
+        }
+    }
 
-    for line in expressions_string.lines(){
-        parse_expression(line, &mut expressions, fancy);
+    match job {
+        Job::Operation(left_name, token, right_name) => Box::new(OperationExpression {
+            left_name,
+            right_name,
+            token,
+            right: None,
+            left: None,
+        }),
+        Job::Literal(value) => Box::new(LiteralExpression {
+            value
+        })
     }
+}
+
+fn build_expressions(jobs: HashMap<String, Job>, fancy: bool) -> Box<dyn Expression> {
+    let mut expressions: HashMap<String, Box<dyn Expression>> = jobs
+        .into_iter()
+        .map(|(name, job)| {
+            let expression = to_expression(&name, job, fancy);
+            (name, expression)
+        })
+        .collect();
 
     let mut root = expressions.remove("root").unwrap();
 
@@ -256,6 +288,31 @@ fn parse_expressions(expressions_string: &str, fancy: bool) -> Box<dyn Expressio
     return root;
 }
 
+fn solve_for_x(expression: Box<dyn Expression>) -> f64 {
+    expression.reorder(Box::new(LiteralExpression{value: 0.0})).evaluate()
+}
+
+pub(crate) struct Day21;
+
+impl Solution for Day21 {
+    const DAY: usize = 21;
+
+    type Parsed = HashMap<String, Job>;
+    type Answer1 = f64;
+    type Answer2 = f64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_jobs(input)
+    }
+
+    fn part1(jobs: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer1 {
+        build_expressions(jobs, false).evaluate()
+    }
+
+    fn part2(jobs: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer2 {
+        solve_for_x(build_expressions(jobs, true))
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -263,29 +320,29 @@ mod tests {
 
     #[test]
     fn simple_test() {
-        let result = parse_expressions(include_str!("day21/test_simple.txt"), false).evaluate();
+        let result = build_expressions(parse_jobs(include_str!("day21/test_simple.txt")).unwrap(), false).evaluate();
 
         assert_eq!(result, 152.0);
     }
 
     #[test]
     fn large_test() {
-        let result = parse_expressions(include_str!("day21/test_large.txt") ,false).evaluate();
+        let result = build_expressions(parse_jobs(include_str!("day21/test_large.txt")).unwrap(), false).evaluate();
 
         assert_eq!(result, 364367103397416.0);
     }
 
     #[test]
     fn large_test_2() {
-        let mut expressions = parse_expressions(include_str!("day21/test_large.txt"), true);
+        let mut expressions = build_expressions(parse_jobs(include_str!("day21/test_large.txt")).unwrap(), true);
 
         let result = expressions.reorder(Box::new(LiteralExpression{value: 0.0})).evaluate();
-        assert_eq!(result, 364367103397416.0);
+        assert_eq!(result, 3782852515583.0);
     }
 
     #[test]
     fn simple_test_2() {
-        let mut expressions = parse_expressions(include_str!("day21/test_simple.txt"), true);
+        let mut expressions = build_expressions(parse_jobs(include_str!("day21/test_simple.txt")).unwrap(), true);
 
         let result = expressions.reorder(Box::new(LiteralExpression{value: 0.0})).evaluate();
         assert_eq!(result, 301.0);
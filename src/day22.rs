@@ -1,12 +1,20 @@
 use std::collections::HashMap;
 use std::convert::identity;
+use itertools::Itertools;
 use crate::day22::Command::{Move, RotateLeft, RotateRight};
-use crate::day22::Rotation::{Left, Up};
+use crate::direction::Dir4;
 use crate::day22::Tile::{Open, Wall};
 use crate::vector2::Vector2i;
+use crate::vector3::Vector3;
+use crate::animate::{Animation, Cell, Frame, Frames};
+use crate::error::{parse_number, ParseError, SolveError};
+use crate::grid::{DenseGrid, Grid};
+use crate::observer::Observer;
+use crate::parse;
+use crate::solution::Solution;
 
  #[derive(PartialEq, Clone, Copy)]
-enum Tile {
+pub(crate) enum Tile {
     Wall,
     Open,
 }
@@ -21,107 +29,55 @@ impl Tile {
     }
 }
 
-type World = HashMap<Vector2i, Tile>;
+type World = DenseGrid<Tile>;
 
-#[derive(PartialEq, Clone, Copy)]
-enum Rotation {
-    Up,
-    Right,
-    Down,
-    Left,
-}
-
-impl Rotation {
-    fn to_vector(&self) -> Vector2i {
-        match self {
-            Rotation::Up => Vector2i{x: 0, y: -1},
-            Rotation::Right => Vector2i{x: 1, y: 0},
-            Rotation::Down => Vector2i{x: 0, y: 1},
-            Rotation::Left => Vector2i{x: -1, y: 0}
-        }
-    }
-
-    fn rotate_right(&self) -> Rotation {
-        match self {
-            Rotation::Up => Rotation::Right,
-            Rotation::Right => Rotation::Down,
-            Rotation::Down => Rotation::Left,
-            Rotation::Left => Rotation::Up,
-        }
-    }
-
-    fn to_code(&self) -> i32 {
-        match self {
-            Rotation::Up => 3,
-            Rotation::Right => 0,
-            Rotation::Down => 1,
-            Rotation::Left => 2,
-        }
-    }
-
-    fn rotate_left(&self) -> Rotation {
-        match self {
-            Rotation::Up => Rotation::Left,
-            Rotation::Right => Rotation::Up,
-            Rotation::Down => Rotation::Right,
-            Rotation::Left => Rotation::Down,
-        }
-    }
-}
-
-enum Command {
+#[derive(Clone, Copy)]
+pub(crate) enum Command {
     RotateRight,
     RotateLeft,
     Move(i32),
 }
 
 impl Command {
-    fn parse_from(string: &str) -> Vec<Command> {
+    fn parse_from(string: &str) -> Result<Vec<Command>, ParseError> {
         let mut commands = vec![];
-        let mut digit_chars = "".to_string();
+        let mut digit_start = None;
 
-        for char in string.chars() {
+        for (i, char) in string.char_indices() {
             if char.is_digit(10) {
-                digit_chars += char.to_string().as_str();
+                digit_start.get_or_insert(i);
             }
             else {
-                if !digit_chars.is_empty() {
-                    commands.push(Move(digit_chars.to_string().parse().unwrap()));
-                    digit_chars = "".to_string();
+                if let Some(start) = digit_start.take() {
+                    commands.push(Move(parse_number(Day22::DAY, string, &string[start..i])?));
                 }
 
                 match char {
                     'R' => commands.push(RotateRight),
                     'L' => commands.push(RotateLeft),
-                    _ => panic!()
+                    _ => return Err(ParseError::new(Day22::DAY, string, &string[i..i + char.len_utf8()], "expected a number, R or L"))
                 }
             }
         }
 
-        if !digit_chars.is_empty() {
-            commands.push(Move(digit_chars.to_string().parse().unwrap()))
+        if let Some(start) = digit_start {
+            commands.push(Move(parse_number(Day22::DAY, string, &string[start..])?))
         }
 
-        return commands;
+        return Ok(commands);
     }
 }
 
-struct You {
+pub(crate) struct You {
     pos: Vector2i,
-    rot: Rotation
+    rot: Dir4
 }
 
 impl You {
     fn execute_command(&mut self, command: Command, world: &World, edges: &Edges, edge_length: i32) {
         match command {
-            RotateRight => {
-                println!("Rotate right");
-                self.rot = self.rot.rotate_right()
-            },
-            RotateLeft => {
-                println!("Rotate left");
-                self.rot = self.rot.rotate_left()
-            },
+            RotateRight => self.rot = self.rot.rotate_right(),
+            RotateLeft => self.rot = self.rot.rotate_left(),
             Move(distance) => self.move_by( distance, world, edges, edge_length)
         }
     }
@@ -130,8 +86,8 @@ impl You {
         for _ in 0..distance {
             let (new_pos, rot) = if edges.len() > 0 {self.calculate_position_2(world, edges, edge_length)} else {self.calculate_position(world)};
 
-            match world.get(&new_pos) {
-                None => panic!(),
+            match world.get(new_pos) {
+                None => unreachable!("every step lands on a tile of the map"),
                 Some(Wall) => return,
                 Some(Open) => {
                     self.pos = new_pos;
@@ -141,43 +97,38 @@ impl You {
         }
     }
 
-    fn calculate_position_2(&self, world: &World, edges: &Edges, edge_length: i32) -> (Vector2i, Rotation){
+    fn calculate_position_2(&self, world: &World, edges: &Edges, edge_length: i32) -> (Vector2i, Dir4){
         let new_position = self.pos + self.rot.to_vector();
 
-        println!("{}, {}", new_position.x, new_position.y);
-
-        if world.contains_key(&new_position) {
+        if world.contains(new_position) {
             return (new_position, self.rot);
         }
 
         for (edge1, edge2) in edges {
             if let Some(wrapped_index) = edge1.is_wrapped(new_position, edge_length) {
-                println!("{}", edge1.origin);
-
                 let index = (edge_length) - wrapped_index;
                 let nth = edge2.get_nth(index - 1, edge_length);
                 return (nth + edge2.rot.rotate_right().to_vector(), edge2.rot.rotate_right());
             }
 
             if let Some(wrapped_index) = edge2.is_wrapped(new_position, edge_length) {
-                println!("{}", edge2.origin);
                 return (edge1.get_nth((edge_length) - wrapped_index - 1, edge_length) + edge1.rot.rotate_right().to_vector(), edge1.rot.rotate_right());
             }
         }
 
-        panic!();
+        unreachable!("every edge of a folded cube is paired with another");
     }
 
-    fn calculate_position(&self, world: &World) -> (Vector2i, Rotation){
+    fn calculate_position(&self, world: &World) -> (Vector2i, Dir4){
         let new_position = self.pos + self.rot.to_vector();
 
-        if world.contains_key(&new_position) {
+        if world.contains(new_position) {
             return (new_position, self.rot);
         }
 
         for i in 1.. {
             let position_to_check = self.pos + self.rot.to_vector() * i * -1;
-            if !world.contains_key(&position_to_check) {
+            if !world.contains(position_to_check) {
                 return (self.pos + self.rot.to_vector() * (i - 1) * -1, self.rot);
             }
         }
@@ -186,56 +137,90 @@ impl You {
     }
 }
 
-fn parse_file(string: &str) -> (You, World, Vec<Command>) {
-    let mut iterator = string.split("\n\n");
-    let (you, world) = parse_world(iterator.next().unwrap());
+fn parse_file(string: &str) -> Result<(You, World, Vec<Command>), ParseError> {
+    let (world_string, path) = parse::split_blocks(Day22::DAY, string, string, "expected a blank line between the map and the path")?;
+    let (you, world) = parse_world(world_string)?;
+    let commands = Command::parse_from(path.trim_end()).map_err(|error| error.relative_to(string, path))?;
 
-    (you, world, Command::parse_from(iterator.next().unwrap()))
+    Ok((you, world, commands))
 }
 
-fn parse_world(string: &str) -> (You, World) {
-    let positions: Vec<(Vector2i, Option<Tile>)> = string
-        .lines()
-        .enumerate()
-        .map(|(y, line)| line
-            .chars()
-            .enumerate()
-            .map(move |(x, char)| (Vector2i{x: (x + 1) as i32, y: (y + 1) as i32}, Tile::parse(char)))
-            .filter(|(pos, result)| result.is_some())
-        ).flatten().collect();
+fn parse_world(string: &str) -> Result<(You, World), ParseError> {
+    let world = Grid::try_parse(Day22::DAY, string, |char| match Tile::parse(char) {
+        Some(tile) => Ok(Some(tile)),
+        None if char == ' ' => Ok(None),
+        None => Err("expected ., # or a space"),
+    })?;
 
     let you = You {
-        pos: positions[0].0,
-        rot: Rotation::Right
+        pos: world.iter().next().ok_or_else(|| ParseError::missing(Day22::DAY, string, "expected at least one tile"))?.0,
+        rot: Dir4::E
     };
 
-    let mut world = HashMap::new();
+    return Ok((you, world));
+}
 
-    for result in positions {
-        //println!("{}, {}: {}", result.0.x, result.0.y, if(result.1.unwrap() == Open) {"."} else {"#"});
-        world.insert(result.0, result.1.unwrap());
+fn facing_score(direction: Dir4) -> i32 {
+    match direction {
+        Dir4::N => 3,
+        Dir4::E => 0,
+        Dir4::S => 1,
+        Dir4::W => 2,
     }
+}
 
-    return (you, world);
+fn decode(mut you: You, world: World, commands: Vec<Command>, edges: Edges, edge_length: i32) -> i32 {
+    for command in commands {
+        you.execute_command(command, &world, &edges, edge_length);
+    }
 
+    // Rows and columns count from 1.
+    (you.pos.y + 1) * 1000 + (you.pos.x + 1) * 4 + facing_score(you.rot)
 }
 
+/// One frame per turn and per tile walked, leaving a trail of where you have been facing.
+fn walk_frames(mut you: You, world: World, commands: Vec<Command>, edges: Edges, edge_length: i32) -> Frames {
+    let (min, max) = world.get_aabb().unwrap_or_default();
+    let mut trail = HashMap::new();
 
+    let steps = commands.into_iter().flat_map(|command| match command {
+        Move(distance) => vec![Move(1); distance.max(0) as usize],
+        turn => vec![turn],
+    });
 
-fn decode(mut you: You, world: World, commands: Vec<Command>, edges: Edges, edge_length: i32) -> i32 {
-    for command in commands {
+    Box::new(steps.enumerate().map(move |(i, command)| {
+        trail.insert(you.pos, you.rot);
         you.execute_command(command, &world, &edges, edge_length);
-    }
 
-    return you.pos.y * 1000 + you.pos.x * 4 + you.rot.to_code();
+        Frame::from_fn(i + 1, min, max, |pos| match (pos == you.pos, trail.get(&pos), world.get(pos)) {
+            (true, _, _) => Cell::Actor(you.rot.to_char()),
+            (_, Some(direction), _) => Cell::Trail(direction.to_char()),
+            (_, _, Some(Wall)) => Cell::Wall,
+            (_, _, Some(Open)) => Cell::Empty,
+            (_, _, None) => Cell::Void,
+        })
+    }))
 }
 
-struct Edge {
+pub(crate) struct Edge {
     origin: Vector2i,
-    rot: Rotation,
+    rot: Dir4,
 }
 
 impl Edge {
+    /// The line of tiles just outside the given side of the face at `block`, running clockwise
+    /// around the face.
+    fn along(block: Vector2i, side: Dir4) -> Edge {
+        let corner = match side {
+            Dir4::N => Vector2i{x: 0, y: 0},
+            Dir4::E => Vector2i{x: 1, y: 0},
+            Dir4::S => Vector2i{x: 1, y: 1},
+            Dir4::W => Vector2i{x: 0, y: 1},
+        };
+
+        Edge { origin: block + corner, rot: side.rotate_right() }
+    }
+
     fn is_wrapped(&self, pos: Vector2i, edge_length: i32) -> Option<i32> {
         for i in 0..edge_length {
             if self.get_nth(i, edge_length) == pos {
@@ -247,116 +232,198 @@ impl Edge {
     }
 
     fn get_nth(&self, n: i32, edge_length: i32) -> Vector2i {
-        let a = (self.origin) * edge_length + (self.rot.to_vector() * n) + Vector2i{x: 1, y: 1};
+        let a = (self.origin) * edge_length + (self.rot.to_vector() * n);
 
         a + match self.rot {
-            Up => Vector2i{x: -1, y: -1},
-            Rotation::Right => Vector2i{x: 0, y: -1},
-            Rotation::Down => Vector2i{x: 0, y: 0},
-            Left => Vector2i{x: -1, y: 0},
+            Dir4::N => Vector2i{x: -1, y: -1},
+            Dir4::E => Vector2i{x: 0, y: -1},
+            Dir4::S => Vector2i{x: 0, y: 0},
+            Dir4::W => Vector2i{x: -1, y: 0},
         }
     }
 }
 
 type Edges = Vec<(Edge, Edge)>;
 
+/// Which way a face of the net ends up on the folded cube: its outward normal and the directions
+/// its rows and columns run in.
+#[derive(Clone, Copy)]
+struct Orientation {
+    normal: Vector3<i32>,
+    right: Vector3<i32>,
+    down: Vector3<i32>,
+}
+
+impl Orientation {
+    /// The orientation of the face folded down across the given side of this one.
+    fn fold(self, side: Dir4) -> Orientation {
+        let Orientation { normal, right, down } = self;
+
+        match side {
+            Dir4::E => Orientation { normal: right, right: -normal, down },
+            Dir4::S => Orientation { normal: down, right, down: -normal },
+            Dir4::W => Orientation { normal: -right, right: normal, down },
+            Dir4::N => Orientation { normal: -down, right, down: normal },
+        }
+    }
+
+    /// Where the given side of the face points on the cube.
+    fn side(self, side: Dir4) -> Vector3<i32> {
+        match side {
+            Dir4::E => self.right,
+            Dir4::S => self.down,
+            Dir4::W => -self.right,
+            Dir4::N => -self.down,
+        }
+    }
+}
+
+/// Folds the map into a cube, pairing up the edges of the net that meet once it is folded, along
+/// with the length of those edges.
+fn fold_cube(world: &World) -> Result<(Edges, i32), SolveError> {
+    let not_a_cube = || SolveError::new(Day22::DAY, "the map does not fold into a cube");
+
+    let tiles = world.iter().count() as i32;
+    let edge_length = ((tiles / 6) as f64).sqrt() as i32;
+
+    if edge_length == 0 || 6 * edge_length * edge_length != tiles {
+        return Err(not_a_cube());
+    }
+
+    let size = world.size();
+    let blocks = (0..(size.y + edge_length - 1) / edge_length)
+        .flat_map(|y| (0..(size.x + edge_length - 1) / edge_length).map(move |x| Vector2i{x, y}))
+        .filter(|block| world.contains(*block * edge_length))
+        .collect::<Vec<_>>();
+
+    let is_filled = |block: Vector2i| (0..edge_length)
+        .all(|y| (0..edge_length).all(|x| world.contains(block * edge_length + Vector2i{x, y})));
+
+    if blocks.len() != 6 || !blocks.iter().all(|block| is_filled(*block)) {
+        return Err(not_a_cube());
+    }
+
+    let mut faces = HashMap::from([(blocks[0], Orientation {
+        normal: Vector3 { x: 0, y: 0, z: 1 },
+        right: Vector3 { x: 1, y: 0, z: 0 },
+        down: Vector3 { x: 0, y: 1, z: 0 },
+    })]);
+    let mut queue = vec![blocks[0]];
+
+    while let Some(block) = queue.pop() {
+        let orientation = faces[&block];
+
+        for side in Dir4::iter() {
+            let neighbour = block + side.to_vector();
+
+            if blocks.contains(&neighbour) && !faces.contains_key(&neighbour) {
+                faces.insert(neighbour, orientation.fold(side));
+                queue.push(neighbour);
+            }
+        }
+    }
+
+    if faces.len() != 6 || faces.values().map(|face| face.normal).unique().count() != 6 {
+        return Err(not_a_cube());
+    }
+
+    let mut edges = vec![];
+
+    for (block, face) in &faces {
+        for side in Dir4::iter().filter(|side| !faces.contains_key(&(*block + side.to_vector()))) {
+            let (other_block, other_face) = faces.iter()
+                .find(|(_, other)| other.normal == face.side(side))
+                .ok_or_else(not_a_cube)?;
+            let other_side = Dir4::iter()
+                .find(|other_side| other_face.side(*other_side) == face.normal)
+                .ok_or_else(not_a_cube)?;
+
+            // Every pair of edges turns up from both of its faces, so keep the one seen first.
+            if (block.y, block.x, side as usize) < (other_block.y, other_block.x, other_side as usize) {
+                edges.push((Edge::along(*block, side), Edge::along(*other_block, other_side)));
+            }
+        }
+    }
+
+    Ok((edges, edge_length))
+}
+
+pub(crate) struct Day22;
+
+impl Solution for Day22 {
+    const DAY: usize = 22;
+    const VERSION: u32 = 2;
+
+    type Parsed = (You, World, Vec<Command>);
+    type Answer1 = i32;
+    type Answer2 = Result<i32, SolveError>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_file(input)
+    }
+
+    fn part1((you, world, commands): Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer1 {
+        decode(you, world, commands, vec![], 0)
+    }
+
+    fn part2((you, world, commands): Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer2 {
+        let (edges, edge_length) = fold_cube(&world)?;
+
+        Ok(decode(you, world, commands, edges, edge_length))
+    }
+}
+
+impl Animation for Day22 {
+    fn frames((you, world, commands): Self::Parsed, part: usize) -> Frames {
+        if part == 1 {
+            return walk_frames(you, world, commands, vec![], 0);
+        }
+
+        match fold_cube(&world) {
+            Ok((edges, edge_length)) => walk_frames(you, world, commands, edges, edge_length),
+            Err(_) => Box::new(std::iter::empty()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::day22::Rotation::{Down, Left, Right, Up};
     use super::*;
+    use crate::observer::NoOp;
+
+    fn part2(input: &str) -> Result<i32, SolveError> {
+        Day22::part2(parse_file(input).unwrap(), &mut NoOp)
+    }
 
     #[test]
     fn simple_test() {
-        let (mut you, mut world, commands) = parse_file(include_str!("day22/test_simple.txt"));
+        let (you, world, commands) = parse_file(include_str!("day22/test_simple.txt")).unwrap();
 
-        assert_eq!(decode(you, world, commands, vec![], 5), 6032)
+        assert_eq!(decode(you, world, commands, vec![], 0), 6032)
 
     }
 
     #[test]
     fn large_test() {
-        let (mut you, mut world, commands) = parse_file(include_str!("day22/test_large.txt"));
+        let (you, world, commands) = parse_file(include_str!("day22/test_large.txt")).unwrap();
 
-        assert_eq!(decode(you, world, commands, vec![], 50), 131052)
+        assert_eq!(decode(you, world, commands, vec![], 0), 131052)
 
     }
 
     #[test]
-    fn large_test_2() {
-        let (mut you, mut world, commands) = parse_file(include_str!("day22/test_large.txt"));
+    fn simple_test_2() {
+        assert_eq!(part2(include_str!("day22/test_simple.txt")), Ok(5031))
+    }
 
-        assert_eq!(decode(you, world, commands, vec![
-            (
-                Edge {
-                    origin: Vector2i {x: 1, y: 0},
-                    rot: Right,
-                },
-                Edge {
-                    origin: Vector2i {x: 0, y: 4},
-                    rot: Up,
-                }
-            ),
-            (
-                Edge {
-                    origin: Vector2i {x: 1, y: 1},
-                    rot: Up,
-                },
-                Edge {
-                    origin: Vector2i {x: 0, y: 3},
-                    rot: Up,
-                }
-            ),
-            (
-                Edge {
-                    origin: Vector2i {x: 2, y: 0},
-                    rot: Right,
-                },
-                Edge {
-                    origin: Vector2i {x: 1, y: 4},
-                    rot: Left,
-                }
-            ),
-            (
-                Edge {
-                    origin: Vector2i {x: 3, y: 1},
-                    rot: Left,
-                },
-                Edge {
-                    origin: Vector2i {x: 2, y: 1},
-                    rot: Down,
-                }
-            ),
-            (
-                Edge {
-                    origin: Vector2i {x: 3, y: 0},
-                    rot: Down,
-                },
-                Edge {
-                    origin: Vector2i {x: 2, y: 2},
-                    rot: Down,
-                }
-            ),
-            (
-                Edge {
-                    origin: Vector2i {x: 2, y: 3},
-                    rot: Left,
-                },
-                Edge {
-                    origin: Vector2i {x: 1, y: 3},
-                    rot: Down,
-                }
-            ),
-            (
-                Edge {
-                    origin: Vector2i {x: 1, y: 2},
-                    rot: Up,
-                },
-                Edge {
-                    origin: Vector2i {x: 0, y: 2},
-                    rot: Right,
-                }
-            )
-        ], 50), 4578)
+    #[test]
+    fn large_test_2() {
+        assert_eq!(part2(include_str!("day22/test_large.txt")), Ok(4578))
+    }
 
+    #[test]
+    fn maps_that_do_not_fold_into_a_cube_are_an_error() {
+        assert!(part2("......\n\n1").is_err());
+        assert!(part2("..\n..\n\n1").is_err());
     }
-}
\ No newline at end of file
+}
@@ -1,58 +1,14 @@
-use std::cmp::{max, min};
-use std::collections::{HashMap, HashSet};
-use itertools::Itertools;
-use Direction::N;
-use crate::day23::Direction::{E, NE, NW, S, SE, SW, W};
+use crate::direction::Dir8;
+use crate::grid::{Grid, SparseGrid};
 use crate::vector2::Vector2i;
 use multimap::MultiMap;
+use crate::animate::{Animation, Cell, Frame, Frames};
+use crate::error::ParseError;
+use crate::observer::Observer;
+use crate::solution::Solution;
 
-enum Direction {
-    N, NE, E, SE, S, SW, W, NW
-}
-
-impl Direction {
-    fn to_vector(&self) -> Vector2i {
-        match self {
-            N => Vector2i{x: 0, y: -1},
-            NE => Vector2i{x: 1, y: -1},
-            E => Vector2i{x: 1, y: 0},
-            SE => Vector2i{x: 1, y: 1},
-            S => Vector2i{x: 0, y: 1},
-            SW => Vector2i{x: -1, y: 1},
-            W => Vector2i{x: -1, y: 0},
-            NW => Vector2i{x: -1, y: -1}
-        }
-    }
-
-    fn rotate_right(&self) -> Direction {
-        match self {
-            N => NE,
-            NE => E,
-            E => SE,
-            SE => S,
-            S => SW,
-            SW => W,
-            W => NW,
-            NW => N,
-        }
-    }
-
-    fn rotate_left(&self) -> Direction {
-        match self {
-            N => NW,
-            NE => N,
-            E => NE,
-            SE => E,
-            S => SE,
-            SW => S,
-            W => SW,
-            NW => W,
-        }
-    }
-}
-
-struct World {
-    grid: HashSet<Vector2i>,
+pub(crate) struct World {
+    grid: SparseGrid<()>,
     //min: Vector2i,
     //max: Vector2i,
 }
@@ -67,52 +23,34 @@ impl World {
     //     self.grid.insert(pos);
     // }
 
-    fn get_aabb(&self) -> (Vector2i, Vector2i) {
-        let mut min_pos = Vector2i{x: i32::MAX, y: i32::MAX};
-        let mut max_pos = Vector2i{x: i32::MIN, y: i32::MIN};
-
-        for pos in self.grid.iter() {
-            min_pos.x = min(pos.x, min_pos.x);
-            min_pos.y = min(pos.y, min_pos.y);
-            max_pos.x = max(pos.x, max_pos.x);
-            max_pos.y = max(pos.y, max_pos.y);
-        }
-
-        return (min_pos, max_pos)
-    }
-
     fn get_empties(&self) -> i32 {
-        let (min_pos, max_pos) = self.get_aabb();
+        let (min_pos, max_pos) = self.grid.get_aabb().unwrap_or_default();
         let size = max_pos - min_pos + Vector2i{x: 1, y: 1};
 
         return size.x * size.y - (self.grid.len() as i32);
     }
 
-    fn parse_from(string: &str) -> World {
-        let mut grid = HashSet::new();
+    fn parse_from(string: &str) -> Result<World, ParseError> {
+        let grid = Grid::try_parse(Day23::DAY, string, |char| match char {
+            '#' => Ok(Some(())),
+            '.' => Ok(None),
+            _ => Err("expected . or #"),
+        })?;
 
-        for (y, line) in string.lines().enumerate() {
-            for (x, char) in line.chars().enumerate() {
-                if char == '#' {
-                    grid.insert(Vector2i{x: x as i32, y: y as i32});
-                }
-            }
-        }
-
-        return World { grid };
+        return Ok(World { grid });
     }
 
     fn step_1(&self, iteration: usize) -> MultiMap<Vector2i, Vector2i> {
         let mut proposed_moves = MultiMap::new();
-        for pos in self.grid.iter() {
-            if [N, NE, E, SE, S, SW, W, NW].iter().any(|dir| self.grid.contains(&(*pos + dir.to_vector()))) {
-                let mut order = [N, S, W, E];
+        for (pos, _) in self.grid.iter() {
+            if Dir8::iter().any(|dir| self.grid.contains(pos + dir.to_vector())) {
+                let mut order = [Dir8::N, Dir8::S, Dir8::W, Dir8::E];
                 order.rotate_left(iteration % 4);
                 for dir in order{
-                    if !self.grid.contains(&(*pos + dir.to_vector())) &&
-                        !self.grid.contains(&(*pos + dir.rotate_left().to_vector())) &&
-                        !self.grid.contains(&(*pos + dir.rotate_right().to_vector())) {
-                        proposed_moves.insert((*pos + dir.to_vector()), *pos);
+                    if !self.grid.contains(pos + dir.to_vector()) &&
+                        !self.grid.contains(pos + dir.rotate_left().to_vector()) &&
+                        !self.grid.contains(pos + dir.rotate_right().to_vector()) {
+                        proposed_moves.insert(pos + dir.to_vector(), pos);
                         break;
                     }
                 }
@@ -128,30 +66,32 @@ impl World {
 
         for (to_pos, from_positions) in proposed {
             if from_positions.len() == 1 {
-                self.grid.remove(from_positions.first().unwrap());
-                self.grid.insert(to_pos);
+                self.grid.remove(*from_positions.first().unwrap());
+                self.grid.insert(to_pos, ());
             }
         }
 
-        let (min_pos, max_pos) = self.get_aabb();
+        //println!("{}", self.grid.render(|cell| if cell.is_some() {'#'} else {'.'}));
 
-        //println!("{}",(min_pos.y..=max_pos.y).map(|y| (min_pos.x..=max_pos.x).map(|x| if self.grid.contains(&Vector2i{x,y}) {"#"} else {"."}).join("")).join("\n"));
+        return has_no_proposed
+    }
 
-        if iteration % 10000 == 0 {
-            println!("Not found after {} steps", iteration)
-        }
+    fn frame(&self, round: usize) -> Frame {
+        let (min_pos, max_pos) = self.grid.get_aabb().unwrap_or_default();
 
-        return has_no_proposed
+        Frame::from_fn(round, min_pos, max_pos, |pos| if self.grid.contains(pos) { Cell::Elf } else { Cell::Empty })
     }
 
-    fn do_x_steps(&mut self, amount: usize) {
+    fn do_x_steps(&mut self, amount: usize, observer: &mut dyn Observer) {
         for i in 0..amount {
+            observer.progress(i, Some(amount));
             self.step(i);
         }
     }
 
-    fn do_steps_until(&mut self) -> usize {
+    fn do_steps_until(&mut self, observer: &mut dyn Observer) -> usize {
         for i in 0.. {
+            observer.progress(i, None);
             if(self.step(i)){
                 return i + 1;
             }
@@ -163,39 +103,85 @@ impl World {
 
 
 
+/// The starting positions and then one frame per round, up to `rounds` or until nobody moves.
+fn diffusion_frames(mut world: World, rounds: Option<usize>) -> Frames {
+    let mut settled = false;
+
+    Box::new((0..).map_while(move |round| {
+        if settled || rounds.is_some_and(|rounds| round > rounds) {
+            return None;
+        }
+        if round > 0 {
+            settled = world.step(round - 1);
+        }
+
+        Some(world.frame(round))
+    }))
+}
+
+pub(crate) struct Day23;
+
+impl Solution for Day23 {
+    const DAY: usize = 23;
+
+    type Parsed = World;
+    type Answer1 = i32;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        World::parse_from(input)
+    }
+
+    fn part1(mut world: Self::Parsed, observer: &mut dyn Observer) -> Self::Answer1 {
+        world.do_x_steps(10, observer);
+        world.get_empties()
+    }
+
+    fn part2(mut world: Self::Parsed, observer: &mut dyn Observer) -> Self::Answer2 {
+        world.do_steps_until(observer)
+    }
+}
+
+impl Animation for Day23 {
+    fn frames(world: Self::Parsed, part: usize) -> Frames {
+        diffusion_frames(world, (part == 1).then_some(10))
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::observer::NoOp;
     use super::*;
 
     #[test]
     fn simple_test() {
-        let mut world = World::parse_from(include_str!("day23/test_simple.txt"));
+        let mut world = World::parse_from(include_str!("day23/test_simple.txt")).unwrap();
 
-        world.do_x_steps(10);
+        world.do_x_steps(10, &mut NoOp);
 
         assert_eq!(world.get_empties(), 110)
     }
 
     #[test]
     fn simple_large() {
-        let mut world = World::parse_from(include_str!("day23/test_large.txt"));
+        let mut world = World::parse_from(include_str!("day23/test_large.txt")).unwrap();
 
-        world.do_x_steps(10);
+        world.do_x_steps(10, &mut NoOp);
 
-        assert_eq!(world.get_empties(), 110)
+        assert_eq!(world.get_empties(), 3780)
     }
 
     #[test]
     fn simple_test_2() {
-        let mut world = World::parse_from(include_str!("day23/test_simple.txt"));
+        let mut world = World::parse_from(include_str!("day23/test_simple.txt")).unwrap();
 
-        assert_eq!(world.do_steps_until(), 20)
+        assert_eq!(world.do_steps_until(&mut NoOp), 20)
     }
 
     #[test]
     fn test_large_2() {
-        let mut world = World::parse_from(include_str!("day23/test_large.txt"));
+        let mut world = World::parse_from(include_str!("day23/test_large.txt")).unwrap();
 
-        assert_eq!(world.do_steps_until(), 20)
+        assert_eq!(world.do_steps_until(&mut NoOp), 930)
     }
 }
\ No newline at end of file
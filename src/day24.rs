@@ -1,16 +1,17 @@
-use std::cmp::Reverse;
-use std::collections::{HashMap, HashSet, VecDeque};
-use itertools::Position;
-use multimap::MultiMap;
-use priority_queue::PriorityQueue;
-use crate::direction::Direction;
-use crate::direction::Direction::{Down, Left, Right, Up};
+use std::collections::{HashMap, HashSet};
+use crate::direction::Dir4;
+use crate::search::{astar, dijkstra, SearchResult};
 use crate::vector2::Vector2i;
+use crate::animate::{Animation, Cell, Frame, Frames};
+use crate::error::{ParseError, SolveError};
+use crate::grid::{DenseGrid, Grid};
+use crate::observer::Observer;
+use crate::solution::Solution;
 
 #[derive(PartialEq, Clone, Copy)]
 struct Blizzard {
     pos: Vector2i,
-    dir: Direction,
+    dir: Dir4,
 }
 
 impl Blizzard {
@@ -25,7 +26,7 @@ impl Blizzard {
 }
 
 #[derive(PartialEq, Clone)]
-struct World {
+pub(crate) struct World {
     dimensions: Vector2i,
     start: Vector2i,
     end: Vector2i,
@@ -35,46 +36,52 @@ struct World {
 impl World {
 
 
-    fn parse_from(string: &str) -> World {
-        let mut blizzards = vec![];
-        for (y, line) in string.lines()
-            .skip(1)
-            .enumerate() {
-            for (x, char) in line.chars()
-                .skip(1)
-                .enumerate() {
-
-                let direction = match char {
-                    '^' => Some(Up),
-                    '>' => Some(Right),
-                    '<' => Some(Left),
-                    'v' => Some(Down),
-                    _ => None
-                };
-
-                if let Some(dir) = direction {
-                    blizzards.push(Blizzard {
-                        pos: Vector2i{x: x as i32,y: y as i32},
-                        dir
-                    })
-                }
-            }
+    fn parse_from(string: &str) -> Result<World, ParseError> {
+        let width = string.lines().next().map(|line| line.chars().count()).unwrap_or(0);
+        let height = string.lines().count();
+        if width < 3 || height < 3 {
+            return Err(ParseError::missing(Day24::DAY, string, "expected a walled valley of at least 3 by 3"));
         }
 
-        let dimensions = Vector2i{x: (string.lines().next().unwrap().chars().count() - 2) as i32, y: (string.lines().count() - 2) as i32 };
-        return World {
+        let grid: DenseGrid<Dir4> = Grid::try_parse(Day24::DAY, string, |char| match char {
+            '^' | '>' | '<' | 'v' => Ok(Dir4::from_char(char)),
+            '.' | '#' => Ok(None),
+            _ => Err("expected ., #, ^, >, < or v")
+        })?;
+
+        // Positions count from the top left square inside the walls.
+        let corner = Vector2i{x: 1, y: 1};
+        let blizzards = grid.iter()
+            .map(|(pos, &dir)| Blizzard { pos: pos - corner, dir })
+            .collect();
+
+        let dimensions = grid.size() - corner * 2;
+        return Ok(World {
             dimensions,
-            start: Vector2i {x: 1, y: -1},
+            start: Vector2i {x: 0, y: -1},
             end: Vector2i {x: dimensions.x - 1, y: dimensions.y},
             blizzards
-        }
+        })
     }
-}
 
-struct BlizzardIterator {
-    blizzards: Vec<Blizzard>,
-    next_index: usize,
-    world_dimensions: Vector2i,
+    fn frame(&self, minute: usize, expedition: Vector2i) -> Frame {
+        let mut blizzards: HashMap<Vector2i, (u32, Dir4)> = HashMap::new();
+        for blizzard in &self.blizzards {
+            blizzards.entry(blizzard.get_position_at(minute, self.dimensions)).or_insert((0, blizzard.dir)).0 += 1;
+        }
+
+        Frame::from_fn(minute, Vector2i{x: -1, y: -1}, self.dimensions, |pos| {
+            let in_valley = pos.x >= 0 && pos.x < self.dimensions.x && pos.y >= 0 && pos.y < self.dimensions.y;
+
+            match blizzards.get(&pos) {
+                _ if pos == expedition => Cell::Actor('E'),
+                Some((1, dir)) => Cell::Blizzard(dir.to_char()),
+                Some((count, _)) => Cell::Blizzard(char::from_digit((*count).min(9), 10).unwrap()),
+                None if in_valley || pos == self.start || pos == self.end => Cell::Empty,
+                None => Cell::Wall,
+            }
+        })
+    }
 }
 
 fn lcm(first: i32, second: i32) -> i32 {
@@ -101,93 +108,40 @@ fn gcd(first: i32, second: i32) -> i32 {
     }
 }
 
-impl Iterator for BlizzardIterator {
-    type Item = HashSet<Vector2i>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let positions = Some(self.blizzards
-            .iter()
-            .map(|blizzard| blizzard.get_position_at(self.next_index, self.world_dimensions))
-            .collect());
-
-        self.next_index += 1;
-
-        return positions;
-    }
+fn search_path(world: &World, blizzards: &[HashSet<Vector2i>]) -> Option<SearchResult<(usize, Vector2i), usize>> {
+    let is_open = |pos: Vector2i, time: usize| {
+        let in_valley = pos.x >= 0 && pos.x < world.dimensions.x && pos.y >= 0 && pos.y < world.dimensions.y;
+
+        pos == world.end || ((in_valley || pos == world.start) && !blizzards[time % blizzards.len()].contains(&pos))
+    };
+
+    astar(
+        [(0, world.start)],
+        |&(time, pos)| [Vector2i { x: 0, y: 0 }, Dir4::E.to_vector(), Dir4::W.to_vector(), Dir4::N.to_vector(), Dir4::S.to_vector()]
+            .into_iter()
+            .map(move |dir| pos + dir)
+            .filter(move |next| is_open(*next, time + 1))
+            .map(move |next| (((time + 1) % blizzards.len(), next), 1)),
+        |(_, pos)| pos.get_manhattan_distance(world.end) as usize,
+        |(_, pos)| *pos == world.end,
+    )
 }
 
-fn find_shortest_path(world: &World, blizzards: &Vec<HashSet<Vector2i>>) -> usize{
-    //let mut explored_paths
-    let mut paths_to_expore: PriorityQueue<Vec<Vector2i>, i32> = PriorityQueue::new();
-    let mut explored_paths: HashSet<(usize, Vector2i)> = HashSet::new();
-
-    paths_to_expore.push(vec![world.start], world.start.get_manhattan_distance(world.end));
-
-    let mut i: usize = 0;
-    while let Some((path, prior)) = paths_to_expore.pop() {
-        let current_blizzards = &blizzards[path.len() % blizzards.len()];
-
-        let mut new =
-            [
-                Vector2i { x: 0, y: 0 },
-                Right.to_vector(),
-                Left.to_vector(),
-                Up.to_vector(),
-                Down.to_vector(),
-            ]
-                .map(|dir| {
-                    let mut new_path = path.clone();
-                    new_path.push(*path.last().unwrap() + dir);
-
-                    return new_path;
-                })
-                .into_iter();
-
-        let result =  new.clone().find(|path| world.end == *path.last().unwrap());
-        if let Some(path) = result {
-            return path.len() - 1;
-        }
-
-        if i % 10000 == 0 {
-            //println!("Priority: {}, Length: {}", i32::MAX - prior, path.len());
-        }
-
-
-
-        let mut nexts: Vec<Vec<Vector2i>> = new
-            .filter(|path| {
-                let pos = path.last().unwrap();
-
-                let a = (pos.x >= 0 && pos.x < world.dimensions.x &&
-                    pos.y >= 0 && pos.y < world.dimensions.y);
-                let b = *pos == world.start;
-                let c = current_blizzards.contains(pos);
-                let d = explored_paths.contains(&(path.len(), *pos));
-
-                return ((pos.x >= 0 && pos.x < world.dimensions.x &&
-                    pos.y >= 0 && pos.y < world.dimensions.y) || *pos == world.start) &&
-                    !current_blizzards.contains(pos) &&
-                    !explored_paths.contains(&(path.len(), *pos));
-            }).collect();
-
-        for next in nexts {
-            let true_priority = (next.len() as i32 + next.last().unwrap().get_manhattan_distance(world.end));
-            let priority = i32::MAX - true_priority;
-            explored_paths.insert((next.len(), *next.last().unwrap()));
-            paths_to_expore.push(next, priority);
-        }
+fn find_shortest_path(world: &World, blizzards: &[HashSet<Vector2i>]) -> Option<usize> {
+    search_path(world, blizzards).map(|result| result.cost)
+}
 
-        i += 1;
-    }
+fn get_blizzard_cycle(world: &World) -> Vec<HashSet<Vector2i>> {
+    let max_cycle = lcm(world.dimensions.x, world.dimensions.y);
 
-    panic!()
+    (0..max_cycle).map(|i| world.blizzards.iter().map(|blizzard| blizzard.get_position_at(i as usize, world.dimensions)).collect()).collect()
 }
 
-fn find_shortest_back_forth_distance(world: &World) -> usize {
+/// Since waiting at the start is always safe, a crossing that cannot be made when leaving at one
+/// minute cannot be made when leaving later either.
+fn find_shortest_back_forth_distance(world: &World, observer: &mut dyn Observer) -> Option<usize> {
     let max_cycle = lcm(world.dimensions.x, world.dimensions.y);
-    let mut blizzards: Vec<HashSet<Vector2i>> = (0..max_cycle).map(|i| world.blizzards.iter().map(|blizzard| blizzard.get_position_at(i as usize, world.dimensions)).collect()).collect();
-
-    println!("Cycle of length {}", max_cycle);
+    let blizzards = get_blizzard_cycle(world);
 
     let mut backward_world = world.clone();
     backward_world.end = world.start;
@@ -195,99 +149,137 @@ fn find_shortest_back_forth_distance(world: &World) -> usize {
 
     let forward_world = world.clone();
 
-    let forward_paths: Vec<_> = (0..max_cycle).map(|i| {
+    let forward_paths: Vec<usize> = (0..max_cycle).map(|i| {
         let mut rotated = blizzards.clone();
         rotated.rotate_left(i as usize);
 
-        println!("Calculating forward path {}/{}", i, max_cycle);
-
+        observer.progress(i as usize, Some(2 * max_cycle as usize));
         return find_shortest_path(&forward_world, &rotated);
-    }).collect();
+    }).collect::<Option<_>>()?;
 
 
 
-    let backward_paths: Vec<_> = (0..max_cycle).map(|i| {
+    let backward_paths: Vec<usize> = (0..max_cycle).map(|i| {
         let mut rotated = blizzards.clone();
         rotated.rotate_left(i as usize);
 
-        println!("Calculating backward path {}/{}", i, max_cycle);
+        observer.progress((max_cycle + i) as usize, Some(2 * max_cycle as usize));
         return find_shortest_path(&backward_world, &rotated);
-    }).collect();
+    }).collect::<Option<_>>()?;
 
-    let mut paths_to_explore = PriorityQueue::<(usize, usize), Reverse<usize>>::new();
+    // States are (legs completed, time), each leg may wait at its starting point before leaving.
+    let result = dijkstra(
+        [(0, 0)],
+        |&(legs, time)| {
+            let leg_paths = if legs % 2 == 0 { &forward_paths } else { &backward_paths };
 
-    for (i, path) in forward_paths.iter().enumerate() {
-        paths_to_explore.push((0, *path + i), Reverse(*path + i));
+            (time..time + max_cycle as usize)
+                .map(move |departure| {
+                    let arrival = departure + leg_paths[departure % max_cycle as usize];
 
-        println!("Added {} ", *path)
-    }
+                    ((legs + 1, arrival), arrival - time)
+                })
+        },
+        |(legs, _)| *legs == 3,
+    );
 
-    while let Some(((i, _), path_len)) = paths_to_explore.pop() {
-        println!("Read {}, {} ", i, path_len.0);
+    result.map(|result| result.cost)
+}
 
-        if i == 2 {
-            let a = "e";
-            return path_len.0;
-        }
+fn no_way_through() -> SolveError {
+    SolveError::new(Day24::DAY, "the blizzards never let the expedition through the valley")
+}
 
-        if i == 1 && path_len.0 == 41 {
-            let a = 0;
-        }
+/// The expedition one minute at a time over `legs` crossings of the valley, each leaving as soon as
+/// the previous one arrived, which is never later than waiting since the ends are always safe. The
+/// route stops where it is when the next crossing cannot be made.
+fn route_frames(world: World, legs: usize) -> Frames {
+    let blizzards = get_blizzard_cycle(&world);
+    let mut route = vec![world.start];
+    let mut leg_world = world.clone();
 
-        for (extra, cycle) in (path_len.0..(path_len.0 + max_cycle as usize)).enumerate()  {
-            if i == 0 {
-                let val = path_len.0 + backward_paths[cycle % max_cycle as usize] + extra;
+    for _ in 0..legs {
+        let mut rotated = blizzards.clone();
+        rotated.rotate_left((route.len() - 1) % blizzards.len());
 
-                println!("{}, {}", i + 1, val);
+        let Some(leg) = search_path(&leg_world, &rotated) else { break };
+        route.extend(leg.path.into_iter().skip(1).map(|(_, pos)| pos));
+        (leg_world.start, leg_world.end) = (leg_world.end, leg_world.start);
+    }
 
-                paths_to_explore.push((i + 1, val), Reverse(path_len.0 + backward_paths[cycle % max_cycle as usize] + extra));
-            }
-            else {
-                let new_val = Reverse(path_len.0 + forward_paths[cycle % max_cycle as usize] + extra);
-                println!("{}, {}", i + 1, new_val.0);
+    Box::new(route.into_iter().enumerate().map(move |(minute, expedition)| world.frame(minute, expedition)))
+}
 
-                paths_to_explore.push((i + 1, path_len.0 + forward_paths[cycle % max_cycle as usize] + extra), new_val);
-            }
-        }
+pub(crate) struct Day24;
+
+impl Solution for Day24 {
+    const DAY: usize = 24;
+
+    type Parsed = World;
+    type Answer1 = Result<usize, SolveError>;
+    type Answer2 = Result<usize, SolveError>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        World::parse_from(input)
+    }
+
+    fn part1(world: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer1 {
+        find_shortest_path(&world, &get_blizzard_cycle(&world)).ok_or_else(no_way_through)
     }
 
-    panic!()
+    fn part2(world: Self::Parsed, observer: &mut dyn Observer) -> Self::Answer2 {
+        find_shortest_back_forth_distance(&world, observer).ok_or_else(no_way_through)
+    }
+}
 
+impl Animation for Day24 {
+    fn frames(world: Self::Parsed, part: usize) -> Frames {
+        route_frames(world, if part == 1 { 1 } else { 3 })
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::observer::NoOp;
+    use crate::runner::RunError;
+    use crate::solution::Solver;
     use super::*;
 
     #[test]
     fn simple_test() {
-        let mut world = World::parse_from(include_str!("day24/test_simple.txt"));
-        let max_cycle = lcm(world.dimensions.x, world.dimensions.y);
-        let mut blizzards: Vec<HashSet<Vector2i>> = (0..max_cycle).map(|i| world.blizzards.iter().map(|blizzard| blizzard.get_position_at(i as usize, world.dimensions)).collect()).collect();
+        let world = World::parse_from(include_str!("day24/test_simple.txt")).unwrap();
 
-        assert_eq!(find_shortest_path(&world, &blizzards), 18)
+        assert_eq!(find_shortest_path(&world, &get_blizzard_cycle(&world)), Some(18))
     }
 
     #[test]
     fn large_test() {
-        let mut world = World::parse_from(include_str!("day24/test_large.txt"));
-        let max_cycle = lcm(world.dimensions.x, world.dimensions.y);
-        let mut blizzards: Vec<HashSet<Vector2i>> = (0..max_cycle).map(|i| world.blizzards.iter().map(|blizzard| blizzard.get_position_at(i as usize, world.dimensions)).collect()).collect();
+        let world = World::parse_from(include_str!("day24/test_large.txt")).unwrap();
 
-        assert_eq!(find_shortest_path(&world, &blizzards), 264)
+        assert_eq!(find_shortest_path(&world, &get_blizzard_cycle(&world)), Some(264))
     }
 
     #[test]
     fn simple_test_2() {
-        let mut world = World::parse_from(include_str!("day24/test_simple.txt"));
+        let world = World::parse_from(include_str!("day24/test_simple.txt")).unwrap();
 
-        assert_eq!(find_shortest_back_forth_distance(&world), 54)
+        assert_eq!(find_shortest_back_forth_distance(&world, &mut NoOp), Some(54))
     }
 
     #[test]
     fn large_test_2() {
-        let mut world = World::parse_from(include_str!("day24/test_large.txt"));
+        let world = World::parse_from(include_str!("day24/test_large.txt")).unwrap();
+
+        assert_eq!(find_shortest_back_forth_distance(&world, &mut NoOp), Some(789))
+    }
+
+    #[test]
+    fn blocked_valley_is_an_error() {
+        // A blizzard in a valley one square wide never moves off that square.
+        let blocked = "#.#\n#>#\n#.#";
 
-        assert_eq!(find_shortest_back_forth_distance(&world), 789)
+        assert_eq!(find_shortest_back_forth_distance(&World::parse_from(blocked).unwrap(), &mut NoOp), None);
+        assert!(matches!(Day24.solve(1, blocked, &mut NoOp), Err(RunError::Solve(SolveError { day: 24, .. }))));
+        assert_eq!(route_frames(World::parse_from(blocked).unwrap(), 3).count(), 1);
     }
 }
\ No newline at end of file
@@ -1,7 +1,11 @@
 use std::ops::Add;
 use itertools::Itertools;
+use crate::error::ParseError;
+use crate::observer::Observer;
+use crate::parse;
+use crate::solution::Solution;
 
-struct Snafu {
+pub(crate) struct Snafu {
     numbers: Vec<i32>
 }
 
@@ -54,14 +58,14 @@ impl Snafu {
         }
     }
 
-    fn from_snafu_char(char: char) -> i32 {
+    fn from_snafu_char(char: char) -> Option<i32> {
         match char {
-            '=' => -2,
-            '-' => -1,
-            '0' => 0,
-            '1' => 1,
-            '2' => 2,
-            _ => panic!()
+            '=' => Some(-2),
+            '-' => Some(-1),
+            '0' => Some(0),
+            '1' => Some(1),
+            '2' => Some(2),
+            _ => None
         }
     }
 
@@ -69,15 +73,46 @@ impl Snafu {
         self.numbers.iter().map(|number| Snafu::to_snafu_char(*number)).rev().join("").trim_start_matches(|char| char == '0').to_string()
     }
 
-    fn parse_from(string: &str) -> Snafu {
-        Snafu{
-            numbers: string.chars().rev().map(|char| Snafu::from_snafu_char(char)).collect()
-        }
+    fn parse_from(string: &str) -> Result<Snafu, ParseError> {
+        let mut numbers = parse::chars(Day25::DAY, string, string, |char| Snafu::from_snafu_char(char).ok_or("expected =, -, 0, 1 or 2"))?;
+        numbers.reverse();
+
+        Ok(Snafu{
+            numbers
+        })
+    }
+}
+
+fn parse_snafus(string: &str) -> Result<Vec<Snafu>, ParseError> {
+    if string.trim().is_empty() {
+        return Err(ParseError::missing(Day25::DAY, string, "expected at least one SNAFU number"));
     }
+
+    string.lines().map(|line| Snafu::parse_from(line).map_err(|error| error.relative_to(string, line))).collect()
+}
+
+fn get_sum_of_snafus(snafus: Vec<Snafu>) -> Snafu {
+    snafus.into_iter().reduce(|a, b| a + b).unwrap()
 }
 
-fn get_sum_of_snafus(string: &str) -> Snafu {
-    string.lines().map(Snafu::parse_from).reduce(|a, b| a + b).unwrap()
+pub(crate) struct Day25;
+
+impl Solution for Day25 {
+    const DAY: usize = 25;
+
+    type Parsed = Vec<Snafu>;
+    type Answer1 = String;
+    type Answer2 = ();
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_snafus(input)
+    }
+
+    fn part1(snafus: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer1 {
+        get_sum_of_snafus(snafus).to_snafu_string()
+    }
+
+    fn part2(_: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer2 {}
 }
 
 #[cfg(test)]
@@ -86,14 +121,14 @@ mod tests {
 
     #[test]
     fn simple_test() {
-        let mut sum = get_sum_of_snafus(include_str!("day25/test_simple.txt"));
+        let mut sum = get_sum_of_snafus(parse_snafus(include_str!("day25/test_simple.txt")).unwrap());
 
         assert_eq!(sum.to_snafu_string(), "2=-1=0")
     }
 
     #[test]
     fn large_test() {
-        let mut sum = get_sum_of_snafus(include_str!("day25/test_large.txt"));
+        let mut sum = get_sum_of_snafus(parse_snafus(include_str!("day25/test_large.txt")).unwrap());
 
         assert_eq!(sum.to_snafu_string(), "2=-0=1-0012-=-2=0=01")
     }
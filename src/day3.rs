@@ -1,9 +1,23 @@
 use std::collections::HashSet;
 use std::str::Lines;
+use crate::error::ParseError;
+use crate::observer::Observer;
+use crate::parse;
+use crate::solution::Solution;
 
-fn get_all_priorities(rucksack_string: String) -> i32 {
-    return rucksack_string
+fn parse_rucksacks(rucksack_string: &str) -> Result<Vec<String>, ParseError> {
+    rucksack_string
         .lines()
+        .map(|line| parse::chars(Day3::DAY, rucksack_string, line, |char| match char.is_ascii_alphabetic() {
+            true => Ok(char),
+            false => Err("expected an item letter"),
+        }).map(String::from_iter))
+        .collect()
+}
+
+fn get_all_priorities(rucksacks: Vec<String>) -> i32 {
+    return rucksacks
+        .iter()
         .map(|rucksack_line| get_rucksack_priority(rucksack_line))
         .sum();
 }
@@ -27,9 +41,10 @@ fn get_rucksack_priority_2(rucksack_string: &[&str]) -> Option<i32>{
     return char_to_priority(intersection.iter().next()?.clone())
 }
 
-fn get_rucksack_group_priority(rucksacks_string: String) -> Option<i32> {
-    return rucksacks_string
-        .lines()
+fn get_rucksack_group_priority(rucksacks: Vec<String>) -> Option<i32> {
+    return rucksacks
+        .iter()
+        .map(|rucksack_line| rucksack_line.as_str())
         .collect::<Vec<_>>()
         .chunks(3)
         .map(|rucksack| get_rucksack_priority_2(rucksack)).sum();
@@ -55,6 +70,29 @@ fn lowercase_char_to_priority(char: char) -> Option<i32> {
     let mut alphabet = ('a'..='z');
     return Some(alphabet.position(|letter| letter == char)? as i32 + 1)
 }
+pub(crate) struct Day3;
+
+impl Solution for Day3 {
+    const DAY: usize = 3;
+    const VERSION: u32 = 2;
+
+    type Parsed = Vec<String>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_rucksacks(input)
+    }
+
+    fn part1(rucksacks: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer1 {
+        get_all_priorities(rucksacks)
+    }
+
+    fn part2(rucksacks: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer2 {
+        get_rucksack_group_priority(rucksacks).unwrap_or(0)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{env, fs};
@@ -62,21 +100,21 @@ mod tests {
 
     #[test]
     fn simple_test_1() {
-        assert_eq!(get_all_priorities(fs::read_to_string("src/day3/test_1_small.txt").unwrap()), 157);
+        assert_eq!(get_all_priorities(parse_rucksacks(&fs::read_to_string("src/day3/test_simple.txt").unwrap()).unwrap()), 157);
     }
 
     #[test]
     fn large_test_1() {
-        assert_eq!(get_all_priorities(fs::read_to_string("src/day3/test_1_large.txt").unwrap()), 7908);
+        assert_eq!(get_all_priorities(parse_rucksacks(&fs::read_to_string("src/day3/test_large.txt").unwrap()).unwrap()), 7908);
     }
 
     #[test]
     fn simple_test_2() {
-        assert_eq!(get_rucksack_group_priority(fs::read_to_string("src/day3/test_1_small.txt").unwrap()), Some(70));
+        assert_eq!(get_rucksack_group_priority(parse_rucksacks(&fs::read_to_string("src/day3/test_simple.txt").unwrap()).unwrap()), Some(70));
     }
 
     #[test]
     fn large_test_2() {
-        assert_eq!(get_rucksack_group_priority(fs::read_to_string("src/day3/test_1_large.txt").unwrap()), Some(2838));
+        assert_eq!(get_rucksack_group_priority(parse_rucksacks(&fs::read_to_string("src/day3/test_large.txt").unwrap()).unwrap()), Some(2838));
     }
 }
\ No newline at end of file
@@ -2,21 +2,38 @@ use std::collections::HashSet;
 use std::ops::{Range, RangeInclusive};
 use std::str::Lines;
 use itertools::Itertools;
+use crate::error::{parse_number, ParseError};
+use crate::observer::Observer;
+use crate::parse;
+use crate::solution::Solution;
 
-fn count_overlapping_assignments(assignments_string: String, overlap_function: fn(&RangeInclusive<i32>, range2: &RangeInclusive<i32>) -> bool) -> usize {
-    return assignments_string
+type Assignment = Vec<RangeInclusive<i32>>;
+
+fn parse_assignments(assignments_string: &str) -> Result<Vec<Assignment>, ParseError> {
+    assignments_string
         .lines()
-        .map(|schedule_line| do_schedules_overlap(schedule_line, overlap_function))
+        .map(|schedule_line| {
+            let [start1, end1, start2, end2] = parse::fields(Day4::DAY, assignments_string, schedule_line, "{}-{},{}-{}")?
+                .map(|number| parse_number(Day4::DAY, assignments_string, number));
+
+            Ok(vec![start1?..=end1?, start2?..=end2?])
+        })
+        .collect()
+}
+
+fn count_overlapping_assignments(assignments: Vec<Assignment>, overlap_function: fn(&RangeInclusive<i32>, range2: &RangeInclusive<i32>) -> bool) -> usize {
+    return assignments
+        .iter()
+        .map(|schedules| do_schedules_overlap(schedules, overlap_function))
         .filter(|result| *result)
         .count()
 }
 
-fn do_schedules_overlap(schedule_line: &str, overlap_function: fn(&RangeInclusive<i32>, &RangeInclusive<i32>) -> bool) -> bool {
-    return schedule_line
-        .split(",")
-        .map(|schedule_string| schedule_to_range(schedule_string))
+fn do_schedules_overlap(schedules: &Assignment, overlap_function: fn(&RangeInclusive<i32>, &RangeInclusive<i32>) -> bool) -> bool {
+    return schedules
+        .iter()
         .combinations(2)
-        .any(|combination| overlap_function(&combination[0], &combination[1]) || overlap_function(&combination[1], &combination[0]));
+        .any(|combination| overlap_function(combination[0], combination[1]) || overlap_function(combination[1], combination[0]));
 }
 
 fn is_subrange(range1: &RangeInclusive<i32>, range2: &RangeInclusive<i32>) -> bool {
@@ -27,10 +44,26 @@ fn is_overlapping_at_all(range1: &RangeInclusive<i32>, range2: &RangeInclusive<i
     return range2.contains(range1.start()) || range2.contains(range1.end());
 }
 
-fn schedule_to_range(schedule: &str) -> RangeInclusive<i32> {
-    let mut values = schedule.split("-").map(|value| value.parse::<i32>().unwrap());
+pub(crate) struct Day4;
+
+impl Solution for Day4 {
+    const DAY: usize = 4;
+
+    type Parsed = Vec<Assignment>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    return values.next().unwrap()..=values.next().unwrap()
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_assignments(input)
+    }
+
+    fn part1(assignments: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer1 {
+        count_overlapping_assignments(assignments, is_subrange)
+    }
+
+    fn part2(assignments: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer2 {
+        count_overlapping_assignments(assignments, is_overlapping_at_all)
+    }
 }
 
 #[cfg(test)]
@@ -40,21 +73,21 @@ mod tests {
 
     #[test]
     fn simple_test() {
-        assert_eq!(count_overlapping_assignments(fs::read_to_string("src/day4/test_simple.txt").unwrap(), is_subrange), 2);
+        assert_eq!(count_overlapping_assignments(parse_assignments(&fs::read_to_string("src/day4/test_simple.txt").unwrap()).unwrap(), is_subrange), 2);
     }
 
     #[test]
     fn large_test() {
-        assert_eq!(count_overlapping_assignments(fs::read_to_string("src/day4/test_large.txt").unwrap(), is_subrange), 462);
+        assert_eq!(count_overlapping_assignments(parse_assignments(&fs::read_to_string("src/day4/test_large.txt").unwrap()).unwrap(), is_subrange), 462);
     }
 
     #[test]
     fn simple_test_2() {
-        assert_eq!(count_overlapping_assignments(fs::read_to_string("src/day4/test_simple.txt").unwrap(), is_overlapping_at_all), 4);
+        assert_eq!(count_overlapping_assignments(parse_assignments(&fs::read_to_string("src/day4/test_simple.txt").unwrap()).unwrap(), is_overlapping_at_all), 4);
     }
 
     #[test]
     fn large_test_2() {
-        assert_eq!(count_overlapping_assignments(fs::read_to_string("src/day4/test_large.txt").unwrap(), is_overlapping_at_all), 835);
+        assert_eq!(count_overlapping_assignments(parse_assignments(&fs::read_to_string("src/day4/test_large.txt").unwrap()).unwrap(), is_overlapping_at_all), 835);
     }
 }
\ No newline at end of file
@@ -1,29 +1,58 @@
 use std::iter::{Skip, StepBy};
 use std::str::Chars;
 use itertools::chain;
-use regex;
+use crate::crane::{self, Crane, CrateMover9000, CrateMover9001, Move, Simulator, Stacks};
+use crate::error::{parse_number, ParseError};
+use crate::observer::Observer;
+use crate::parse;
+use crate::solution::Solution;
 
-fn get_tops(text: String, command_executor: fn(stacks: Vec<Vec<char>>, commands: Vec<(usize, usize, usize)>) -> String) -> String {
-    let mut text_parts = text.split("\n\n");
-    let stacks = text_parts.next().unwrap();
-    let moves = text_parts.next().unwrap();
+type Supplies = (Stacks, Vec<Move>);
 
-    let stacks = parse_stacks(stacks);
-    let commands = parse_commands(moves);
-    return command_executor(stacks, commands);
+fn parse_supplies(text: &str) -> Result<Supplies, ParseError> {
+    let (stacks_string, moves) = parse::split_blocks(Day5::DAY, text, text, "expected a blank line between the stacks and the moves")?;
+
+    let stacks = parse_stacks(stacks_string)?;
+    let commands = parse_commands(text, moves, stacks.len())?;
+
+    // Every crane leaves as many crates on each stack, so the moves are checked once for both parts.
+    if let Err(error) = crane::check(&stacks, &commands) {
+        return Err(ParseError::new(Day5::DAY, text, moves.lines().nth(error.step() - 1).unwrap_or(moves), error.to_string()));
+    }
+
+    return Ok((stacks, commands));
 }
 
-fn parse_commands(commands_string: &str) -> Vec<(usize, usize, usize)> {
-    let split_regex = regex::Regex::new(r"move|from|to").unwrap();
+fn get_tops((stacks, commands): Supplies, crane: impl Crane) -> String {
+    let mut simulator = Simulator::new(crane, stacks);
+    simulator.run(commands).expect("moves are checked while parsing");
 
+    simulator.tops()
+}
+
+fn parse_commands(text: &str, commands_string: &str, stack_count: usize) -> Result<Vec<(usize, usize, usize)>, ParseError> {
     return commands_string
         .lines()
-        .map(|line| split_regex.split(line).map(|str| str.trim()).skip(1))
-        .map(|mut split| (split.next().unwrap().parse::<usize>().unwrap(), split.next().unwrap().parse::<usize>().unwrap(), split.next().unwrap().parse::<usize>().unwrap()))
+        .map(|line| parse_command(text, line, stack_count))
         .collect();
 }
 
-fn parse_stacks(stacks_string: &str) -> Vec<Vec<char>> {
+fn parse_command(text: &str, line: &str, stack_count: usize) -> Result<(usize, usize, usize), ParseError> {
+    let tokens: [&str; 3] = parse::fields(Day5::DAY, text, line, "move {} from {} to {}")?;
+    let mut values = [0; 3];
+
+    for (i, token) in tokens.into_iter().enumerate() {
+        values[i] = parse_number(Day5::DAY, text, token)?;
+
+        if i > 0 && (values[i] == 0 || values[i] > stack_count) {
+            return Err(ParseError::new(Day5::DAY, text, token, format!("expected a stack between 1 and {}", stack_count)));
+        }
+    }
+
+    return Ok((values[0], values[1], values[2]));
+}
+
+fn parse_stacks(stacks_string: &str) -> Result<Vec<Vec<char>>, ParseError> {
     let mut stacks: Vec<Vec<char>> = Vec::new();
 
    for stack_layer in stacks_string.lines().rev().skip(1) {
@@ -32,37 +61,41 @@ fn parse_stacks(stacks_string: &str) -> Vec<Vec<char>> {
                stacks.push(Vec::new());
            }
 
-           if !el.is_whitespace(){
+           if el.is_ascii_alphabetic() {
                stacks[i].push(el);
            }
+           else if !el.is_whitespace() {
+               let index = stack_layer.char_indices().nth(1 + 4 * i).unwrap().0;
+               return Err(ParseError::new(Day5::DAY, stacks_string, &stack_layer[index..index + el.len_utf8()], "expected a crate letter"));
+           }
        }
    }
 
-    return stacks;
+    return Ok(stacks);
 }
 
-fn execute_commands_one_at_time(mut stacks: Vec<Vec<char>>, commands: Vec<(usize, usize, usize)>) -> String{
-    for command in commands {
-        for _ in 0..command.0{
-            let element = stacks[command.1 - 1].pop().unwrap();
-            stacks[command.2 - 1].push(element);
-        }
-    }
+pub(crate) struct Day5;
 
-    return stacks.iter().map(|stack| stack.last().unwrap().clone()).collect()
-}
+impl Solution for Day5 {
+    const DAY: usize = 5;
+    const VERSION: u32 = 2;
 
-fn execute_commands_all_at_once(mut stacks: Vec<Vec<char>>, commands: Vec<(usize, usize, usize)>) -> String{
-    for command in commands {
-        let final_length = stacks[command.1 - 1].len().saturating_sub(command.0);
-        let tail = stacks[command.1 - 1].split_off(final_length);
-        stacks[command.2 - 1].extend(tail);
+    type Parsed = Supplies;
+    type Answer1 = String;
+    type Answer2 = String;
 
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_supplies(input)
     }
 
-    return stacks.iter().map(|stack| stack.last().unwrap().clone()).collect()
-}
+    fn part1(supplies: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer1 {
+        get_tops(supplies, CrateMover9000)
+    }
 
+    fn part2(supplies: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer2 {
+        get_tops(supplies, CrateMover9001)
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -71,21 +104,30 @@ mod tests {
 
     #[test]
     fn simple_test() {
-        assert_eq!(get_tops(fs::read_to_string("src/day5/test_simple.txt").unwrap(), execute_commands_one_at_time), "CMZ");
+        assert_eq!(get_tops(parse_supplies(&fs::read_to_string("src/day5/test_simple.txt").unwrap()).unwrap(), CrateMover9000), "CMZ");
     }
 
     #[test]
     fn large_test() {
-        assert_eq!(get_tops(fs::read_to_string("src/day5/test_large.txt").unwrap(), execute_commands_one_at_time), "FRDSQRRCD");
+        assert_eq!(get_tops(parse_supplies(&fs::read_to_string("src/day5/test_large.txt").unwrap()).unwrap(), CrateMover9000), "FRDSQRRCD");
     }
 
     #[test]
     fn simple_test_2() {
-        assert_eq!(get_tops(fs::read_to_string("src/day5/test_simple.txt").unwrap(), execute_commands_all_at_once), "MCD");
+        assert_eq!(get_tops(parse_supplies(&fs::read_to_string("src/day5/test_simple.txt").unwrap()).unwrap(), CrateMover9001), "MCD");
     }
 
     #[test]
     fn large_test_2() {
-        assert_eq!(get_tops(fs::read_to_string("src/day5/test_large.txt").unwrap(), execute_commands_all_at_once), "HRFTQVWNN");
+        assert_eq!(get_tops(parse_supplies(&fs::read_to_string("src/day5/test_large.txt").unwrap()).unwrap(), CrateMover9001), "HRFTQVWNN");
+    }
+
+    #[test]
+    fn batched_crane_and_under_filled_stacks() {
+        let supplies = parse_supplies(&fs::read_to_string("src/day5/test_simple.txt").unwrap()).unwrap();
+        assert_eq!(get_tops(supplies, crane::Batched(std::num::NonZeroUsize::new(2).unwrap())), "MCZ");
+
+        let error = parse_supplies("[A] [B]\n 1   2 \n\nmove 1 from 1 to 2\nmove 3 from 2 to 1").unwrap_err();
+        assert!(error.to_string().contains("line 5, column 1: move 2 takes 3 crates from stack 2, which only holds 2"), "{}", error);
     }
-}
\ No newline at end of file
+}
@@ -1,32 +1,73 @@
 use std::collections::HashSet;
+use crate::error::{ParseError, SolveError};
+use crate::observer::Observer;
+use crate::solution::Solution;
 
-fn get_indicator_index(string: &str, window_size: usize) -> usize {
-   return string
-       .chars()
-       .collect::<Vec<_>>()
-       .windows(window_size)
-       .position(|window| HashSet::<&char>::from_iter(window.into_iter()).len() == window_size)
-       .unwrap() + window_size;
+fn get_indicator_index(string: &str, window_size: usize) -> Option<usize> {
+    string
+        .chars()
+        .collect::<Vec<_>>()
+        .windows(window_size)
+        .position(|window| HashSet::<&char>::from_iter(window).len() == window_size)
+        .map(|index| index + window_size)
+}
+
+fn find_marker(datastream: &str, window_size: usize, marker: &str) -> Result<usize, SolveError> {
+    get_indicator_index(datastream, window_size)
+        .ok_or_else(|| SolveError::new(Day6::DAY, format!("there is no {} marker of {} different characters", marker, window_size)))
+}
+
+pub(crate) struct Day6;
+
+impl Solution for Day6 {
+    const DAY: usize = 6;
+    const VERSION: u32 = 2;
+
+    type Parsed = String;
+    type Answer1 = Result<usize, SolveError>;
+    type Answer2 = Result<usize, SolveError>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Ok(input.trim().to_string())
+    }
+
+    fn part1(datastream: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer1 {
+        find_marker(&datastream, 4, "start-of-packet")
+    }
+
+    fn part2(datastream: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer2 {
+        find_marker(&datastream, 14, "start-of-message")
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::{fs};
+    use crate::observer::NoOp;
     use super::*;
 
     #[test]
     fn simple_test() {
-        assert_eq!(get_indicator_index("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4), 7);
-        assert_eq!(get_indicator_index("bvwbjplbgvbhsrlpgdmjqwftvncz", 4), 5);
-        assert_eq!(get_indicator_index("nppdvjthqldpwncqszvftbrmjlhg", 4), 6);
-        assert_eq!(get_indicator_index("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 4), 10);
-        assert_eq!(get_indicator_index("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 4), 11);
-        assert_eq!(get_indicator_index("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 14), 26);
+        assert_eq!(get_indicator_index("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4), Some(7));
+        assert_eq!(get_indicator_index("bvwbjplbgvbhsrlpgdmjqwftvncz", 4), Some(5));
+        assert_eq!(get_indicator_index("nppdvjthqldpwncqszvftbrmjlhg", 4), Some(6));
+        assert_eq!(get_indicator_index("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 4), Some(10));
+        assert_eq!(get_indicator_index("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 4), Some(11));
+        assert_eq!(get_indicator_index("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 14), Some(26));
     }
 
     #[test]
     fn large_test() {
-       assert_eq!(get_indicator_index(fs::read_to_string("src/day6/test_large.txt").unwrap().as_mut_str(), 4), 1757);
-       assert_eq!(get_indicator_index(fs::read_to_string("src/day6/test_large.txt").unwrap().as_mut_str(), 14), 2950);
+       assert_eq!(get_indicator_index(fs::read_to_string("src/day6/test_large.txt").unwrap().as_mut_str(), 4), Some(1757));
+       assert_eq!(get_indicator_index(fs::read_to_string("src/day6/test_large.txt").unwrap().as_mut_str(), 14), Some(2950));
+    }
+
+    #[test]
+    fn streams_without_a_marker_have_no_answer() {
+        let datastream = Day6::parse("abcdabcabc\n").unwrap();
+
+        assert_eq!(Day6::part1(datastream.clone(), &mut NoOp), Ok(4));
+        assert_eq!(Day6::part2(datastream, &mut NoOp).unwrap_err().message, "there is no start-of-message marker of 14 different characters");
+        assert!(Day6::part1(Day6::parse("").unwrap(), &mut NoOp).is_err());
     }
-}
\ No newline at end of file
+}
@@ -1,136 +1,48 @@
-use std::collections::HashSet;
-use std::rc::{Rc, Weak};
-use regex::{Captures, Match, Regex};
+use crate::error::{ParseError, SolveError};
+use crate::observer::Observer;
+use crate::solution::Solution;
+use crate::vfs::FileSystem;
 
-use std::cell::{Ref, RefCell, RefMut};
-use std::ops::Deref;
-
-struct File {
-    name: String,
-    size: usize,
+fn get_sizes_under(fs: &FileSystem, max_size: usize) -> usize {
+    fs.directories(FileSystem::ROOT).map(|id| fs.size(id)).filter(|size| *size <= max_size).sum()
 }
 
-struct Directory {
-    name: String,
-    files: Vec<File>,
-    directories: Vec<Rc<RefCell<Directory>>>,
-    parent: Option<Rc<RefCell<Directory>>>,
-}
+fn get_smallest_valid_dir(fs: &FileSystem, file_system_size: usize, min_size: usize) -> Result<usize, SolveError> {
+    let free_space = file_system_size.checked_sub(fs.size(FileSystem::ROOT))
+        .ok_or_else(|| SolveError::new(Day7::DAY, format!("the files take up more than the {} the disk holds", file_system_size)))?;
 
-impl Directory {
-    fn get_size(&self) -> usize {
-        self.files.iter().map(|file| file.size).sum::<usize>() + self.directories.iter().map(|dir| dir.borrow().get_size()).sum::<usize>()
-    }
+    fs.directories(FileSystem::ROOT)
+        .map(|id| fs.size(id))
+        .filter(|size| *size >= min_size.saturating_sub(free_space))
+        .min()
+        .ok_or_else(|| SolveError::new(Day7::DAY, format!("no directory frees up {} on the disk", min_size)))
+}
 
-    fn get_sizes_under(&self, max_size: usize) -> usize {
-        let own_size = if self.get_size() <= max_size { self.get_size() } else { 0 };
+fn parse_file_tree(transcript: &str) -> Result<FileSystem, ParseError> {
+    FileSystem::replay(transcript)
+}
 
-        return self.directories.iter().map(|dir| dir.borrow().get_sizes_under(max_size)).sum::<usize>() + own_size;
-    }
+pub(crate) struct Day7;
 
-    fn flatten(directory: &Rc<RefCell<Directory>>) -> Vec<Rc<RefCell<Directory>>> {
-        let mut children: Vec<Rc<RefCell<Directory>>> = directory
-            .borrow()
-            .directories
-            .iter()
-            .map(|dir| Directory::flatten(dir))
-            .flatten()
-            .collect();
+impl Solution for Day7 {
+    const DAY: usize = 7;
+    const VERSION: u32 = 2;
 
-        children.push(Rc::clone(directory));
+    type Parsed = FileSystem;
+    type Answer1 = usize;
+    type Answer2 = Result<usize, SolveError>;
 
-        return children;
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_file_tree(input)
     }
 
-    fn get_smallest_valid_dir(directory: &Rc<RefCell<Directory>>, file_system_size: usize, min_size: usize) -> usize {
-        let free_space= file_system_size - directory.borrow().get_size();
-
-        Directory::flatten(directory)
-            .iter()
-            .map(|dir| dir.borrow().get_size())
-            .filter(|size| *size >= min_size - free_space)
-            .min()
-            .unwrap()
+    fn part1(fs: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer1 {
+        get_sizes_under(&fs, 100000)
     }
 
-}
-
-fn parse_file_tree(str: &str) -> Rc<RefCell<Directory>> {
-    let root_dir = Rc::new(RefCell::new(Directory {
-        name: "root".parse().unwrap(),
-        files: vec![],
-        directories: vec![],
-        parent: None
-    }));
-
-    let mut current_dir: Rc<RefCell<Directory>> = Rc::clone(&root_dir);
-
-    for directory_string in str.split("$ "){
-        let cd_regex = Regex::new(r"cd (?P<name>.+)\n").unwrap();
-        let ls_regex= Regex::new(r"(?s)ls\n(?P<dirlist>.*)").unwrap();
-
-        match cd_regex.captures(directory_string) {
-            Some(capture) => match capture.name("name").map(|name| name.as_str()) {
-                None => {}
-                Some("..") => {
-                    current_dir = get_parent_dir(current_dir);
-                }
-                Some("/") => {
-                    current_dir = Rc::clone(&root_dir);
-                }
-                Some(name) => {
-                    current_dir = get_child_dir(current_dir, name);
-                }
-            }
-            None => {
-                match ls_regex.captures(directory_string) {
-                    Some(capture) => {
-                        parse_dir_content( Rc::clone(&current_dir), capture.name("dirlist").unwrap().as_str());
-                    }
-                    None => {}
-                }
-            }
-        }
+    fn part2(fs: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer2 {
+        get_smallest_valid_dir(&fs, 70000000, 30000000)
     }
-
-    return Rc::clone(&root_dir);
-}
-
-fn get_parent_dir(current_dir: Rc<RefCell<Directory>>) -> Rc<RefCell<Directory>> {
-    Rc::clone(&current_dir.borrow().parent.as_ref().unwrap())
-}
-
-fn get_child_dir(current_dir: Rc<RefCell<Directory>>, name: &str) -> Rc<RefCell<Directory>> {
-    let a: RefMut<Directory> = current_dir.borrow_mut();
-    Rc::clone(a.directories.iter().find(|dir| dir.borrow_mut().name == name).unwrap())
-}
-
-fn parse_dir_content(directory: Rc<RefCell<Directory>>, str: &str) {
-    let regex_dir = Regex::new(r"dir (?P<dir>.*)\n").unwrap();
-
-    let directories: Vec<_> = regex_dir
-        .captures_iter(str)
-        .map(|mat| mat.name("dir").unwrap().as_str())
-        .map(|name| Rc::new(RefCell::new(Directory{
-            name: name.parse().unwrap(),
-            parent: Some(Rc::clone(&directory)),
-            files: vec![],
-            directories: vec![],
-        })))
-        .collect();
-
-    let regex_files = Regex::new(r"(?P<size>\d+) (?P<filename>.*)\n").unwrap();
-
-    let files = regex_files
-        .captures_iter(str)
-        .map(|mat| File {
-                name: mat.name("filename").unwrap().as_str().parse().unwrap(),
-                size: mat.name("size").unwrap().as_str().parse::<usize>().unwrap()
-        });
-
-    let mut dir = directory.borrow_mut();
-    dir.files.extend(files);
-    dir.directories.extend(directories);
 }
 
 #[cfg(test)]
@@ -140,21 +52,51 @@ mod tests {
 
     #[test]
     fn simple_test() {
-        assert_eq!(parse_file_tree(fs::read_to_string("src/day7/test_simple.txt").unwrap().as_mut_str()).borrow().get_sizes_under(100000), 95437);
+        assert_eq!(get_sizes_under(&parse_file_tree(fs::read_to_string("src/day7/test_simple.txt").unwrap().as_mut_str()).unwrap(), 100000), 95437);
     }
 
     #[test]
     fn large_test() {
-        assert_eq!(parse_file_tree(fs::read_to_string("src/day7/test_large.txt").unwrap().as_mut_str()).borrow().get_sizes_under(100000), 1391690);
+        assert_eq!(get_sizes_under(&parse_file_tree(fs::read_to_string("src/day7/test_large.txt").unwrap().as_mut_str()).unwrap(), 100000), 1391690);
     }
 
     #[test]
     fn simple_test_2() {
-        assert_eq!(Directory::get_smallest_valid_dir(&parse_file_tree(fs::read_to_string("src/day7/test_simple.txt").unwrap().as_mut_str()), 70000000, 30000000), 24933642);
+        assert_eq!(get_smallest_valid_dir(&parse_file_tree(fs::read_to_string("src/day7/test_simple.txt").unwrap().as_mut_str()).unwrap(), 70000000, 30000000), Ok(24933642));
     }
 
     #[test]
     fn large_test_2() {
-        assert_eq!(Directory::get_smallest_valid_dir(&parse_file_tree(fs::read_to_string("src/day7/test_large.txt").unwrap().as_mut_str()), 70000000, 30000000), 5469168);
+        assert_eq!(get_smallest_valid_dir(&parse_file_tree(fs::read_to_string("src/day7/test_large.txt").unwrap().as_mut_str()).unwrap(), 70000000, 30000000), Ok(5469168));
+    }
+
+    #[test]
+    fn full_disk_is_an_error() {
+        let fs = parse_file_tree("$ cd /\n$ ls\n80 a.txt").unwrap();
+
+        assert!(get_smallest_valid_dir(&fs, 70, 30).is_err());
+        assert!(get_smallest_valid_dir(&fs, 100, 130).is_err());
+        assert_eq!(get_smallest_valid_dir(&fs, 100, 30), Ok(80));
+    }
+
+    #[test]
+    fn unknown_directory_is_error() {
+        let error = parse_file_tree("$ cd /\n$ ls\ndir a\n14848514 b.txt\n$ cd c").err().unwrap();
+
+        assert_eq!((error.line, error.column, error.text.as_str()), (5, 6, "c"));
+    }
+
+    #[test]
+    fn replays_paths_and_repeated_listings() {
+        let transcript = "$ cd /\n$ ls\ndir a\n10 b\n$ cd a\n$ ls\ndir c\n$ cd /a/c\n$ ls\n5 d\n$ cd ../..\n$ ls\ndir a\n10 b\n$ cd a/c";
+        let fs = parse_file_tree(transcript).unwrap();
+
+        assert_eq!(fs.node_count(), 5);
+        assert_eq!(fs.size(FileSystem::ROOT), 15);
+        assert_eq!(fs.size(fs.resolve(FileSystem::ROOT, "/a").unwrap()), 5);
+
+        let error = parse_file_tree("$ cd /\n$ ls\n10 b\n$ cd b").unwrap_err();
+        assert_eq!((error.line, error.message.as_str()), (4, "not a directory: /b"));
+        assert_eq!(parse_file_tree("$ cd /\ndir a").unwrap_err().message, "expected a command");
     }
 }
\ No newline at end of file
@@ -5,91 +5,112 @@ use regex::{Captures, Match, Regex};
 use std::cell::{Ref, RefCell, RefMut};
 use std::ops::{Deref, Range};
 use std::cmp;
+use crate::error::ParseError;
+use crate::grid::{DenseGrid, Grid};
+use crate::observer::Observer;
+use crate::solution::Solution;
+use crate::vector2::Vector2i;
 
-struct Tree {
+pub(crate) struct Tree {
     visible: bool,
     height: u32,
     score: usize,
 }
 
-type TreeGrid = Vec<Vec<Tree>>;
+type TreeGrid = DenseGrid<Tree>;
 
-fn parse_grid(str: &str) -> TreeGrid {
-    str.lines().map(|line| line.chars().map(|char| Tree {
-        height: char.to_digit(10).unwrap(),
-        visible: false,
-        score: 1,
-    }).collect()).collect()
+fn parse_grid(str: &str) -> Result<TreeGrid, ParseError> {
+    let width = str.lines().next().map(|line| line.len()).unwrap_or(0);
+    if width == 0 {
+        return Err(ParseError::missing(Day8::DAY, str, "expected a row of trees"));
+    }
+
+    if let Some(line) = str.lines().find(|line| line.len() != width) {
+        return Err(ParseError::new(Day8::DAY, str, line, format!("expected {} trees in every row", width)));
+    }
+
+    Grid::try_parse(Day8::DAY, str, |char| match char.to_digit(10) {
+        Some(height) => Ok(Some(Tree { height, visible: false, score: 1 })),
+        None => Err("expected a tree height"),
+    })
 }
 
-fn process_row_1(tree_grid: &mut TreeGrid, row_indices: Vec<usize>, col_indices: Vec<usize>) {
+fn process_row_1(tree_grid: &mut TreeGrid, positions: Vec<Vector2i>) {
     let mut previous_heights: Vec<u32> = Vec::new();
-    for row in &row_indices {
-        for col in &col_indices {
-            let mut tree: &mut Tree = &mut tree_grid[*row][*col];
-
-            if previous_heights.iter().all(|prev_height| tree.height > *prev_height) {
-                tree.visible = true
-            }
+    for pos in positions {
+        let tree: &mut Tree = tree_grid.get_mut(pos).unwrap();
 
-            previous_heights.push(tree.height);
+        if previous_heights.iter().all(|prev_height| tree.height > *prev_height) {
+            tree.visible = true
         }
+
+        previous_heights.push(tree.height);
     }
 }
 
-fn process_row_2(tree_grid: &mut TreeGrid, row_indices: Vec<usize>, col_indices: Vec<usize>) {
+fn process_row_2(tree_grid: &mut TreeGrid, positions: Vec<Vector2i>) {
     let mut previous_heights: Vec<u32> = Vec::new();
-    for row in &row_indices {
-        for col in &col_indices {
-            let mut tree: &mut Tree = &mut tree_grid[*row][*col];
-            let direction_score = previous_heights
-                .iter()
-                .rev()
-                .position(|prev_height| tree.height <= *prev_height)
-                .map(|score| score + 1)
-                .unwrap_or(previous_heights.len());
-
-            tree.score *= direction_score;
-            previous_heights.push(tree.height);
-        }
+    for pos in positions {
+        let tree: &mut Tree = tree_grid.get_mut(pos).unwrap();
+        let direction_score = previous_heights
+            .iter()
+            .rev()
+            .position(|prev_height| tree.height <= *prev_height)
+            .map(|score| score + 1)
+            .unwrap_or(previous_heights.len());
+
+        tree.score *= direction_score;
+        previous_heights.push(tree.height);
     }
 }
 
-fn count_visible_trees(mut tree_grid: TreeGrid) -> usize {
-    let col_count = tree_grid[0].len();
-    let row_count = tree_grid.len();
+/// Every column and row of the grid, each once from either end.
+fn lines_of_sight(tree_grid: &TreeGrid) -> Vec<Vec<Vector2i>> {
+    let size = tree_grid.size();
+    let columns = (0..size.x).map(|x| (0..size.y).map(|y| Vector2i{x, y}).collect::<Vec<_>>());
+    let rows = (0..size.y).map(|y| (0..size.x).map(|x| Vector2i{x, y}).collect::<Vec<_>>());
 
-    for col in 0..col_count {
-        process_row_1(&mut tree_grid, (0..row_count).collect(), vec![col]);
-        process_row_1(&mut tree_grid, (0..row_count).rev().collect(), vec![col]);
+    columns.chain(rows)
+        .flat_map(|line| [line.iter().rev().copied().collect(), line])
+        .collect()
+}
+
+fn count_visible_trees(mut tree_grid: TreeGrid) -> usize {
+    for line in lines_of_sight(&tree_grid) {
+        process_row_1(&mut tree_grid, line);
     }
 
+    tree_grid.iter().filter(|(_, tree)| tree.visible).count()
+}
 
-    for row in 0..row_count {
-        process_row_1(&mut tree_grid, vec![row], (0..col_count).collect());
-        process_row_1(&mut tree_grid, vec![row], (0..col_count).rev().collect());
+fn get_max_score(mut tree_grid: TreeGrid) -> usize {
+    for line in lines_of_sight(&tree_grid) {
+        process_row_2(&mut tree_grid, line);
     }
 
-    return tree_grid.iter().map(|row| row.iter().filter(|tree| tree.visible).count()).sum();
+    tree_grid.iter().map(|(_, tree)| tree.score).max().unwrap()
 }
 
-fn get_max_score(mut tree_grid: TreeGrid) -> usize {
-    let col_count = tree_grid[0].len();
-    let row_count = tree_grid.len();
+pub(crate) struct Day8;
 
-    for col in 0..col_count {
-        process_row_2(&mut tree_grid, (0..row_count).collect(), vec![col]);
-        process_row_2(&mut tree_grid, (0..row_count).rev().collect(), vec![col]);
-    }
+impl Solution for Day8 {
+    const DAY: usize = 8;
 
+    type Parsed = TreeGrid;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    for row in 0..row_count {
-        process_row_2(&mut tree_grid, vec![row], (0..col_count).collect());
-        process_row_2(&mut tree_grid, vec![row], (0..col_count).rev().collect());
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_grid(input)
     }
 
+    fn part1(tree_grid: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer1 {
+        count_visible_trees(tree_grid)
+    }
 
-    return tree_grid.iter().map(|row| row.iter().map(|tree| tree.score).max().unwrap()).max().unwrap();
+    fn part2(tree_grid: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer2 {
+        get_max_score(tree_grid)
+    }
 }
 
 #[cfg(test)]
@@ -99,21 +120,21 @@ mod tests {
 
     #[test]
     fn simple_test() {
-        assert_eq!(count_visible_trees(parse_grid(fs::read_to_string("src/day8/test_simple.txt").unwrap().as_mut_str())), 21);
+        assert_eq!(count_visible_trees(parse_grid(fs::read_to_string("src/day8/test_simple.txt").unwrap().as_mut_str()).unwrap()), 21);
     }
 
     #[test]
     fn large_test() {
-        assert_eq!(count_visible_trees(parse_grid(fs::read_to_string("src/day8/test_large.txt").unwrap().as_mut_str())), 1816);
+        assert_eq!(count_visible_trees(parse_grid(fs::read_to_string("src/day8/test_large.txt").unwrap().as_mut_str()).unwrap()), 1816);
     }
 
     #[test]
     fn simple_test_2() {
-        assert_eq!(get_max_score(parse_grid(fs::read_to_string("src/day8/test_simple.txt").unwrap().as_mut_str())), 8);
+        assert_eq!(get_max_score(parse_grid(fs::read_to_string("src/day8/test_simple.txt").unwrap().as_mut_str()).unwrap()), 8);
     }
 
     #[test]
     fn large_test_2() {
-       assert_eq!(get_max_score(parse_grid(fs::read_to_string("src/day8/test_large.txt").unwrap().as_mut_str())), 21);
+       assert_eq!(get_max_score(parse_grid(fs::read_to_string("src/day8/test_large.txt").unwrap().as_mut_str()).unwrap()), 383520);
     }
 }
\ No newline at end of file
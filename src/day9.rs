@@ -5,87 +5,40 @@ use regex::{Captures, Match, Regex};
 use std::cell::{Ref, RefCell, RefMut};
 use std::ops::{Deref, Range};
 use std::{cmp, ops};
-
-#[derive(Eq, PartialEq, Hash, Clone, Copy)]
-struct Vector2 {
-    x: i32,
-    y: i32
+use crate::error::{parse_number, ParseError};
+use crate::vector2::Vector2i;
+use crate::observer::Observer;
+use crate::solution::Solution;
+
+fn move_to_vec(direction: &str, amount: i32) -> Option<Vec<Vector2i>>{
+    let step = match direction {
+        "R" => Vector2i{x: 1, y: 0},
+        "L" => Vector2i{x: -1, y: 0},
+        "U" => Vector2i{x: 0, y: 1},
+        "D" => Vector2i{x: 0, y: -1},
+        &_ => return None
+    };
+
+    Some((0..amount).map(|_| step).collect())
 }
 
-impl Vector2 {
-    fn get_manhattan_distance(self, other: Vector2) -> i32 {
-        let absolute = (self - other).abs();
-
-        let mut distance = absolute.x + absolute.y;
-
-
-        if(absolute.x != 0 && absolute.y != 0){
-            distance -= 1;
-        }
-
-        return distance;
-    }
-
-    fn abs(&self) -> Vector2 {
-        return Vector2 {
-            x: self.x.abs(),
-            y: self.y.abs()
-        }
-    }
-
-}
+fn parse_moves(moves_string: &str) -> Result<Vec<Vector2i>, ParseError> {
+    let mut moves = Vec::new();
 
-impl ops::Add<Vector2> for Vector2 {
-    type Output = Vector2;
+    for line in moves_string.lines() {
+        let (direction, amount) = line
+            .split_once(" ")
+            .ok_or_else(|| ParseError::new(Day9::DAY, moves_string, line, "expected a direction and an amount"))?;
 
-    fn add(self, other: Vector2) -> Vector2 {
-        return Vector2 {
-            x: self.x + other.x,
-            y: self.y + other.y,
-        }
+        let amount = parse_number(Day9::DAY, moves_string, amount)?;
+        moves.extend(move_to_vec(direction, amount)
+            .ok_or_else(|| ParseError::new(Day9::DAY, moves_string, direction, "expected R, L, U or D"))?);
     }
-}
 
-impl ops::Sub<Vector2> for Vector2 {
-    type Output = Vector2;
-
-    fn sub(self, other: Vector2) -> Vector2 {
-        return Vector2 {
-            x: self.x - other.x,
-            y: self.y - other.y,
-        }
-    }
+    Ok(moves)
 }
 
-impl ops::Mul<i32> for Vector2 {
-    type Output = Vector2;
-
-    fn mul(self, other: i32) -> Vector2 {
-        return Vector2 {
-            x: self.x * other,
-            y: self.y * other,
-        }
-    }
-}
-
-fn move_to_vec(direction: &str, amount: i32) -> Vec<Vector2>{
-    (0..amount).map(|_| match direction {
-        "R" => Vector2{x: 1, y: 0},
-        "L" => Vector2{x: -1, y: 0},
-        "U" => Vector2{x: 0, y: 1},
-        "D" => Vector2{x: 0, y: -1},
-        &_ => panic!()
-    }).collect()
-}
-
-fn count_visited_positions(moves_string: &str, initial_rope: &mut Vec<Vector2>) -> usize {
-    let moves = moves_string
-        .lines()
-        .map(|line| {
-            let mut split_line = line.split(" ");
-            move_to_vec(split_line.next().unwrap(), split_line.next().unwrap().parse::<i32>().unwrap())
-        }).flatten();
-
+fn count_visited_positions(moves: Vec<Vector2i>, initial_rope: &mut Vec<Vector2i>) -> usize {
     let mut visited_positions = HashSet::new();
 
     visited_positions.insert(initial_rope.last().unwrap().clone());
@@ -96,17 +49,17 @@ fn count_visited_positions(moves_string: &str, initial_rope: &mut Vec<Vector2>)
         initial_rope[0] = initial_rope[0] + rope_move;
 
         for i in 1..initial_rope.len() {
-            if initial_rope[i].get_manhattan_distance(initial_rope[i - 1]) > 1 {
+            if initial_rope[i].get_chebyshev_distance(initial_rope[i - 1]) > 1 {
                 let mut equal_position = initial_rope[i - 1] - initial_rope[i];
 
                 if equal_position.x.abs() == equal_position.y.abs() && equal_position.x.abs() > 1 {
                     equal_position = equal_position - (old_rope[i - 1] - initial_rope[i])
                 }
                 else if equal_position.x.abs() > equal_position.y.abs(){
-                     equal_position.x -= equal_position.x / equal_position.x.abs();
+                     equal_position.x -= equal_position.x.signum();
                 }
                 else {
-                    equal_position.y -= equal_position.y / equal_position.y.abs();
+                    equal_position.y -= equal_position.y.signum();
                 }
 
                 initial_rope[i] = initial_rope[i] + equal_position;
@@ -118,6 +71,28 @@ fn count_visited_positions(moves_string: &str, initial_rope: &mut Vec<Vector2>)
 
     return visited_positions.len()
 }
+pub(crate) struct Day9;
+
+impl Solution for Day9 {
+    const DAY: usize = 9;
+
+    type Parsed = Vec<Vector2i>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_moves(input)
+    }
+
+    fn part1(moves: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer1 {
+        count_visited_positions(moves, &mut vec![Vector2i{x: 0, y: 0}; 2])
+    }
+
+    fn part2(moves: Self::Parsed, _observer: &mut dyn Observer) -> Self::Answer2 {
+        count_visited_positions(moves, &mut vec![Vector2i{x: 0, y: 0}; 10])
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{fs};
@@ -125,16 +100,16 @@ mod tests {
 
     #[test]
     fn simple_test() {
-        assert_eq!(count_visited_positions(fs::read_to_string("src/day9/test_simple.txt").unwrap().as_str(), &mut vec![Vector2{x: 0, y: 0}; 2]), 13);
+        assert_eq!(count_visited_positions(parse_moves(fs::read_to_string("src/day9/test_simple.txt").unwrap().as_str()).unwrap(), &mut vec![Vector2i{x: 0, y: 0}; 2]), 13);
     }
 
     #[test]
     fn medium_test() {
-        assert_eq!(count_visited_positions(fs::read_to_string("src/day9/test_medium.txt").unwrap().as_str(), &mut vec![Vector2{x: 0, y: 0}; 10]), 36);
+        assert_eq!(count_visited_positions(parse_moves(fs::read_to_string("src/day9/test_medium.txt").unwrap().as_str()).unwrap(), &mut vec![Vector2i{x: 0, y: 0}; 10]), 36);
     }
 
     #[test]
     fn large_test() {
-        assert_eq!(count_visited_positions(fs::read_to_string("src/day9/test_large.txt").unwrap().as_str(), &mut vec![Vector2{x: 0, y: 0}; 10]), 2536);
+        assert_eq!(count_visited_positions(parse_moves(fs::read_to_string("src/day9/test_large.txt").unwrap().as_str()).unwrap(), &mut vec![Vector2i{x: 0, y: 0}; 10]), 2536);
     }
 }
\ No newline at end of file
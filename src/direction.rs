@@ -1,40 +1,184 @@
-use Direction::{Down, Left, Right, Up};
+use std::str::FromStr;
 use crate::vector2::Vector2i;
 
-#[derive(PartialEq, Clone, Copy)]
-pub enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Dir4 {
+    N,
+    E,
+    S,
+    W,
 }
 
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
 
-impl Direction {
-    pub(crate) fn to_vector(&self) -> Vector2i {
-        match self {
-            Up => Vector2i{x: 0, y: -1},
-            Right => Vector2i{x: 1, y: 0},
-            Down => Vector2i{x: 0, y: 1},
-            Left => Vector2i{x: -1, y: 0}
+impl Dir4 {
+    pub const ALL: [Dir4; 4] = [Dir4::N, Dir4::E, Dir4::S, Dir4::W];
+
+    pub fn iter() -> impl Iterator<Item = Dir4> {
+        Dir4::ALL.into_iter()
+    }
+
+    pub fn rotate(self, quarter_turns: i32) -> Dir4 {
+        Dir4::ALL[(self as i32 + quarter_turns).rem_euclid(4) as usize]
+    }
+
+    pub fn rotate_right(self) -> Dir4 {
+        self.rotate(1)
+    }
+
+    pub fn rotate_left(self) -> Dir4 {
+        self.rotate(-1)
+    }
+
+    pub fn opposite(self) -> Dir4 {
+        self.rotate(2)
+    }
+
+    pub fn to_vector(self) -> Vector2i {
+        Dir8::from(self).to_vector()
+    }
+
+    pub fn to_vector_y_up(self) -> Vector2i {
+        Dir8::from(self).to_vector_y_up()
+    }
+
+    pub fn from_char(char: char) -> Option<Dir4> {
+        match char {
+            'U' | 'N' | '^' => Some(Dir4::N),
+            'R' | 'E' | '>' => Some(Dir4::E),
+            'D' | 'S' | 'v' => Some(Dir4::S),
+            'L' | 'W' | '<' => Some(Dir4::W),
+            _ => None,
         }
     }
 
-    fn rotate_right(&self) -> Direction {
+    /// The arrow pointing this way, as the puzzles draw it.
+    pub fn to_char(self) -> char {
         match self {
-            Up => Right,
-            Right => Down,
-            Down => Left,
-            Left => Up,
+            Dir4::N => '^',
+            Dir4::E => '>',
+            Dir4::S => 'v',
+            Dir4::W => '<',
         }
     }
+}
+
+impl Dir8 {
+    pub const ALL: [Dir8; 8] = [Dir8::N, Dir8::NE, Dir8::E, Dir8::SE, Dir8::S, Dir8::SW, Dir8::W, Dir8::NW];
+
+    pub fn iter() -> impl Iterator<Item = Dir8> {
+        Dir8::ALL.into_iter()
+    }
 
-    fn rotate_left(&self) -> Direction {
+    pub fn rotate(self, eighth_turns: i32) -> Dir8 {
+        Dir8::ALL[(self as i32 + eighth_turns).rem_euclid(8) as usize]
+    }
+
+    pub fn rotate_right(self) -> Dir8 {
+        self.rotate(1)
+    }
+
+    pub fn rotate_left(self) -> Dir8 {
+        self.rotate(-1)
+    }
+
+    pub fn opposite(self) -> Dir8 {
+        self.rotate(4)
+    }
+
+    pub fn to_vector(self) -> Vector2i {
         match self {
-            Up => Left,
-            Right => Up,
-            Down => Right,
-            Left => Down,
+            Dir8::N => Vector2i{x: 0, y: -1},
+            Dir8::NE => Vector2i{x: 1, y: -1},
+            Dir8::E => Vector2i{x: 1, y: 0},
+            Dir8::SE => Vector2i{x: 1, y: 1},
+            Dir8::S => Vector2i{x: 0, y: 1},
+            Dir8::SW => Vector2i{x: -1, y: 1},
+            Dir8::W => Vector2i{x: -1, y: 0},
+            Dir8::NW => Vector2i{x: -1, y: -1},
+        }
+    }
+
+    pub fn to_vector_y_up(self) -> Vector2i {
+        let vector = self.to_vector();
+
+        Vector2i{x: vector.x, y: -vector.y}
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(direction: Dir4) -> Dir8 {
+        Dir8::ALL[direction as usize * 2]
+    }
+}
+
+impl FromStr for Dir4 {
+    type Err = ();
+
+    fn from_str(string: &str) -> Result<Dir4, ()> {
+        let mut chars = string.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(char), None) => Dir4::from_char(char).ok_or(()),
+            _ => Err(()),
         }
     }
-}
\ No newline at end of file
+}
+
+impl FromStr for Dir8 {
+    type Err = ();
+
+    fn from_str(string: &str) -> Result<Dir8, ()> {
+        match string {
+            "NE" => Ok(Dir8::NE),
+            "SE" => Ok(Dir8::SE),
+            "SW" => Ok(Dir8::SW),
+            "NW" => Ok(Dir8::NW),
+            other => other.parse::<Dir4>().map(Dir8::from),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotations() {
+        assert_eq!(Dir4::N.rotate_left(), Dir4::W);
+        assert_eq!(Dir4::W.rotate_right(), Dir4::N);
+        assert_eq!(Dir4::E.opposite(), Dir4::W);
+        assert_eq!(Dir8::N.rotate_left(), Dir8::NW);
+        assert_eq!(Dir8::NE.rotate(2), Dir8::SE);
+        assert_eq!(Dir8::SW.opposite(), Dir8::NE);
+        assert!(Dir8::iter().all(|direction| direction.rotate(-3).rotate(3) == direction));
+    }
+
+    #[test]
+    fn vectors() {
+        assert_eq!(Dir4::N.to_vector(), Vector2i{x: 0, y: -1});
+        assert_eq!(Dir4::N.to_vector_y_up(), Vector2i{x: 0, y: 1});
+        assert_eq!(Dir8::SE.to_vector(), Vector2i{x: 1, y: 1});
+        assert!(Dir4::iter().all(|direction| direction.to_vector() == -direction.opposite().to_vector()));
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!(Dir4::from_char('U'), Some(Dir4::N));
+        assert_eq!(Dir4::from_char('>'), Some(Dir4::E));
+        assert_eq!("W".parse::<Dir4>(), Ok(Dir4::W));
+        assert_eq!("v".parse::<Dir8>(), Ok(Dir8::S));
+        assert_eq!("NE".parse::<Dir8>(), Ok(Dir8::NE));
+        assert_eq!("X".parse::<Dir4>(), Err(()));
+    }
+}
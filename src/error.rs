@@ -0,0 +1,127 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub day: usize,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub(crate) fn new(day: usize, source: &str, fragment: &str, message: impl Into<String>) -> ParseError {
+        let (line, column) = locate(source, fragment);
+
+        ParseError {
+            day,
+            line,
+            column,
+            text: fragment.to_string(),
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn missing(day: usize, source: &str, message: impl Into<String>) -> ParseError {
+        ParseError::new(day, source, &source[source.len()..], message)
+    }
+
+    pub(crate) fn relative_to(mut self, outer: &str, inner: &str) -> ParseError {
+        let (line, column) = locate(outer, inner);
+
+        if self.line == 1 {
+            self.column += column - 1;
+        }
+        self.line += line - 1;
+
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "day {}, line {}, column {}: {}", self.day, self.line, self.column, self.message)?;
+
+        if !self.text.is_empty() {
+            write!(f, " (found \"{}\")", self.text)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A well-formed input that has no answer, such as a maze with no way through.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolveError {
+    pub day: usize,
+    pub message: String,
+}
+
+impl SolveError {
+    pub(crate) fn new(day: usize, message: impl Into<String>) -> SolveError {
+        SolveError { day, message: message.into() }
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "day {}: {}", self.day, self.message)
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+pub(crate) fn parse_number<T: FromStr>(day: usize, source: &str, token: &str) -> Result<T, ParseError> {
+    token.trim().parse::<T>().map_err(|_| ParseError::new(day, source, token, "expected a number"))
+}
+
+fn locate(source: &str, fragment: &str) -> (usize, usize) {
+    let start = source.as_ptr() as usize;
+    let position = fragment.as_ptr() as usize;
+
+    if position < start || position > start + source.len() {
+        return (1, 1);
+    }
+
+    let preceding = &source[..position - start];
+    let line_start = preceding.rfind('\n').map(|index| index + 1).unwrap_or(0);
+
+    (preceding.matches('\n').count() + 1, preceding[line_start..].chars().count() + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_fragment_in_source() {
+        let source = "first line\nsecond 42x line";
+        let error = ParseError::new(3, source, &source[18..21], "expected a number");
+
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 8, "42x"));
+        assert_eq!(error.to_string(), "day 3, line 2, column 8: expected a number (found \"42x\")");
+    }
+
+    #[test]
+    fn relative_errors_are_moved_into_outer_source() {
+        let source = "a\nb\nc 1-x";
+        let line = &source[4..];
+        let token = &line[2..];
+
+        let error = parse_number::<i32>(4, token, &token[2..]).unwrap_err()
+            .relative_to(line, token)
+            .relative_to(source, line);
+
+        assert_eq!((error.line, error.column), (3, 5));
+    }
+
+    #[test]
+    fn missing_points_at_end_of_source() {
+        let error = ParseError::missing(1, "move 1 from", "expected 'to'");
+
+        assert_eq!((error.line, error.column), (1, 12));
+    }
+}
@@ -0,0 +1,737 @@
+//! Seeded generators for random puzzle inputs. Generated inputs keep to the guarantees the puzzles
+//! make about their real inputs, so every registered solver should be able to solve them.
+
+use std::collections::BTreeSet;
+use itertools::Itertools;
+
+/// SplitMix64, so that a seed gives the same input on every machine.
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform below `n`, ignoring the negligible modulo bias.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    pub fn between(&mut self, low: usize, high: usize) -> usize {
+        low + self.below(high - low + 1)
+    }
+
+    pub fn signed(&mut self, low: i64, high: i64) -> i64 {
+        low + (self.next_u64() % (high - low + 1) as u64) as i64
+    }
+
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.next_u64() % 100 < percent
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// A random input for `day`, or `None` if there is no generator for it.
+pub fn input(day: usize, rng: &mut Rng) -> Option<String> {
+    let input = match day {
+        1 => calories(rng),
+        2 => strategy_guide(rng),
+        3 => rucksacks(rng),
+        4 => assignments(rng),
+        5 => rearrangement(rng, false),
+        6 => datastream(rng, true),
+        7 => terminal_output(rng),
+        8 => tree_heights(rng),
+        9 => rope_motions(rng),
+        10 => program(rng),
+        11 => monkey_notes(rng),
+        12 => heightmap(rng, true),
+        13 => packet_pairs(rng),
+        14 => rock_paths(rng),
+        15 => sensors(rng),
+        16 => valves(rng),
+        17 => jets(rng),
+        18 => cubes(rng),
+        19 => blueprints(rng),
+        20 => encrypted_file(rng),
+        21 => monkey_jobs(rng),
+        22 => monkey_map(rng),
+        23 => elves(rng),
+        24 => basin(rng),
+        25 => snafu_numbers(rng),
+        _ => return None,
+    };
+
+    Some(input)
+}
+
+/// A random input for `day` that breaks one of the guarantees `input` keeps, so that the solver
+/// has to report it rather than answer: day 5 takes crates from an empty stack, day 6 has no
+/// start-of-message marker and day 12 walls off E. `None` for the other days.
+pub fn broken_input(day: usize, rng: &mut Rng) -> Option<String> {
+    let input = match day {
+        5 => rearrangement(rng, true),
+        6 => datastream(rng, false),
+        12 => heightmap(rng, false),
+        _ => return None,
+    };
+
+    Some(input)
+}
+
+fn word(rng: &mut Rng, length: usize) -> String {
+    (0..length).map(|_| (b'a' + rng.below(26) as u8) as char).collect()
+}
+
+fn calories(rng: &mut Rng) -> String {
+    (0..rng.between(1, 20))
+        .map(|_| (0..rng.between(1, 8)).map(|_| rng.between(1, 60000)).join("\n"))
+        .join("\n\n")
+}
+
+fn strategy_guide(rng: &mut Rng) -> String {
+    (0..rng.between(1, 50))
+        .map(|_| format!("{} {}", rng.pick(&['A', 'B', 'C']), rng.pick(&['X', 'Y', 'Z'])))
+        .join("\n")
+}
+
+fn rucksacks(rng: &mut Rng) -> String {
+    let letters: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+    let mut lines = Vec::new();
+
+    for _ in 0..rng.between(1, 5) {
+        let mut pool = letters.clone();
+        rng.shuffle(&mut pool);
+        let badge = pool.pop().unwrap();
+
+        for elf in 0..3 {
+            // Every elf leaves out a third of the pool, so the badge is the only item all three carry.
+            let mut own: Vec<char> = pool.iter().enumerate().filter(|(i, _)| i % 3 != elf).map(|(_, item)| *item).collect();
+            let shared = own.pop().unwrap();
+            let (left_items, right_items) = own.split_at(own.len() / 2);
+
+            let size = rng.between(2, 12);
+            let mut left: Vec<char> = (1..size).map(|_| *rng.pick(left_items)).chain([shared]).collect();
+            let mut right: Vec<char> = (1..size).map(|_| *rng.pick(right_items)).chain([shared]).collect();
+            rng.shuffle(&mut left);
+            rng.shuffle(&mut right);
+
+            let half = if rng.chance(50) { &mut left } else { &mut right };
+            let slot = half.iter().position(|item| *item != shared).unwrap();
+            half[slot] = badge;
+
+            lines.push(left.into_iter().chain(right).collect::<String>());
+        }
+    }
+
+    lines.join("\n")
+}
+
+fn assignments(rng: &mut Rng) -> String {
+    (0..rng.between(1, 50))
+        .map(|_| {
+            let mut range = || {
+                let start = rng.between(1, 99);
+                (start, rng.between(start, 99))
+            };
+            let ((start1, end1), (start2, end2)) = (range(), range());
+
+            format!("{}-{},{}-{}", start1, end1, start2, end2)
+        })
+        .join("\n")
+}
+
+fn rearrangement(rng: &mut Rng, empty_pop: bool) -> String {
+    // The first stack has a crate to spare, so there is always a move.
+    let mut stacks: Vec<Vec<char>> = (0..rng.between(2, 9))
+        .map(|i| (0..rng.between(if i == 0 { 2 } else { 1 }, 6)).map(|_| (b'A' + rng.below(26) as u8) as char).collect())
+        .collect();
+
+    let height = stacks.iter().map(Vec::len).max().unwrap();
+    let mut lines: Vec<String> = (0..height).rev()
+        .map(|level| stacks.iter().map(|stack| stack.get(level).map_or("   ".to_string(), |item| format!("[{}]", item))).join(" "))
+        .collect();
+    lines.push(stacks.iter().enumerate().map(|(i, _)| format!(" {} ", i + 1)).join(" "));
+    lines.push(String::new());
+
+    // Moves never take the last crate of a stack, so every stack has a top crate at the end.
+    for _ in 0..rng.between(1, 30) {
+        let candidates: Vec<usize> = (0..stacks.len()).filter(|i| stacks[*i].len() > 1).collect();
+        if candidates.is_empty() {
+            break;
+        }
+
+        let from = *rng.pick(&candidates);
+        let to = (from + rng.between(1, stacks.len() - 1)) % stacks.len();
+        let amount = rng.between(1, stacks[from].len() - 1);
+
+        let keep = stacks[from].len() - amount;
+        let moved = stacks[from].split_off(keep);
+        stacks[to].extend(moved);
+        lines.push(format!("move {} from {} to {}", amount, from + 1, to + 1));
+    }
+
+    if empty_pop {
+        let from = rng.below(stacks.len());
+        let to = (from + rng.between(1, stacks.len() - 1)) % stacks.len();
+        if !stacks[from].is_empty() {
+            lines.push(format!("move {} from {} to {}", stacks[from].len(), from + 1, to + 1));
+        }
+        lines.push(format!("move {} from {} to {}", rng.between(1, 3), from + 1, to + 1));
+    }
+
+    lines.join("\n")
+}
+
+fn datastream(rng: &mut Rng, with_marker: bool) -> String {
+    // Three letters cannot form a marker on their own, the fourteen distinct letters are inserted somewhere.
+    let length = rng.between(0, 100);
+    let mut stream: Vec<char> = (0..length).map(|_| *rng.pick(&['a', 'b', 'c'])).collect();
+    let mut marker: Vec<char> = ('a'..='z').collect();
+    rng.shuffle(&mut marker);
+
+    // Without the message marker, the stream keeps to thirteen letters, four of which are inserted
+    // somewhere as a packet marker.
+    if !with_marker {
+        marker.retain(|letter| !['a', 'b', 'c'].contains(letter));
+        marker.truncate(10);
+        marker.extend(['a', 'b', 'c']);
+        stream.extend((0..rng.below(40)).map(|_| *rng.pick(&marker)));
+
+        rng.shuffle(&mut marker);
+        let position = rng.between(0, stream.len());
+        stream.splice(position..position, marker.into_iter().take(4));
+        return stream.into_iter().collect();
+    }
+
+    let position = rng.between(0, stream.len());
+    stream.splice(position..position, marker.into_iter().take(14));
+    stream.extend((0..rng.below(20)).map(|_| (b'a' + rng.below(26) as u8) as char));
+
+    stream.into_iter().collect()
+}
+
+fn terminal_output(rng: &mut Rng) -> String {
+    loop {
+        let mut lines = vec!["$ cd /".to_string(), "$ ls".to_string()];
+        directory_listing(rng, 0, &mut lines);
+
+        let used: usize = lines.iter().filter_map(|line| line.split(' ').next()?.parse::<usize>().ok()).sum();
+        if used > 30_000_000 {
+            continue;
+        }
+
+        // A large file at the root fills the disk past 40000000, so there is always something to delete.
+        lines.insert(2, format!("{} {}.dat", 40_000_000 - used + rng.between(1, 25_000_000), word(rng, 5)));
+        return lines.join("\n");
+    }
+}
+
+fn directory_listing(rng: &mut Rng, depth: usize, lines: &mut Vec<String>) {
+    let directories: Vec<String> = (0..if depth < 4 { rng.below(4) } else { 0 })
+        .map(|i| format!("{}{}", word(rng, 3), i))
+        .collect();
+    let mut entries: Vec<String> = (0..rng.below(5))
+        .map(|i| {
+            let size = if rng.chance(5) { rng.between(1_000_000, 10_000_000) } else { rng.between(1, 300_000) };
+
+            format!("{} {}{}.txt", size, word(rng, 4), i)
+        })
+        .chain(directories.iter().map(|name| format!("dir {}", name)))
+        .collect();
+    rng.shuffle(&mut entries);
+    lines.extend(entries);
+
+    for name in directories {
+        lines.push(format!("$ cd {}", name));
+        lines.push("$ ls".to_string());
+        directory_listing(rng, depth + 1, lines);
+        lines.push("$ cd ..".to_string());
+    }
+}
+
+fn tree_heights(rng: &mut Rng) -> String {
+    let (width, height) = (rng.between(1, 15), rng.between(1, 15));
+
+    (0..height).map(|_| (0..width).map(|_| rng.below(10)).join("")).join("\n")
+}
+
+fn rope_motions(rng: &mut Rng) -> String {
+    (0..rng.between(1, 100))
+        .map(|_| format!("{} {}", rng.pick(&['U', 'D', 'L', 'R']), rng.between(1, 10)))
+        .join("\n")
+}
+
+fn program(rng: &mut Rng) -> String {
+    let mut lines = Vec::new();
+    let mut cycles = 0;
+
+    // The CRT draws 240 pixels.
+    while cycles < 240 {
+        if rng.chance(40) {
+            lines.push("noop".to_string());
+            cycles += 1;
+        } else {
+            lines.push(format!("addx {}", rng.signed(-20, 20)));
+            cycles += 2;
+        }
+    }
+
+    lines.join("\n")
+}
+
+fn monkey_notes(rng: &mut Rng) -> String {
+    let mut divisors = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+    rng.shuffle(&mut divisors);
+    let count = rng.between(2, 8);
+
+    (0..count)
+        .map(|monkey| {
+            let items = (0..rng.between(1, 5)).map(|_| rng.between(1, 99)).join(", ");
+            let operation = match rng.below(5) {
+                0 => "old * old".to_string(),
+                1 | 2 => format!("old * {}", rng.between(2, 19)),
+                _ => format!("old + {}", rng.between(1, 8)),
+            };
+            let mut target = || (monkey + rng.between(1, count - 1)) % count;
+            let (if_true, if_false) = (target(), target());
+
+            format!(
+                "Monkey {}:\n  Starting items: {}\n  Operation: new = {}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}",
+                monkey, items, operation, divisors[monkey], if_true, if_false
+            )
+        })
+        .join("\n\n")
+}
+
+fn heightmap(rng: &mut Rng, reachable: bool) -> String {
+    let (width, height) = (rng.between(5, 12), rng.between(6, 10));
+    let mut grid: Vec<Vec<u8>> = (0..height).map(|_| (0..width).map(|_| b'a' + rng.below(26) as u8).collect()).collect();
+
+    // A winding path climbs from S to E one letter at a time, so E can always be reached.
+    let path: Vec<(usize, usize)> = (0..height)
+        .flat_map(|y| (0..width).map(move |x| if y % 2 == 0 { (x, y) } else { (width - 1 - x, y) }))
+        .collect();
+    let length = rng.between(26, path.len());
+    let mut climbs: Vec<usize> = (1..length).collect();
+    rng.shuffle(&mut climbs);
+    let climbs: BTreeSet<usize> = climbs.into_iter().take(25).collect();
+
+    let mut elevation = b'a';
+    for (step, &(x, y)) in path.iter().take(length).enumerate() {
+        if climbs.contains(&step) {
+            elevation += 1;
+        }
+        grid[y][x] = elevation;
+    }
+
+    let (start, end) = (path[0], path[length - 1]);
+    grid[start.1][start.0] = b'S';
+    grid[end.1][end.0] = b'E';
+
+    // Squares next to E that are lower than y cannot climb onto it.
+    if !reachable {
+        let neighbours = [(end.0.wrapping_sub(1), end.1), (end.0 + 1, end.1), (end.0, end.1.wrapping_sub(1)), (end.0, end.1 + 1)];
+        for (x, y) in neighbours {
+            if let Some(square) = grid.get_mut(y).and_then(|row| row.get_mut(x)).filter(|square| **square != b'S') {
+                *square = b'a' + rng.below(24) as u8;
+            }
+        }
+    }
+
+    if rng.chance(50) {
+        grid.reverse();
+    }
+
+    grid.into_iter().map(|row| String::from_utf8(row).unwrap()).join("\n")
+}
+
+fn packet_items(rng: &mut Rng, depth: usize) -> Vec<String> {
+    (0..rng.below(5))
+        .map(|_| match depth < 3 && rng.chance(30) {
+            true => format!("[{}]", packet_items(rng, depth + 1).join(",")),
+            false => rng.between(0, 10).to_string(),
+        })
+        .collect()
+}
+
+fn packet_pairs(rng: &mut Rng) -> String {
+    // Leading numbers that differ keep every pair ordered, and avoiding 2 and 6 keeps packets apart from the dividers.
+    let leading = [0, 1, 3, 4, 5, 7, 8, 9, 10];
+
+    (0..rng.between(1, 10))
+        .map(|_| {
+            let left = *rng.pick(&leading);
+            let right = *rng.pick(&leading.iter().copied().filter(|value| *value != left).collect::<Vec<_>>());
+            let mut packet = |first: i32| format!("[{}]", [first.to_string()].into_iter().chain(packet_items(rng, 1)).join(","));
+
+            format!("{}\n{}", packet(left), packet(right))
+        })
+        .join("\n\n")
+}
+
+fn rock_paths(rng: &mut Rng) -> String {
+    (0..rng.between(1, 8))
+        .map(|_| {
+            let (mut x, mut y) = (rng.signed(485, 515), rng.signed(3, 30));
+            let mut points = vec![(x, y)];
+
+            for segment in 0..rng.between(1, 4) {
+                match segment % 2 == 0 {
+                    true => x += rng.signed(-6, 6),
+                    false => y = (y + rng.signed(-6, 6)).max(1),
+                }
+                points.push((x, y));
+            }
+
+            points.into_iter().map(|(x, y)| format!("{},{}", x, y)).join(" -> ")
+        })
+        .join("\n")
+}
+
+/// Sensors around the row part 1 looks at. Part 2 looks for the one uncovered position in a square
+/// of 4000000 by 4000000, which takes sensors with huge ranges that are too slow to test with.
+fn sensors(rng: &mut Rng) -> String {
+    (0..rng.between(1, 10))
+        .map(|_| {
+            let (x, y) = (rng.signed(0, 200), rng.signed(2_000_000 - 40, 2_000_000 + 40));
+
+            format!(
+                "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+                x, y, x + rng.signed(-30, 30), y + rng.signed(-30, 30)
+            )
+        })
+        .join("\n")
+}
+
+/// Sensors that cover every position in the square from 0 to `size` but one, for part 2 on a
+/// square small enough to check. Four sensors just out of reach diagonally from the gap cover
+/// the square between them, the others are too short-sighted to reach the gap.
+pub fn sensors_with_gap(rng: &mut Rng, size: i64) -> String {
+    let gap = (rng.signed(0, size), rng.signed(0, size));
+    let reach = size + 1;
+    let mut sensors: Vec<((i64, i64), (i64, i64))> = [(-1, -1), (-1, 1), (1, -1), (1, 1)]
+        .into_iter()
+        .map(|(x, y)| ((gap.0 + x * reach, gap.1 + y * reach), (gap.0 + x * (1 - reach), gap.1 + y * reach)))
+        .collect();
+
+    for _ in 0..rng.below(5) {
+        let sensor = (rng.signed(0, size), rng.signed(0, size));
+        let distance = (sensor.0 - gap.0).abs() + (sensor.1 - gap.1).abs();
+        if distance > 0 {
+            let range = rng.signed(0, distance - 1);
+            let across = rng.signed(0, range);
+            sensors.push((sensor, (sensor.0 + across, sensor.1 + range - across)));
+        }
+    }
+    rng.shuffle(&mut sensors);
+
+    sensors.into_iter()
+        .map(|(sensor, beacon)| format!("Sensor at x={}, y={}: closest beacon is at x={}, y={}", sensor.0, sensor.1, beacon.0, beacon.1))
+        .join("\n")
+}
+
+fn valves(rng: &mut Rng) -> String {
+    let count = rng.between(3, 9);
+    let mut names = vec!["AA".to_string()];
+    while names.len() < count {
+        let name: String = (0..2).map(|_| (b'A' + rng.below(26) as u8) as char).collect();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    // A spanning tree keeps every valve reachable from AA, the extra tunnels add loops.
+    let mut tunnels = vec![BTreeSet::new(); count];
+    let mut connect = |a: usize, b: usize| if a != b {
+        tunnels[a].insert(b);
+        tunnels[b].insert(a);
+    };
+    for valve in 1..count {
+        connect(valve, rng.below(valve));
+    }
+    for _ in 0..rng.below(count) {
+        connect(rng.below(count), rng.below(count));
+    }
+
+    let mut lines: Vec<String> = names.iter().zip(&tunnels)
+        .enumerate()
+        .map(|(valve, (name, tunnels))| {
+            let flow_rate = if valve == 0 || rng.chance(40) { 0 } else { rng.between(1, 25) };
+            let tunnels = tunnels.iter().map(|tunnel| &names[*tunnel]).join(", ");
+
+            match tunnels.contains(',') {
+                true => format!("Valve {} has flow rate={}; tunnels lead to valves {}", name, flow_rate, tunnels),
+                false => format!("Valve {} has flow rate={}; tunnel leads to valve {}", name, flow_rate, tunnels),
+            }
+        })
+        .collect();
+    rng.shuffle(&mut lines);
+
+    lines.join("\n")
+}
+
+fn jets(rng: &mut Rng) -> String {
+    (0..rng.between(1, 60)).map(|_| rng.pick(&['<', '>'])).collect()
+}
+
+fn cubes(rng: &mut Rng) -> String {
+    let cubes: BTreeSet<(usize, usize, usize)> = (0..rng.between(1, 80))
+        .map(|_| (rng.between(1, 8), rng.between(1, 8), rng.between(1, 8)))
+        .collect();
+
+    cubes.into_iter().map(|(x, y, z)| format!("{},{},{}", x, y, z)).join("\n")
+}
+
+fn blueprints(rng: &mut Rng) -> String {
+    (1..=rng.between(1, 2))
+        .map(|id| format!(
+            "Blueprint {}: Each ore robot costs {} ore. Each clay robot costs {} ore. Each obsidian robot costs {} ore and {} clay. Each geode robot costs {} ore and {} obsidian.",
+            id, rng.between(2, 4), rng.between(2, 4), rng.between(2, 4), rng.between(5, 16), rng.between(2, 4), rng.between(5, 16)
+        ))
+        .join("\n")
+}
+
+fn encrypted_file(rng: &mut Rng) -> String {
+    let mut numbers: Vec<i64> = (0..rng.between(1, 50))
+        .map(|_| match rng.signed(-9999, 9999) {
+            0 => 1,
+            number => number,
+        })
+        .collect();
+    let zero = rng.between(0, numbers.len());
+    numbers.insert(zero, 0);
+
+    numbers.into_iter().join("\n")
+}
+
+struct Jobs<'a> {
+    rng: &'a mut Rng,
+    lines: Vec<String>,
+    names: BTreeSet<String>,
+}
+
+impl Jobs<'_> {
+    fn name(&mut self) -> String {
+        loop {
+            let name = word(self.rng, 4);
+            if name != "root" && name != "humn" && self.names.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+
+    /// A monkey that yells `value`, possibly through more monkeys doing exact integer arithmetic.
+    fn yelling(&mut self, value: i64, depth: usize) -> String {
+        let name = self.name();
+        let divisors: Vec<i64> = (1..=12).filter(|divisor| value % divisor == 0).collect();
+
+        let job = match if depth == 0 { 0 } else { self.rng.below(5) } {
+            1 => {
+                let left = self.rng.signed(-50, 50);
+                format!("{} + {}", self.yelling(left, depth - 1), self.yelling(value - left, depth - 1))
+            }
+            2 => {
+                let right = self.rng.signed(-50, 50);
+                format!("{} - {}", self.yelling(value + right, depth - 1), self.yelling(right, depth - 1))
+            }
+            3 => {
+                let right = *self.rng.pick(&divisors);
+                format!("{} * {}", self.yelling(value / right, depth - 1), self.yelling(right, depth - 1))
+            }
+            4 => {
+                let right = self.rng.signed(1, 6);
+                format!("{} / {}", self.yelling(value * right, depth - 1), self.yelling(right, depth - 1))
+            }
+            _ => value.to_string(),
+        };
+
+        self.lines.push(format!("{}: {}", name, job));
+        name
+    }
+
+    /// A monkey whose value depends on humn, with humn yelling whatever makes it come out as `value`.
+    fn depending_on_humn(&mut self, value: i64, depth: usize) -> String {
+        if depth == 0 {
+            self.lines.push(format!("humn: {}", value));
+            return "humn".to_string();
+        }
+
+        let name = self.name();
+        let (humn_side, other, other_value, humn_on_left) = match self.rng.below(5) {
+            0 => {
+                let other = self.rng.signed(-50, 50);
+                ('+', other, other, self.rng.chance(50))
+            }
+            1 => {
+                let other = self.rng.signed(-50, 50);
+                ('-', other, other, true)
+            }
+            2 => {
+                let other = self.rng.signed(-50, 50);
+                ('-', other, other, false)
+            }
+            3 if value != 0 => {
+                let divisors: Vec<i64> = (1..=12).filter(|divisor| value % divisor == 0).collect();
+                let other = *self.rng.pick(&divisors);
+                ('*', other, other, self.rng.chance(50))
+            }
+            // humn never ends up dividing, as in the puzzle inputs.
+            _ => {
+                let other = self.rng.signed(1, 6);
+                ('/', other, other, true)
+            }
+        };
+        let inner = match (humn_side, humn_on_left) {
+            ('+', _) => value - other,
+            ('-', true) => value + other,
+            ('-', false) => other - value,
+            ('*', _) => value / other,
+            _ => value * other,
+        };
+
+        let humn_name = self.depending_on_humn(inner, depth - 1);
+        let other_name = self.yelling(other_value, 2);
+        let job = match humn_on_left {
+            true => format!("{} {} {}", humn_name, humn_side, other_name),
+            false => format!("{} {} {}", other_name, humn_side, humn_name),
+        };
+
+        self.lines.push(format!("{}: {}", name, job));
+        name
+    }
+}
+
+/// Monkey jobs where humn already yells the number part 2 is looking for.
+fn monkey_jobs(rng: &mut Rng) -> String {
+    let target = rng.signed(-1000, 1000);
+    let depth = rng.between(1, 6);
+    let mut jobs = Jobs { rng, lines: Vec::new(), names: BTreeSet::new() };
+
+    let left = jobs.depending_on_humn(target, depth);
+    let right = jobs.yelling(target, 3);
+    jobs.lines.push(format!("root: {} + {}", left, right));
+
+    let mut lines = jobs.lines;
+    rng.shuffle(&mut lines);
+    lines.join("\n")
+}
+
+/// A map folding into a cube with sides of 50, laid out like the puzzle input that part 2 is written for.
+fn monkey_map(rng: &mut Rng) -> String {
+    let faces = [(1, 0), (2, 0), (1, 1), (0, 2), (1, 2), (0, 3)];
+    let mut lines: Vec<String> = (0..200)
+        .map(|y| {
+            let columns: Vec<usize> = faces.iter().filter(|(_, row)| *row == y / 50).map(|(column, _)| *column).collect();
+            let first = *columns.iter().min().unwrap();
+            let last = *columns.iter().max().unwrap();
+
+            " ".repeat(first * 50) + &(first * 50..(last + 1) * 50)
+                .map(|x| if (x, y) != (50, 0) && rng.chance(10) { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect();
+
+    lines.push(String::new());
+    lines.push((0..rng.between(1, 20)).map(|_| format!("{}{}", rng.between(1, 60), rng.pick(&['L', 'R']))).join("") + &rng.between(1, 60).to_string());
+
+    lines.join("\n")
+}
+
+fn elves(rng: &mut Rng) -> String {
+    let (width, height) = (rng.between(1, 12), rng.between(1, 12));
+    let mut grid: Vec<Vec<char>> = (0..height).map(|_| (0..width).map(|_| if rng.chance(40) { '#' } else { '.' }).collect()).collect();
+    grid[rng.below(height)][rng.below(width)] = '#';
+
+    grid.into_iter().map(|row| row.into_iter().collect::<String>()).join("\n")
+}
+
+fn basin(rng: &mut Rng) -> String {
+    let (width, height) = (rng.between(3, 10), rng.between(3, 8));
+    let mut lines = vec![format!("#.{}", "#".repeat(width))];
+
+    for _ in 0..height {
+        // No blizzard moves up or down through the entrance or exit columns, as in the puzzle inputs.
+        let row: String = (0..width)
+            .map(|x| match rng.chance(25) {
+                true if x == 0 || x == width - 1 => *rng.pick(&['<', '>']),
+                true => *rng.pick(&['<', '>', '^', 'v']),
+                false => '.',
+            })
+            .collect();
+        lines.push(format!("#{}#", row));
+    }
+
+    lines.push(format!("{}.#", "#".repeat(width)));
+    lines.join("\n")
+}
+
+pub fn to_snafu(mut number: i64) -> String {
+    let mut digits = Vec::new();
+
+    while number != 0 {
+        let (digit, carry) = match number.rem_euclid(5) {
+            0 => ('0', 0),
+            1 => ('1', 0),
+            2 => ('2', 0),
+            3 => ('=', 1),
+            _ => ('-', 1),
+        };
+        digits.push(digit);
+        number = number.div_euclid(5) + carry;
+    }
+
+    match digits.is_empty() {
+        true => "0".to_string(),
+        false => digits.into_iter().rev().collect(),
+    }
+}
+
+fn snafu_numbers(rng: &mut Rng) -> String {
+    (0..rng.between(1, 30))
+        .map(|_| to_snafu(rng.between(1, 1_000_000_000_000) as i64))
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_input() {
+        for day in 1..=25 {
+            assert_eq!(input(day, &mut Rng::new(7)), input(day, &mut Rng::new(7)));
+        }
+
+        assert_ne!(input(1, &mut Rng::new(1)), input(1, &mut Rng::new(2)));
+        assert_eq!(input(26, &mut Rng::new(1)), None);
+    }
+
+    #[test]
+    fn snafu_digits() {
+        assert_eq!(to_snafu(1), "1");
+        assert_eq!(to_snafu(3), "1=");
+        assert_eq!(to_snafu(2022), "1=11-2");
+        assert_eq!(to_snafu(314159265), "1121-1110-1=0");
+    }
+}
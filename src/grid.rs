@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+use crate::direction::{Dir4, Dir8};
+use crate::error::ParseError;
+use crate::parse;
+use crate::vector2::Vector2i;
+
+pub trait Storage<T> {
+    fn with_size(width: usize, height: usize) -> Self;
+    fn get(&self, pos: Vector2i) -> Option<&T>;
+    fn get_mut(&mut self, pos: Vector2i) -> Option<&mut T>;
+    fn insert(&mut self, pos: Vector2i, value: T) -> Option<T>;
+    fn remove(&mut self, pos: Vector2i) -> Option<T>;
+    fn cells<'a>(&'a self) -> impl Iterator<Item = (Vector2i, &'a T)> where T: 'a;
+}
+
+pub struct Dense<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<Option<T>>,
+}
+
+impl<T> Dense<T> {
+    fn index(&self, pos: Vector2i) -> Option<usize> {
+        let (x, y) = (usize::try_from(pos.x).ok()?, usize::try_from(pos.y).ok()?);
+
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    fn position(&self, index: usize) -> Vector2i {
+        Vector2i{x: (index % self.width) as i32, y: (index / self.width) as i32}
+    }
+}
+
+impl<T> Storage<T> for Dense<T> {
+    fn with_size(width: usize, height: usize) -> Self {
+        Dense {
+            width,
+            height,
+            cells: (0..width * height).map(|_| None).collect(),
+        }
+    }
+
+    fn get(&self, pos: Vector2i) -> Option<&T> {
+        self.cells[self.index(pos)?].as_ref()
+    }
+
+    fn get_mut(&mut self, pos: Vector2i) -> Option<&mut T> {
+        let index = self.index(pos)?;
+
+        self.cells[index].as_mut()
+    }
+
+    fn insert(&mut self, pos: Vector2i, value: T) -> Option<T> {
+        let index = self.index(pos)
+            .unwrap_or_else(|| panic!("{} is outside of a {}x{} dense grid", pos, self.width, self.height));
+
+        self.cells[index].replace(value)
+    }
+
+    fn remove(&mut self, pos: Vector2i) -> Option<T> {
+        let index = self.index(pos)?;
+
+        self.cells[index].take()
+    }
+
+    fn cells<'a>(&'a self) -> impl Iterator<Item = (Vector2i, &'a T)> where T: 'a {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter_map(|(index, cell)| cell.as_ref().map(|value| (self.position(index), value)))
+    }
+}
+
+pub struct Sparse<T> {
+    cells: HashMap<Vector2i, T>,
+}
+
+impl<T> Storage<T> for Sparse<T> {
+    fn with_size(_width: usize, _height: usize) -> Self {
+        Sparse { cells: HashMap::new() }
+    }
+
+    fn get(&self, pos: Vector2i) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    fn get_mut(&mut self, pos: Vector2i) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    fn insert(&mut self, pos: Vector2i, value: T) -> Option<T> {
+        self.cells.insert(pos, value)
+    }
+
+    fn remove(&mut self, pos: Vector2i) -> Option<T> {
+        self.cells.remove(&pos)
+    }
+
+    fn cells<'a>(&'a self) -> impl Iterator<Item = (Vector2i, &'a T)> where T: 'a {
+        self.cells.iter().map(|(pos, value)| (*pos, value))
+    }
+}
+
+pub struct Grid<T, S: Storage<T> = Dense<T>> {
+    storage: S,
+    cell_type: PhantomData<T>,
+}
+
+pub type DenseGrid<T> = Grid<T, Dense<T>>;
+pub type SparseGrid<T> = Grid<T, Sparse<T>>;
+
+impl<T, S: Storage<T>> Grid<T, S> {
+    pub fn new(width: usize, height: usize) -> Self {
+        Grid {
+            storage: S::with_size(width, height),
+            cell_type: PhantomData,
+        }
+    }
+
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> Option<T>) -> Self {
+        match Grid::try_parse(0, input, |char| Ok::<_, String>(cell(char))) {
+            Ok(grid) => grid,
+            Err(_) => unreachable!(),
+        }
+    }
+
+    pub fn try_parse<M: Into<String>>(day: usize, input: &str, cell: impl FnMut(char) -> Result<Option<T>, M>) -> Result<Self, ParseError> {
+        let rows = parse::char_grid(day, input, input, cell)?;
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let mut grid = Grid::new(width, rows.len());
+
+        for (y, row) in rows.into_iter().enumerate() {
+            for (x, value) in row.into_iter().enumerate() {
+                if let Some(value) = value {
+                    grid.insert(Vector2i{x: x as i32, y: y as i32}, value);
+                }
+            }
+        }
+
+        Ok(grid)
+    }
+
+    pub fn get(&self, pos: Vector2i) -> Option<&T> {
+        self.storage.get(pos)
+    }
+
+    pub fn get_mut(&mut self, pos: Vector2i) -> Option<&mut T> {
+        self.storage.get_mut(pos)
+    }
+
+    pub fn contains(&self, pos: Vector2i) -> bool {
+        self.get(pos).is_some()
+    }
+
+    pub fn insert(&mut self, pos: Vector2i, value: T) -> Option<T> {
+        self.storage.insert(pos, value)
+    }
+
+    pub fn remove(&mut self, pos: Vector2i) -> Option<T> {
+        self.storage.remove(pos)
+    }
+
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Vector2i, &T)> {
+        self.storage.cells()
+    }
+
+    pub fn neighbours4(&self, pos: Vector2i) -> impl Iterator<Item = (Vector2i, &T)> {
+        Dir4::iter().filter_map(move |dir| self.cell_at(pos + dir.to_vector()))
+    }
+
+    pub fn neighbours8(&self, pos: Vector2i) -> impl Iterator<Item = (Vector2i, &T)> {
+        Dir8::iter().filter_map(move |dir| self.cell_at(pos + dir.to_vector()))
+    }
+
+    fn cell_at(&self, pos: Vector2i) -> Option<(Vector2i, &T)> {
+        self.get(pos).map(|value| (pos, value))
+    }
+
+    pub fn get_aabb(&self) -> Option<(Vector2i, Vector2i)> {
+        self.iter().fold(None, |aabb, (pos, _)| Some(match aabb {
+            None => (pos, pos),
+            Some((min_pos, max_pos)) => (
+                Vector2i{x: min_pos.x.min(pos.x), y: min_pos.y.min(pos.y)},
+                Vector2i{x: max_pos.x.max(pos.x), y: max_pos.y.max(pos.y)},
+            ),
+        }))
+    }
+
+    pub fn render(&self, mut cell: impl FnMut(Option<&T>) -> char) -> String {
+        let Some((min_pos, max_pos)) = self.get_aabb() else {
+            return String::new();
+        };
+
+        (min_pos.y..=max_pos.y)
+            .map(|y| (min_pos.x..=max_pos.x).map(|x| cell(self.get(Vector2i{x, y}))).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T> DenseGrid<T> {
+    /// The width and height the grid was made with, whether or not its edges are filled.
+    pub fn size(&self) -> Vector2i {
+        Vector2i{x: self.storage.width as i32, y: self.storage.height as i32}
+    }
+}
+
+impl<T: Display, S: Storage<T>> Display for Grid<T, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let rendered = self.render(|cell| match cell {
+            Some(value) => value.to_string().chars().next().unwrap_or(' '),
+            None => '.',
+        });
+
+        f.write_str(&rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_digit(char: char) -> Result<Option<u32>, &'static str> {
+        match char {
+            '.' => Ok(None),
+            _ => char.to_digit(10).map(Some).ok_or("expected a digit or ."),
+        }
+    }
+
+    #[test]
+    fn dense_and_sparse_agree() {
+        let input = "1.2\n.3.\n4..";
+        let dense: DenseGrid<u32> = Grid::try_parse(0, input, parse_digit).unwrap();
+        let sparse: SparseGrid<u32> = Grid::try_parse(0, input, parse_digit).unwrap();
+
+        assert_eq!((dense.len(), sparse.len()), (4, 4));
+        assert_eq!(dense.get(Vector2i{x: 1, y: 1}), Some(&3));
+        assert_eq!(sparse.get(Vector2i{x: 1, y: 1}), Some(&3));
+        assert_eq!(dense.get(Vector2i{x: -1, y: 0}), None);
+        assert_eq!(dense.get(Vector2i{x: 3, y: 0}), None);
+        assert_eq!(dense.size(), Vector2i{x: 3, y: 3});
+        assert_eq!(dense.to_string(), input);
+        assert_eq!(sparse.to_string(), input);
+    }
+
+    #[test]
+    fn neighbours_and_bounds() {
+        let mut grid: SparseGrid<u32> = Grid::parse("1.2\n.3.\n4..", |char| char.to_digit(10));
+
+        assert_eq!(grid.neighbours4(Vector2i{x: 1, y: 1}).count(), 0);
+        assert_eq!(grid.neighbours8(Vector2i{x: 1, y: 1}).map(|(_, value)| *value).sum::<u32>(), 7);
+
+        grid.insert(Vector2i{x: -2, y: 5}, 9);
+        assert_eq!(grid.get_aabb(), Some((Vector2i{x: -2, y: 0}, Vector2i{x: 2, y: 5})));
+    }
+
+    #[test]
+    fn parse_errors_point_at_cell() {
+        let error = DenseGrid::try_parse(4, "1.\n.x", parse_digit).err().unwrap();
+
+        assert_eq!((error.day, error.line, error.column, error.text.as_str()), (4, 2, 2, "x"));
+    }
+}
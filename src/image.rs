@@ -0,0 +1,503 @@
+//! Still images and animated GIFs of the frames the grid days draw, without any image crates.
+//! Every cell becomes a square of pixels coloured by its type, through a palette that can be
+//! overridden by name. Images keep palette indices, which is what PNG and GIF both store.
+
+use std::fmt::{Display, Formatter};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Duration;
+use crate::animate::{self, Cell, Frame};
+use crate::runner::RunError;
+
+const DEFAULT_COLOURS: [(&str, [u8; 3]); 15] = [
+    ("void", [0x0f, 0x0f, 0x23]),
+    ("empty", [0x1e, 0x1e, 0x3c]),
+    ("wall", [0xcc, 0xcc, 0xcc]),
+    ("rock", [0x80, 0x80, 0x80]),
+    ("sand", [0xe6, 0xc3, 0x5c]),
+    ("elf", [0x00, 0xcc, 0x00]),
+    ("blizzard", [0x66, 0xcc, 0xff]),
+    ("actor", [0xff, 0x33, 0x33]),
+    ("trail", [0xcc, 0x66, 0xcc]),
+    ("lit", [0xff, 0xff, 0x66]),
+    ("covered", [0x33, 0x66, 0x99]),
+    ("sensor", [0x00, 0xcc, 0x66]),
+    ("beacon", [0x33, 0x66, 0xff]),
+    ("lava", [0xff, 0x66, 0x00]),
+    ("pocket", [0x99, 0x66, 0xff]),
+];
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Palette {
+    colours: Vec<(&'static str, [u8; 3])>,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette { colours: DEFAULT_COLOURS.to_vec() }
+    }
+}
+
+impl Palette {
+    pub fn set(&mut self, name: &str, colour: [u8; 3]) -> Result<(), String> {
+        let entry = self.colours.iter_mut().find(|(known, _)| *known == name).ok_or(format!("unknown cell type '{}'", name))?;
+        entry.1 = colour;
+
+        Ok(())
+    }
+
+    /// Applies overrides like `sand=ffcc00,rock=404040`.
+    pub fn with_overrides(mut self, overrides: &str) -> Result<Self, String> {
+        for assignment in overrides.split(',').map(str::trim).filter(|assignment| !assignment.is_empty()) {
+            let (name, hex) = assignment.split_once('=').ok_or(format!("expected name=rrggbb, got '{}'", assignment))?;
+            let hex = hex.trim().trim_start_matches('#');
+            let value = u32::from_str_radix(hex, 16).ok().filter(|_| hex.len() == 6).ok_or(format!("invalid colour '{}'", hex))?;
+
+            self.set(name.trim(), [(value >> 16) as u8, (value >> 8) as u8, value as u8])?;
+        }
+
+        Ok(self)
+    }
+
+    pub fn index(&self, cell: Cell) -> u8 {
+        self.colours.iter().position(|(name, _)| *name == cell.name()).unwrap_or(0) as u8
+    }
+
+    pub fn colours(&self) -> Vec<[u8; 3]> {
+        self.colours.iter().map(|(_, colour)| *colour).collect()
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub colours: Vec<[u8; 3]>,
+    /// Palette indices, row by row.
+    pub pixels: Vec<u8>,
+}
+
+impl Image {
+    /// The frame with every cell drawn as a `cell_size` by `cell_size` square.
+    pub fn render(frame: &Frame, palette: &Palette, cell_size: usize) -> Image {
+        let width = frame.cells.first().map_or(0, |row| row.len()) * cell_size;
+        let mut pixels = Vec::with_capacity(width * frame.cells.len() * cell_size);
+
+        for row in &frame.cells {
+            let line: Vec<u8> = row.iter().flat_map(|cell| [palette.index(*cell)].repeat(cell_size)).collect();
+            for _ in 0..cell_size {
+                pixels.extend_from_slice(&line);
+            }
+        }
+
+        Image { width, height: frame.cells.len() * cell_size, colours: palette.colours(), pixels }
+    }
+
+    /// A binary PPM, which most viewers open and is trivial to convert.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut ppm = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        ppm.extend(self.pixels.iter().flat_map(|index| self.colours[*index as usize]));
+
+        ppm
+    }
+
+    /// An indexed colour PNG, deflated with stored blocks only.
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut header = Vec::new();
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        header.extend([8, 3, 0, 0, 0]);
+
+        let scanlines: Vec<u8> = self.pixels.chunks(self.width.max(1)).flat_map(|row| [&[0][..], row].concat()).collect();
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png_chunk(&mut png, b"IHDR", &header);
+        png_chunk(&mut png, b"PLTE", &self.colours.concat());
+        png_chunk(&mut png, b"IDAT", &zlib_stored(&scanlines));
+        png_chunk(&mut png, b"IEND", &[]);
+
+        png
+    }
+}
+
+fn png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    png.extend(kind);
+    png.extend(data);
+    png.extend(crc32(&[&kind[..], data].concat()).to_be_bytes());
+}
+
+pub fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |crc, byte| {
+        (0..8).fold(crc ^ *byte as u32, |crc, _| if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 })
+    })
+}
+
+pub fn adler32(bytes: &[u8]) -> u32 {
+    let (a, b) = bytes.iter().fold((1u32, 0u32), |(a, b), byte| {
+        let a = (a + *byte as u32) % 65521;
+
+        (a, (b + a) % 65521)
+    });
+
+    b << 16 | a
+}
+
+/// A zlib stream of stored blocks, which any inflater reads.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut stream = vec![0x78, 0x01];
+    let blocks: Vec<&[u8]> = if data.is_empty() { vec![&[]] } else { data.chunks(0xffff).collect() };
+
+    for (i, block) in blocks.iter().enumerate() {
+        let length = block.len() as u16;
+        stream.push((i + 1 == blocks.len()) as u8);
+        stream.extend(length.to_le_bytes());
+        stream.extend((!length).to_le_bytes());
+        stream.extend(*block);
+    }
+
+    stream.extend(adler32(data).to_be_bytes());
+    stream
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    length: u8,
+}
+
+impl BitWriter {
+    /// Appends the `size` low bits of `code`, least significant first as GIF wants them.
+    fn write(&mut self, code: u16, size: u8) {
+        self.buffer |= (code as u32) << self.length;
+        self.length += size;
+
+        while self.length >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.length -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.length > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+
+        self.bytes
+    }
+}
+
+/// The variable width LZW codes of `pixels`, starting over with a clear code whenever the table
+/// fills up at 4096 entries.
+fn lzw(pixels: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+    let mut writer = BitWriter::default();
+    let mut table = std::collections::HashMap::new();
+    let mut next_code = end + 1;
+    let mut code_size = min_code_size + 1;
+    let mut prefix: Option<u16> = None;
+
+    writer.write(clear, code_size);
+
+    for &pixel in pixels {
+        let Some(current) = prefix else {
+            prefix = Some(pixel as u16);
+            continue;
+        };
+
+        if let Some(&code) = table.get(&(current, pixel)) {
+            prefix = Some(code);
+            continue;
+        }
+
+        writer.write(current, code_size);
+        table.insert((current, pixel), next_code);
+        next_code += 1;
+
+        if next_code == 4096 {
+            writer.write(clear, code_size);
+            table.clear();
+            next_code = end + 1;
+            code_size = min_code_size + 1;
+        } else if next_code > 1 << code_size {
+            code_size += 1;
+        }
+
+        prefix = Some(pixel as u16);
+    }
+
+    if let Some(current) = prefix {
+        writer.write(current, code_size);
+    }
+    writer.write(end, code_size);
+
+    writer.finish()
+}
+
+/// Writes an animated GIF one frame at a time. Frames may be smaller than the screen, in which case
+/// they are drawn from the top left corner over the background.
+pub struct GifEncoder<W: Write> {
+    out: W,
+    width: usize,
+    height: usize,
+    delay: u16,
+    min_code_size: u8,
+}
+
+impl<W: Write> GifEncoder<W> {
+    pub fn new(mut out: W, width: usize, height: usize, colours: &[[u8; 3]], delay: Duration, background: u8) -> io::Result<Self> {
+        if width > 0xffff || height > 0xffff || colours.len() > 256 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "a GIF is at most 65535 pixels a side with 256 colours"));
+        }
+
+        let bits = (1..=8).find(|bits| 1 << bits >= colours.len()).unwrap_or(8);
+        let mut table = colours.concat();
+        table.resize(3 << bits, 0);
+
+        out.write_all(b"GIF89a")?;
+        out.write_all(&(width as u16).to_le_bytes())?;
+        out.write_all(&(height as u16).to_le_bytes())?;
+        out.write_all(&[0x80 | (bits as u8 - 1) << 4 | (bits as u8 - 1), background, 0])?;
+        out.write_all(&table)?;
+        // Loops forever.
+        out.write_all(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00")?;
+
+        let delay = (delay.as_millis() / 10).clamp(2, 0xffff) as u16;
+        Ok(GifEncoder { out, width, height, delay, min_code_size: bits.max(2) as u8 })
+    }
+
+    pub fn add(&mut self, image: &Image) -> io::Result<()> {
+        if image.width > self.width || image.height > self.height {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "the frame is larger than the GIF"));
+        }
+
+        // Graphic control: restore to the background before the next frame, then the delay.
+        self.out.write_all(&[0x21, 0xf9, 4, 2 << 2])?;
+        self.out.write_all(&self.delay.to_le_bytes())?;
+        self.out.write_all(&[0, 0])?;
+
+        self.out.write_all(&[0x2c, 0, 0, 0, 0])?;
+        self.out.write_all(&(image.width as u16).to_le_bytes())?;
+        self.out.write_all(&(image.height as u16).to_le_bytes())?;
+        self.out.write_all(&[0, self.min_code_size])?;
+
+        for block in lzw(&image.pixels, self.min_code_size).chunks(255) {
+            self.out.write_all(&[block.len() as u8])?;
+            self.out.write_all(block)?;
+        }
+        self.out.write_all(&[0])
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        self.out.write_all(&[0x3b])?;
+        self.out.flush()?;
+
+        Ok(self.out)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Format {
+    Ppm,
+    Png,
+    Gif,
+}
+
+impl Format {
+    pub fn from_path(path: &Path) -> Option<Format> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "ppm" => Some(Format::Ppm),
+            "png" => Some(Format::Png),
+            "gif" => Some(Format::Gif),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    Run(RunError),
+    Io(io::Error),
+    NoFrames,
+}
+
+impl Display for ExportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportError::Run(error) => write!(f, "{}", error),
+            ExportError::Io(error) => write!(f, "could not write the image: {}", error),
+            ExportError::NoFrames => write!(f, "there is nothing to draw"),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+pub struct Export {
+    pub palette: Palette,
+    pub cell_size: usize,
+    pub fps: f64,
+    /// Keeps one GIF frame out of this many, and always the last one.
+    pub every: usize,
+}
+
+impl Default for Export {
+    fn default() -> Self {
+        Export { palette: Palette::default(), cell_size: 4, fps: 10.0, every: 1 }
+    }
+}
+
+impl Export {
+    fn frames(&self, day: usize, part: usize, input: &str) -> Result<impl Iterator<Item = Frame>, ExportError> {
+        let every = self.every.max(1);
+        let mut frames = animate::frames(day, part, input).map_err(ExportError::Run)?.enumerate().peekable();
+
+        Ok(std::iter::from_fn(move || loop {
+            let (i, frame) = frames.next()?;
+            if i % every == 0 || frames.peek().is_none() {
+                return Some(frame);
+            }
+        }))
+    }
+
+    /// Draws `day` and `part` to `out`, the last frame for stills and the kept frames for GIFs,
+    /// returning how many frames were drawn.
+    pub fn write(&self, day: usize, part: usize, input: &str, format: Format, mut out: impl Write) -> Result<usize, ExportError> {
+        let render = |frame: &Frame| Image::render(frame, &self.palette, self.cell_size.max(1));
+
+        if format != Format::Gif {
+            let image = render(&self.frames(day, part, input)?.last().ok_or(ExportError::NoFrames)?);
+            let bytes = if format == Format::Ppm { image.to_ppm() } else { image.to_png() };
+
+            return out.write_all(&bytes).map(|_| 1).map_err(ExportError::Io);
+        }
+
+        // Frames can change size, so a first pass finds the screen that fits them all.
+        let (width, height) = self.frames(day, part, input)?
+            .map(|frame| (frame.cells.first().map_or(0, |row| row.len()), frame.cells.len()))
+            .fold((0, 0), |(width, height), (w, h)| (width.max(w), height.max(h)));
+        if width == 0 || height == 0 {
+            return Err(ExportError::NoFrames);
+        }
+
+        let delay = Duration::from_secs_f64(1.0 / self.fps.max(0.01));
+        let cell_size = self.cell_size.max(1);
+        let background = self.palette.index(Cell::Void);
+        let mut encoder = GifEncoder::new(out, width * cell_size, height * cell_size, &self.palette.colours(), delay, background).map_err(ExportError::Io)?;
+
+        let mut drawn = 0;
+        for frame in self.frames(day, part, input)? {
+            encoder.add(&render(&frame)).map_err(ExportError::Io)?;
+            drawn += 1;
+        }
+        encoder.finish().map_err(ExportError::Io)?;
+
+        Ok(drawn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::vector2::Vector2i;
+    use super::*;
+
+    /// Decodes the LZW codes back into pixels, the way a GIF reader does.
+    fn unlzw(bytes: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let bits: Vec<bool> = bytes.iter().flat_map(|byte| (0..8).map(move |bit| byte >> bit & 1 == 1)).collect();
+        let (mut position, mut code_size) = (0, min_code_size as usize + 1);
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let mut previous: Option<Vec<u8>> = None;
+        let mut pixels = Vec::new();
+
+        while position + code_size <= bits.len() {
+            let code = (0..code_size).filter(|bit| bits[position + bit]).fold(0, |code, bit| code | 1 << bit);
+            position += code_size;
+
+            if code == clear {
+                table = (0..clear).map(|index| vec![index as u8]).chain([vec![], vec![]]).collect();
+                code_size = min_code_size as usize + 1;
+                previous = None;
+                continue;
+            }
+            if code == clear + 1 {
+                break;
+            }
+
+            let entry = match (table.get(code), &previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(previous)) => [&previous[..], &previous[..1]].concat(),
+                (None, None) => panic!("code {} before any entry", code),
+            };
+            if let Some(previous) = previous {
+                if table.len() < 4096 {
+                    table.push([&previous[..], &entry[..1]].concat());
+                }
+            }
+            if table.len() == 1 << code_size && code_size < 12 {
+                code_size += 1;
+            }
+
+            pixels.extend(&entry);
+            previous = Some(entry);
+        }
+
+        pixels
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn lzw_round_trips() {
+        let mut seed = 7u32;
+        let noisy: Vec<u8> = (0..20000).map(|_| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (seed >> 16) as u8 % 16
+        }).collect();
+
+        for pixels in [vec![], vec![3], vec![0; 10000], noisy] {
+            assert_eq!(unlzw(&lzw(&pixels, 4), 4), pixels);
+        }
+    }
+
+    #[test]
+    fn renders_and_encodes_frames() {
+        let frame = Frame::from_fn(0, Vector2i{x: 0, y: 0}, Vector2i{x: 1, y: 0}, |pos| if pos.x == 0 { Cell::Sand } else { Cell::Rock });
+        let palette = Palette::default().with_overrides("sand=ff0000, rock=#00ff00").unwrap();
+        let image = Image::render(&frame, &palette, 2);
+
+        assert_eq!((image.width, image.height), (4, 2));
+        assert_eq!(image.pixels, [4, 4, 3, 3, 4, 4, 3, 3]);
+        assert_eq!(&image.to_ppm()[..11], b"P6\n4 2\n255\n");
+        assert_eq!(&image.to_ppm()[11..17], [255, 0, 0, 255, 0, 0]);
+
+        let png = image.to_png();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[png.len() - 12..], b"\0\0\0\0IEND\xae\x42\x60\x82");
+
+        assert!(Palette::default().with_overrides("snow=ffffff").is_err());
+        assert!(Palette::default().with_overrides("sand=fff").is_err());
+    }
+
+    #[test]
+    fn exports_days() {
+        let input = std::fs::read_to_string("src/day14/test_simple.txt").unwrap();
+        let export = Export { every: 10, ..Export::default() };
+
+        let mut gif = Vec::new();
+        assert_eq!(export.write(14, 1, &input, Format::Gif, &mut gif).unwrap(), 4);
+        assert!(gif.starts_with(b"GIF89a") && gif.ends_with(&[0x3b]));
+
+        let mut png = Vec::new();
+        assert_eq!(export.write(14, 2, &input, Format::Png, &mut png).unwrap(), 1);
+        assert!(matches!(export.write(3, 1, "", Format::Png, Vec::new()), Err(ExportError::Run(RunError::NoAnimation(3)))));
+        assert_eq!(Format::from_path(Path::new("out/sand.GIF")), Some(Format::Gif));
+    }
+}
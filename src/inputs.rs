@@ -0,0 +1,70 @@
+//! Puzzle inputs kept outside of the code, one directory per user since everyone gets their own.
+//! An input is looked up as `<root>/<user>/dayNN.txt`, then as the shared `<root>/dayNN.txt`, and
+//! finally as the bundled `<fixtures>/dayN/test_large.txt`.
+
+use std::fs;
+use std::path::PathBuf;
+
+pub struct InputStore {
+    root: PathBuf,
+    fixtures: PathBuf,
+}
+
+impl InputStore {
+    pub fn new(root: impl Into<PathBuf>, fixtures: impl Into<PathBuf>) -> Self {
+        InputStore { root: root.into(), fixtures: fixtures.into() }
+    }
+
+    /// The paths an input is looked up at, in order.
+    pub fn candidates(&self, user: Option<&str>, day: usize) -> Vec<PathBuf> {
+        let file = format!("day{:02}.txt", day);
+        let mut candidates = Vec::new();
+
+        if let Some(user) = user {
+            candidates.push(self.root.join(user).join(&file));
+        }
+        candidates.push(self.root.join(&file));
+        candidates.push(self.fixtures.join(format!("day{}", day)).join("test_large.txt"));
+
+        candidates
+    }
+
+    pub fn resolve(&self, user: Option<&str>, day: usize) -> Option<PathBuf> {
+        self.candidates(user, day).into_iter().find(|path| path.is_file())
+    }
+
+    pub fn read(&self, user: Option<&str>, day: usize) -> Result<(PathBuf, String), String> {
+        let Some(path) = self.resolve(user, day) else {
+            let tried: Vec<_> = self.candidates(user, day).iter().map(|path| path.display().to_string()).collect();
+            return Err(format!("no input for day {}, tried {}", day, tried.join(", ")));
+        };
+
+        let input = fs::read_to_string(&path).map_err(|error| format!("could not read {}: {}", path.display(), error))?;
+        Ok((path, input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn falls_back_from_user_to_shared_to_fixture() {
+        let root = std::env::temp_dir().join(format!("aoc-inputs-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("alex")).unwrap();
+        fs::write(root.join("alex/day01.txt"), "1").unwrap();
+        fs::write(root.join("day01.txt"), "2").unwrap();
+
+        let store = InputStore::new(&root, concat!(env!("CARGO_MANIFEST_DIR"), "/src"));
+        assert_eq!(store.read(Some("alex"), 1).unwrap().1, "1");
+        assert_eq!(store.read(Some("sam"), 1).unwrap().1, "2");
+        assert_eq!(store.read(None, 1).unwrap().1, "2");
+        assert!(store.resolve(Some("alex"), 6).unwrap().ends_with("day6/test_large.txt"));
+
+        let error = store.read(Some("alex"), 2).unwrap_err();
+        assert!(error.starts_with("no input for day 2, tried ") && error.contains("alex/day02.txt"), "{}", error);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
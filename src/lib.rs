@@ -1,6 +1,6 @@
 extern crate core;
 
-mod vector2;
+pub mod vector2;
 mod day1;
 mod day2;
 mod day3;
@@ -20,11 +20,33 @@ mod day16;
 mod day17;
 mod day18;
 mod day19;
-mod vector3;
+pub mod vector3;
 mod day20;
 mod day21;
 mod day22;
 mod day23;
 mod day24;
-mod direction;
-mod day25;
\ No newline at end of file
+pub mod direction;
+mod day25;
+pub mod animate;
+pub mod answers;
+pub mod bench;
+pub mod cache;
+pub mod cleanup;
+pub mod crane;
+pub mod cycle;
+pub mod error;
+pub mod generate;
+pub mod grid;
+pub mod image;
+pub mod inputs;
+pub mod mock_server;
+pub mod observer;
+pub mod parse;
+pub mod reference;
+pub mod runner;
+pub mod search;
+pub mod solution;
+pub mod submit;
+pub mod transcript;
+pub mod vfs;
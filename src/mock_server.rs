@@ -0,0 +1,188 @@
+//! A local stand-in for the puzzle site's answer endpoint, for testing submissions without
+//! sending anything anywhere. It records every submission and answers like the site does.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Submission {
+    pub day: usize,
+    pub part: usize,
+    pub answer: String,
+    pub session: Option<String>,
+}
+
+#[derive(Default)]
+struct State {
+    answers: BTreeMap<(usize, usize), String>,
+    solved: BTreeSet<(usize, usize)>,
+    submissions: Vec<Submission>,
+}
+
+pub struct MockServer {
+    address: SocketAddr,
+    state: Arc<Mutex<State>>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    /// Serves on `address`, which may use port 0 to pick a free one, knowing the correct answer
+    /// for every day and part in `answers`.
+    pub fn start(address: &str, answers: BTreeMap<(usize, usize), String>) -> io::Result<MockServer> {
+        let listener = TcpListener::bind(address)?;
+        let address = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(State { answers, ..State::default() }));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread = {
+            let (state, stop) = (Arc::clone(&state), Arc::clone(&stop));
+
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if stop.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        // A client that hangs up early only loses its own response.
+                        let _ = handle(stream, &state);
+                    }
+                }
+            })
+        };
+
+        Ok(MockServer { address, state, stop, thread: Some(thread) })
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    pub fn submissions(&self) -> Vec<Submission> {
+        self.state.lock().unwrap().submissions.clone()
+    }
+
+    /// Serves until the process exits.
+    pub fn wait(mut self) {
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(thread) = self.thread.take() {
+            self.stop.store(true, Ordering::SeqCst);
+            // The listener only notices the flag on its next connection.
+            let _ = TcpStream::connect(self.address);
+            let _ = thread.join();
+        }
+    }
+}
+
+fn handle(stream: TcpStream, state: &Mutex<State>) -> io::Result<()> {
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    let mut length = 0;
+    let mut session = None;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let Some((name, value)) = line.trim_end().split_once(':') else {
+            break;
+        };
+
+        match name.to_ascii_lowercase().as_str() {
+            "content-length" => length = value.trim().parse().unwrap_or(0),
+            "cookie" => session = value.split(';').find_map(|cookie| cookie.trim().strip_prefix("session=")).map(str::to_string),
+            _ => {}
+        }
+    }
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+
+    let (status, page) = respond(&request_line, &String::from_utf8_lossy(&body), session, &mut state.lock().unwrap());
+    write!(&stream, "HTTP/1.1 {}\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, page.len(), page)
+}
+
+fn respond(request_line: &str, body: &str, session: Option<String>, state: &mut State) -> (&'static str, String) {
+    let path = match request_line.split(' ').collect::<Vec<_>>()[..] {
+        ["POST", path, _] => path,
+        _ => return ("405 Method Not Allowed", "only answers can be posted".to_string()),
+    };
+    let Some(day) = path.strip_suffix("/answer").and_then(|path| path.rsplit_once("/day/")).and_then(|(_, day)| day.parse().ok()) else {
+        return ("404 Not Found", "no such page".to_string());
+    };
+
+    let form: BTreeMap<String, String> = body.split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(key, value)| (decode(key), decode(value)))
+        .collect();
+    let (Some(part), Some(answer)) = (form.get("level").and_then(|level| level.parse().ok()), form.get("answer")) else {
+        return ("400 Bad Request", "expected a level and an answer".to_string());
+    };
+
+    state.submissions.push(Submission { day, part, answer: answer.clone(), session });
+    let Some(expected) = state.answers.get(&(day, part)) else {
+        return ("404 Not Found", "no such puzzle".to_string());
+    };
+
+    let sentence = if state.solved.contains(&(day, part)) {
+        "You don't seem to be solving the right level. Did you already complete it?"
+    } else if answer == expected {
+        state.solved.insert((day, part));
+        "That's the right answer! You are one gold star closer to collecting enough star fruit."
+    } else {
+        match (answer.parse::<i64>(), expected.parse::<i64>()) {
+            (Ok(answer), Ok(expected)) if answer > expected => "That's not the right answer; your answer is too high.",
+            (Ok(_), Ok(_)) => "That's not the right answer; your answer is too low.",
+            _ => "That's not the right answer.",
+        }
+    };
+
+    ("200 OK", format!("<html><body><main><article><p>{}</p></article></main></body></html>", sentence))
+}
+
+/// Decodes a form value, where `+` is a space and `%XX` a byte.
+fn decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let escaped = bytes.get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match (bytes[i], escaped) {
+            (b'+', _) => decoded.push(b' '),
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 2;
+            }
+            (byte, _) => decoded.push(byte),
+        }
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_form_values() {
+        assert_eq!(decode("2%3D-1%3D0"), "2=-1=0");
+        assert_eq!(decode("a+b%2"), "a b%2");
+    }
+}
@@ -0,0 +1,99 @@
+use std::fmt::Display;
+use std::io::{self, Stderr, Write};
+
+const BAR_WIDTH: usize = 30;
+const UNBOUNDED_STEP: usize = 1000;
+
+pub trait Observer {
+    /// `total` is `None` when a solver runs until some condition is met rather than for a fixed number of steps.
+    fn progress(&mut self, _done: usize, _total: Option<usize>) {}
+    fn frame(&mut self, _frame: &dyn Display) {}
+    fn finished(&mut self) {}
+}
+
+pub struct NoOp;
+
+impl Observer for NoOp {}
+
+pub struct ProgressBar<W: Write> {
+    out: W,
+    drawn: Option<usize>,
+}
+
+impl ProgressBar<Stderr> {
+    pub fn stderr() -> Self {
+        ProgressBar::new(io::stderr())
+    }
+}
+
+impl<W: Write> ProgressBar<W> {
+    pub fn new(out: W) -> Self {
+        ProgressBar { out, drawn: None }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+impl<W: Write> Observer for ProgressBar<W> {
+    fn progress(&mut self, done: usize, total: Option<usize>) {
+        // Only redraw when the visible output changes, solvers report progress from their hot loops.
+        let (key, line) = match total {
+            Some(total) => {
+                let percent = (done * 100 / total.max(1)).min(100);
+                let filled = percent * BAR_WIDTH / 100;
+
+                (percent, format!("[{}{}] {:>3}%", "#".repeat(filled), " ".repeat(BAR_WIDTH - filled), percent))
+            }
+            None => (done / UNBOUNDED_STEP, format!("{} steps", done / UNBOUNDED_STEP * UNBOUNDED_STEP)),
+        };
+
+        if self.drawn != Some(key) {
+            self.drawn = Some(key);
+            let _ = write!(self.out, "\r{}", line);
+            let _ = self.out.flush();
+        }
+    }
+
+    fn finished(&mut self) {
+        if self.drawn.take().is_some() {
+            let _ = writeln!(self.out);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_bar_redraws_on_change() {
+        let mut bar = ProgressBar::new(Vec::new());
+
+        for done in 0..=4 {
+            bar.progress(done, Some(4));
+            bar.progress(done, Some(4));
+        }
+        bar.finished();
+        bar.finished();
+
+        let output = String::from_utf8(bar.into_inner()).unwrap();
+        let lines: Vec<_> = output.split('\r').skip(1).collect();
+
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[2], format!("[{}{}]  50%", "#".repeat(15), " ".repeat(15)));
+        assert_eq!(lines[4], format!("[{}] 100%\n", "#".repeat(30)));
+    }
+
+    #[test]
+    fn unbounded_progress_counts_steps() {
+        let mut bar = ProgressBar::new(Vec::new());
+
+        for done in 0..2500 {
+            bar.progress(done, None);
+        }
+
+        assert_eq!(String::from_utf8(bar.into_inner()).unwrap(), "\r0 steps\r1000 steps\r2000 steps");
+    }
+}
@@ -0,0 +1,188 @@
+//! Helpers shared by the day parsers. Each takes the whole `source` being parsed next to the part
+//! of it to look at, so that errors point at the right line and column of the input.
+
+use std::str::FromStr;
+use crate::error::{parse_number, ParseError};
+
+/// Blocks of lines separated by blank lines, skipping empty blocks.
+pub fn blocks(text: &str) -> impl Iterator<Item = &str> {
+    text.split("\n\n")
+        .map(|block| block.trim_matches('\n'))
+        .filter(|block| !block.is_empty())
+}
+
+/// Splits `text` at its first blank line, for inputs made of two differently formatted parts.
+pub fn split_blocks<'a>(day: usize, source: &str, text: &'a str, message: &str) -> Result<(&'a str, &'a str), ParseError> {
+    match text.split_once("\n\n") {
+        Some(parts) => Ok(parts),
+        None => Err(ParseError::new(day, source, &text[text.len()..], message)),
+    }
+}
+
+/// Every integer in `text`, ignoring whatever is around them. A minus sign only counts when it
+/// does not follow a letter or digit, so `2-4` is two positive numbers but `x=-2` is negative.
+pub fn integers<T: FromStr>(day: usize, source: &str, text: &str) -> Result<Vec<T>, ParseError> {
+    let bytes = text.as_bytes();
+    let mut numbers = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let negative = i > 0 && bytes[i - 1] == b'-' && (i == 1 || !bytes[i - 2].is_ascii_alphanumeric());
+        let start = if negative { i - 1 } else { i };
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+
+        numbers.push(parse_number(day, source, &text[start..i])?);
+    }
+
+    Ok(numbers)
+}
+
+/// Exactly `N` integers in `text`, see [integers].
+pub fn integers_n<T: FromStr, const N: usize>(day: usize, source: &str, text: &str, message: &str) -> Result<[T; N], ParseError> {
+    integers(day, source, text)?
+        .try_into()
+        .map_err(|_| ParseError::new(day, source, text, message))
+}
+
+/// The rest of `text` after `prefix`.
+pub fn prefixed<'a>(day: usize, source: &str, text: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+    text.strip_prefix(prefix)
+        .ok_or_else(|| ParseError::new(day, source, text, format!("expected '{}'", prefix.trim())))
+}
+
+/// Splits a `key: value` line at the first `separator`, trimming both sides.
+pub fn key_value<'a>(day: usize, source: &str, line: &'a str, separator: &str) -> Result<(&'a str, &'a str), ParseError> {
+    match line.split_once(separator) {
+        Some((key, value)) if !key.trim().is_empty() => Ok((key.trim(), value.trim())),
+        _ => Err(ParseError::new(day, source, line, format!("expected 'key{} value'", separator.trim_end()))),
+    }
+}
+
+/// The values in a sentence that follows `template`, where every `{}` stands for a value:
+/// `"move 1 from 2 to 3"` with `"move {} from {} to {}"` gives `["1", "2", "3"]`.
+/// A value stops at the first occurrence of the text following it in the template.
+pub fn fields<'a, const N: usize>(day: usize, source: &str, line: &'a str, template: &str) -> Result<[&'a str; N], ParseError> {
+    let literals: Vec<_> = template.split("{}").collect();
+    assert_eq!(literals.len(), N + 1, "'{}' should have {} placeholders", template, N);
+
+    let mut rest = prefixed(day, source, line, literals[0])?;
+    let mut values = [""; N];
+
+    for (value, literal) in values.iter_mut().zip(&literals[1..]) {
+        let end = match literal.is_empty() {
+            true => rest.len(),
+            false => rest.find(literal)
+                .ok_or_else(|| ParseError::new(day, source, &rest[rest.len()..], format!("expected '{}'", literal.trim())))?,
+        };
+
+        *value = &rest[..end];
+        rest = &rest[end + literal.len()..];
+    }
+
+    match rest.is_empty() {
+        true => Ok(values),
+        false => Err(ParseError::new(day, source, rest, "unexpected text")),
+    }
+}
+
+/// The trimmed items of a comma separated list.
+pub fn comma_list(text: &str) -> impl Iterator<Item = &str> {
+    text.split(',').map(str::trim)
+}
+
+pub fn comma_numbers<T: FromStr>(day: usize, source: &str, text: &str) -> Result<Vec<T>, ParseError> {
+    comma_list(text).map(|item| parse_number(day, source, item)).collect()
+}
+
+/// Converts every character of `text`, failing at the first one `cell` rejects.
+pub fn chars<T, M: Into<String>>(day: usize, source: &str, text: &str, mut cell: impl FnMut(char) -> Result<T, M>) -> Result<Vec<T>, ParseError> {
+    text.char_indices()
+        .map(|(i, char)| cell(char).map_err(|message| ParseError::new(day, source, &text[i..i + char.len_utf8()], message)))
+        .collect()
+}
+
+/// Rows of converted characters, see [chars].
+pub fn char_grid<T, M: Into<String>>(day: usize, source: &str, text: &str, mut cell: impl FnMut(char) -> Result<T, M>) -> Result<Vec<Vec<T>>, ParseError> {
+    text.lines().map(|line| chars(day, source, line, &mut cell)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_integers() {
+        let source = "Sensor at x=2, y=-18: beacon at 4-7, x=-0";
+
+        assert_eq!(integers::<i32>(1, source, source), Ok(vec![2, -18, 4, 7, 0]));
+        assert_eq!(integers_n::<i32, 2>(1, source, &source[10..21], "expected two numbers"), Ok([2, -18]));
+
+        let error = integers_n::<i32, 3>(1, source, &source[10..21], "expected three numbers").unwrap_err();
+        assert_eq!((error.column, error.message.as_str()), (11, "expected three numbers"));
+
+        let overflowing = "a\n\nb 300";
+        let error = integers::<u8>(1, overflowing, overflowing).unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (3, 3, "300"));
+    }
+
+    #[test]
+    fn splits_blocks() {
+        let source = "1\n2\n\n3\n\n\n\n4\n";
+
+        assert_eq!(blocks(source).collect::<Vec<_>>(), vec!["1\n2", "3", "4"]);
+        assert_eq!(split_blocks(1, source, source, "expected a blank line"), Ok(("1\n2", "3\n\n\n\n4\n")));
+
+        let error = split_blocks(1, source, &source[..3], "expected a blank line").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+    }
+
+    #[test]
+    fn matches_sentences() {
+        let source = "move 1 from 2 to 3\nmove 1 to 3";
+        let (first, second) = source.split_once('\n').unwrap();
+
+        assert_eq!(fields(5, source, first, "move {} from {} to {}"), Ok(["1", "2", "3"]));
+        assert_eq!(fields(5, source, "a=1, b=2", "a={}, b={}"), Ok(["1", "2"]));
+
+        let error = fields::<3>(5, source, second, "move {} from {} to {}").unwrap_err();
+        assert_eq!((error.line, error.column, error.message.as_str()), (2, 12, "expected 'from'"));
+
+        let error = fields::<1>(5, source, first, "move {} from").unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (12, " 2 to 3"));
+    }
+
+    #[test]
+    fn splits_keys_and_lists() {
+        let source = "root: pppw + sjmn\n  Starting items: 79, 98\nno separator";
+        let lines: Vec<_> = source.lines().collect();
+
+        assert_eq!(key_value(21, source, lines[0], ":"), Ok(("root", "pppw + sjmn")));
+        assert_eq!(key_value(11, source, lines[1], ":"), Ok(("Starting items", "79, 98")));
+        assert_eq!(key_value(11, source, lines[2], ":").unwrap_err().line, 3);
+        assert_eq!(comma_numbers::<i64>(11, source, "79, 98"), Ok(vec![79, 98]));
+        assert_eq!(comma_list("DD, II,BB").collect::<Vec<_>>(), vec!["DD", "II", "BB"]);
+        assert_eq!(prefixed(11, source, lines[1].trim(), "Starting items: "), Ok("79, 98"));
+    }
+
+    #[test]
+    fn converts_characters() {
+        let source = "<>\n<x";
+        let jet = |char| match char {
+            '<' => Ok(-1),
+            '>' => Ok(1),
+            _ => Err("expected < or >"),
+        };
+
+        assert_eq!(chars(17, source, "<><", jet), Ok(vec![-1, 1, -1]));
+
+        let error = char_grid(17, source, source, jet).unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 2, "x"));
+    }
+}
@@ -0,0 +1,229 @@
+//! Deliberately naive solvers, slow but simple enough to trust, for checking the real solvers
+//! against on small generated inputs. They assume valid input and panic otherwise.
+
+use std::collections::{HashMap, HashSet};
+use crate::parse;
+
+/// The reference answer for `day` and `part`, or `None` if there is no reference solver for it.
+pub fn solve(day: usize, part: usize, input: &str) -> Option<String> {
+    let answer = match (day, part) {
+        (15, 1) => covered_on_row(input, 2000000).to_string(),
+        (16, 1) => most_pressure(input, 30, false).to_string(),
+        (16, 2) => most_pressure(input, 26, true).to_string(),
+        (19, 1) => blueprints(input).iter().map(|(id, costs)| id * max_geodes(costs, 24)).sum::<usize>().to_string(),
+        (19, 2) => blueprints(input).iter().take(3).map(|(_, costs)| max_geodes(costs, 32)).product::<usize>().to_string(),
+        (20, 1) => grove_coordinates(input, 1, 1).to_string(),
+        (20, 2) => grove_coordinates(input, 811589153, 10).to_string(),
+        _ => return None,
+    };
+
+    Some(answer)
+}
+
+fn numbers(line: &str) -> Vec<i64> {
+    parse::integers(0, line, line).unwrap()
+}
+
+/// A sensor, its closest beacon and the distance between them.
+type Sensor = ((i64, i64), (i64, i64), i64);
+
+fn sensors(input: &str) -> Vec<Sensor> {
+    input.lines()
+        .map(|line| match numbers(line)[..] {
+            [x, y, beacon_x, beacon_y] => ((x, y), (beacon_x, beacon_y), (x - beacon_x).abs() + (y - beacon_y).abs()),
+            _ => panic!("expected a sensor and a beacon in '{}'", line),
+        })
+        .collect()
+}
+
+fn covered(sensors: &[Sensor], (x, y): (i64, i64)) -> bool {
+    sensors.iter().any(|((sensor_x, sensor_y), _, range)| (sensor_x - x).abs() + (sensor_y - y).abs() <= *range)
+}
+
+/// Positions on `row` that cannot hold a beacon, checked one at a time.
+pub fn covered_on_row(input: &str, row: i64) -> usize {
+    let sensors = sensors(input);
+    let left = sensors.iter().map(|((x, _), _, range)| x - range).min().unwrap_or(0);
+    let right = sensors.iter().map(|((x, _), _, range)| x + range).max().unwrap_or(0);
+
+    (left..=right)
+        .filter(|x| covered(&sensors, (*x, row)) && !sensors.iter().any(|(_, beacon, _)| *beacon == (*x, row)))
+        .count()
+}
+
+/// The first position in the square from 0 to `size` that no sensor covers, trying every one.
+pub fn tuning_frequency(input: &str, size: i64) -> Option<i64> {
+    let sensors = sensors(input);
+
+    (0..=size)
+        .flat_map(|x| (0..=size).map(move |y| (x, y)))
+        .find(|position| !covered(&sensors, *position))
+        .map(|(x, y)| x * 4000000 + y)
+}
+
+/// Tries every move and every valve to open, minute by minute, remembering what was already tried.
+/// With an elephant, every split of the valves between the two of them is tried.
+fn most_pressure(input: &str, minutes: usize, elephant: bool) -> usize {
+    let mut names = Vec::new();
+    let mut valves = Vec::new();
+    for line in input.lines() {
+        let (valve, tunnels) = line.split_once(';').unwrap();
+        let tunnels: Vec<String> = tunnels.split([' ', ',']).filter(|name| name.len() == 2 && name.chars().all(|c| c.is_ascii_uppercase())).map(String::from).collect();
+
+        names.push(valve[6..8].to_string());
+        valves.push((numbers(valve)[0] as usize, tunnels));
+    }
+
+    let index = |name: &str| names.iter().position(|other| other == name).unwrap();
+    let graph: Vec<(usize, Vec<usize>)> = valves.iter().map(|(flow_rate, tunnels)| (*flow_rate, tunnels.iter().map(|name| index(name)).collect())).collect();
+    let useful: Vec<usize> = (0..graph.len()).filter(|valve| graph[*valve].0 > 0).collect();
+    let start = index("AA");
+
+    let best = |allowed: u64| {
+        let mut seen = HashMap::new();
+        pressure(&graph, allowed, start, minutes, 0, &mut seen)
+    };
+
+    let all: u64 = useful.iter().map(|valve| 1 << valve).sum();
+    if !elephant {
+        return best(all);
+    }
+
+    (0..1u64 << useful.len())
+        .map(|split| useful.iter().enumerate().filter(|(bit, _)| split & (1 << bit) != 0).map(|(_, valve)| 1 << valve).sum::<u64>())
+        .map(|mine| best(mine) + best(all & !mine))
+        .max()
+        .unwrap()
+}
+
+fn pressure(graph: &[(usize, Vec<usize>)], allowed: u64, valve: usize, minutes: usize, open: u64, seen: &mut HashMap<(usize, usize, u64), usize>) -> usize {
+    if minutes == 0 {
+        return 0;
+    }
+    if let Some(pressure) = seen.get(&(valve, minutes, open)) {
+        return *pressure;
+    }
+
+    let mut best = graph[valve].1.iter()
+        .map(|next| pressure(graph, allowed, *next, minutes - 1, open, seen))
+        .max()
+        .unwrap_or(0);
+
+    if allowed & !open & (1 << valve) != 0 {
+        let released = graph[valve].0 * (minutes - 1);
+        best = best.max(released + pressure(graph, allowed, valve, minutes - 1, open | 1 << valve, seen));
+    }
+
+    seen.insert((valve, minutes, open), best);
+    best
+}
+
+/// Robot costs as ore for ore, clay, obsidian and geode robots, plus clay for obsidian robots and
+/// obsidian for geode robots.
+fn blueprints(input: &str) -> Vec<(usize, [usize; 6])> {
+    input.lines()
+        .map(|line| match numbers(line)[..] {
+            [id, ore, clay, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian] =>
+                (id as usize, [ore, clay, obsidian_ore, geode_ore, obsidian_clay, geode_obsidian].map(|cost| cost as usize)),
+            _ => panic!("expected an id and six costs in '{}'", line),
+        })
+        .collect()
+}
+
+/// Every choice of robot to build, minute by minute. It only leaves out states that cannot matter:
+/// more robots of a kind than can be spent in a minute, resources beyond what can still be spent,
+/// and states that cannot catch up with another one.
+pub fn max_geodes(costs: &[usize; 6], minutes: usize) -> usize {
+    let [ore, clay, obsidian_ore, geode_ore, obsidian_clay, geode_obsidian] = *costs;
+    let robot_costs = [[ore, 0, 0], [clay, 0, 0], [obsidian_ore, obsidian_clay, 0], [geode_ore, 0, geode_obsidian]];
+    let most_needed = [ore.max(clay).max(obsidian_ore).max(geode_ore), obsidian_clay, geode_obsidian];
+
+    let mut states = HashSet::from([([1, 0, 0, 0], [0, 0, 0, 0])]);
+    for minute in 0..minutes {
+        let left = minutes - minute - 1;
+        let mut next = HashSet::new();
+
+        for (robots, resources) in states {
+            let produce = |robots: [usize; 4], mut resources: [usize; 4], built: [usize; 4]| {
+                for kind in 0..4 {
+                    resources[kind] += built[kind];
+                    if kind < 3 {
+                        resources[kind] = resources[kind].min(most_needed[kind] * left);
+                    }
+                }
+                (robots, resources)
+            };
+
+            next.insert(produce(robots, resources, robots));
+
+            for (kind, cost) in robot_costs.iter().enumerate() {
+                let affordable = (0..3).all(|resource| resources[resource] >= cost[resource]);
+                if !affordable || (kind < 3 && robots[kind] >= most_needed[kind]) {
+                    continue;
+                }
+
+                let mut paid = resources;
+                (0..3).for_each(|resource| paid[resource] -= cost[resource]);
+                let mut more = robots;
+                more[kind] += 1;
+
+                next.insert(produce(more, paid, robots));
+            }
+        }
+
+        // Even a geode robot every remaining minute cannot catch up with the best guaranteed count.
+        let guaranteed = |(robots, resources): &([usize; 4], [usize; 4])| resources[3] + robots[3] * left;
+        let best = next.iter().map(guaranteed).max().unwrap_or(0);
+        next.retain(|state| guaranteed(state) + left * left.saturating_sub(1) / 2 >= best);
+
+        states = next;
+    }
+
+    states.into_iter().map(|(_, resources)| resources[3]).max().unwrap_or(0)
+}
+
+/// Mixes by swapping every number one place at a time, as many times as it has to move.
+fn grove_coordinates(input: &str, key: i64, rounds: usize) -> i64 {
+    let mut list: Vec<(usize, i64)> = input.lines().map(|line| line.trim().parse::<i64>().unwrap() * key).enumerate().collect();
+    let length = list.len();
+
+    for _ in 0..rounds {
+        for original in 0..length {
+            let mut position = list.iter().position(|(index, _)| *index == original).unwrap();
+            let value = list[position].1;
+            // Moving past every other number once puts a number back where it was.
+            let steps = value.rem_euclid(length as i64 - 1) as usize;
+
+            for _ in 0..steps {
+                let next = (position + 1) % length;
+                list.swap(position, next);
+                position = next;
+            }
+        }
+    }
+
+    let zero = list.iter().position(|(_, value)| *value == 0).unwrap();
+    [1000, 2000, 3000].iter().map(|offset| list[(zero + offset) % length].1).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solves_examples() {
+        let sensors = include_str!("day15/test_simple.txt");
+        assert_eq!(covered_on_row(sensors, 10), 26);
+        assert_eq!(tuning_frequency(sensors, 20), Some(56000011));
+
+        let valves = include_str!("day16/test_simple.txt");
+        assert_eq!(solve(16, 1, valves).as_deref(), Some("1651"));
+        assert_eq!(solve(16, 2, valves).as_deref(), Some("1707"));
+
+        assert_eq!(solve(19, 1, include_str!("day19/test_simple.txt")).as_deref(), Some("33"));
+
+        let file = include_str!("day20/test_simple.txt");
+        assert_eq!(solve(20, 1, file).as_deref(), Some("3"));
+        assert_eq!(solve(20, 2, file).as_deref(), Some("1623178306"));
+    }
+}
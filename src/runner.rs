@@ -0,0 +1,331 @@
+use std::any::Any;
+use std::fmt::{Display, Formatter};
+use std::panic;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::bench::format_nanos;
+use crate::cache::Cache;
+use crate::error::{ParseError, SolveError};
+use crate::observer::{NoOp, Observer};
+use crate::solution::get_solver;
+
+#[derive(Debug, PartialEq)]
+pub enum RunError {
+    UnknownDay(usize),
+    UnknownPart(usize, usize),
+    NoAnimation(usize),
+    Parse(ParseError),
+    Solve(SolveError),
+}
+
+impl Display for RunError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RunError::UnknownDay(day) => write!(f, "there is no solution for day {}", day),
+            RunError::UnknownPart(day, part) => write!(f, "day {} has no part {}", day, part),
+            RunError::NoAnimation(day) => write!(f, "day {} has no frames to draw", day),
+            RunError::Parse(error) => write!(f, "invalid input: {}", error),
+            RunError::Solve(error) => write!(f, "no answer: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for RunError {}
+
+pub fn solve(day: usize, part: usize, input: &str) -> Result<String, RunError> {
+    solve_observed(day, part, input, &mut NoOp)
+}
+
+pub fn solve_observed(day: usize, part: usize, input: &str, observer: &mut dyn Observer) -> Result<String, RunError> {
+    get_solver(day)
+        .ok_or(RunError::UnknownDay(day))?
+        .solve(part, input, observer)
+}
+
+type SolveFn = fn(usize, usize, &str) -> Result<String, RunError>;
+
+/// One day and part to solve as part of a batch.
+pub struct Job {
+    pub day: usize,
+    pub part: usize,
+    pub input: Arc<str>,
+    pub expected: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Answer(String),
+    Error(RunError),
+    Panic(String),
+    Timeout,
+    /// The job could not be made, for the given reason, such as a fixture that does not exist.
+    NoInput(String),
+}
+
+#[derive(Debug)]
+pub struct JobReport {
+    pub day: usize,
+    pub part: usize,
+    pub outcome: Outcome,
+    pub elapsed: Duration,
+    pub expected: Option<String>,
+    /// Whether the answer and time come from the cache rather than from solving.
+    pub cached: bool,
+}
+
+impl JobReport {
+    /// The report of a job that could not be made because its input is missing.
+    pub fn no_input(day: usize, part: usize, reason: impl Into<String>) -> JobReport {
+        JobReport { day, part, outcome: Outcome::NoInput(reason.into()), elapsed: Duration::ZERO, expected: None, cached: false }
+    }
+
+    /// `None` when the answer is not known, so there is nothing to compare against.
+    pub fn is_correct(&self) -> Option<bool> {
+        match (&self.outcome, &self.expected) {
+            (Outcome::Answer(answer), Some(expected)) => Some(answer == expected),
+            (Outcome::Answer(_), None) | (Outcome::NoInput(_), _) => None,
+            _ => Some(false),
+        }
+    }
+
+    fn status(&self) -> &'static str {
+        match (&self.outcome, self.is_correct()) {
+            (Outcome::Answer(_), Some(true)) => "pass",
+            (Outcome::Answer(_), Some(false)) => "FAIL",
+            (Outcome::Answer(_), None) => "unknown",
+            (Outcome::Error(_), _) => "ERROR",
+            (Outcome::Panic(_), _) => "PANIC",
+            (Outcome::Timeout, _) => "TIMEOUT",
+            (Outcome::NoInput(_), _) => "missing",
+        }
+    }
+
+    fn details(&self) -> String {
+        match &self.outcome {
+            Outcome::Answer(answer) => {
+                let answer = match answer.lines().count() {
+                    0 | 1 => answer.clone(),
+                    lines => format!("({} lines)", lines),
+                };
+
+                let answer = match (&self.expected, self.is_correct()) {
+                    (Some(expected), Some(false)) => format!("{}, expected {}", answer, expected.replace('\n', " ")),
+                    _ => answer,
+                };
+
+                match self.cached {
+                    true => format!("{} (cached)", answer),
+                    false => answer,
+                }
+            }
+            Outcome::Error(error) => error.to_string(),
+            Outcome::NoInput(reason) => reason.clone(),
+            Outcome::Panic(message) => message.clone(),
+            Outcome::Timeout => format!("gave up after {}", format_nanos(self.elapsed.as_nanos() as u64)),
+        }
+    }
+}
+
+/// Solves every job on `threads` threads, returning the reports in the order of the jobs.
+pub fn solve_all(jobs: Vec<Job>, threads: usize, timeout: Duration) -> Vec<JobReport> {
+    solve_all_with(jobs, threads, timeout, solve)
+}
+
+/// Like [solve_all], answering each job with `solve`.
+fn solve_all_with(jobs: Vec<Job>, threads: usize, timeout: Duration, solve: SolveFn) -> Vec<JobReport> {
+    let queue = Mutex::new(jobs.into_iter().enumerate());
+    let reports = Mutex::new(Vec::new());
+
+    thread::scope(|scope| {
+        for _ in 0..threads.max(1) {
+            scope.spawn(|| loop {
+                let Some((index, job)) = queue.lock().unwrap().next() else {
+                    break;
+                };
+                let report = run_job(job, timeout, solve);
+
+                reports.lock().unwrap().push((index, report));
+            });
+        }
+    });
+
+    let mut reports = reports.into_inner().unwrap();
+    reports.sort_by_key(|(index, _)| *index);
+    reports.into_iter().map(|(_, report)| report).collect()
+}
+
+/// Like [solve_all], but answers jobs from `cache` where it can and stores every new answer in it.
+pub fn solve_all_cached(jobs: Vec<Job>, threads: usize, timeout: Duration, cache: &Cache) -> Vec<JobReport> {
+    let mut reports = Vec::new();
+    let mut unsolved = Vec::new();
+
+    for job in jobs {
+        match cache.get(job.day, job.part, &job.input) {
+            Some(entry) => reports.push(Some(JobReport {
+                day: job.day,
+                part: job.part,
+                outcome: Outcome::Answer(entry.answer.clone()),
+                elapsed: entry.elapsed(),
+                expected: job.expected,
+                cached: true,
+            })),
+            None => {
+                reports.push(None);
+                unsolved.push(job);
+            }
+        }
+    }
+
+    let inputs: Vec<Arc<str>> = unsolved.iter().map(|job| Arc::clone(&job.input)).collect();
+    let mut solved = solve_all(unsolved, threads, timeout).into_iter().zip(inputs).map(|(report, input)| {
+        // A cache that cannot be written to only costs time on the next run.
+        if let Outcome::Answer(answer) = &report.outcome {
+            let _ = cache.put(report.day, report.part, &input, answer, report.elapsed);
+        }
+        report
+    });
+
+    reports.into_iter().map(|report| report.unwrap_or_else(|| solved.next().unwrap())).collect()
+}
+
+fn run_job(job: Job, timeout: Duration, solve: SolveFn) -> JobReport {
+    let (sender, receiver) = mpsc::channel();
+    let (day, part, input) = (job.day, job.part, job.input);
+    let start = Instant::now();
+
+    // Threads cannot be killed, a solver that times out keeps running in the background until it finishes or the process exits.
+    thread::spawn(move || {
+        let _ = sender.send(panic::catch_unwind(|| solve(day, part, &input)));
+    });
+
+    let outcome = match receiver.recv_timeout(timeout) {
+        Ok(Ok(Ok(answer))) => Outcome::Answer(answer),
+        Ok(Ok(Err(error))) => Outcome::Error(error),
+        Ok(Err(payload)) => Outcome::Panic(panic_message(payload)),
+        Err(RecvTimeoutError::Timeout) => Outcome::Timeout,
+        Err(RecvTimeoutError::Disconnected) => Outcome::Panic("solver thread exited".to_string()),
+    };
+
+    JobReport { day, part, outcome, elapsed: start.elapsed(), expected: job.expected, cached: false }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload.downcast_ref::<&str>().map_or("panicked".to_string(), |message| message.to_string()),
+    }
+}
+
+pub fn summary(reports: &[JobReport]) -> String {
+    let mut table = format!("{:>3}  {:>4}  {:>10}  {:<7}  {}", "day", "part", "time", "status", "answer");
+
+    for report in reports {
+        table += &format!(
+            "\n{:>3}  {:>4}  {:>10}  {:<7}  {}",
+            report.day, report.part, format_nanos(report.elapsed.as_nanos() as u64), report.status(), report.details()
+        );
+    }
+
+    let count = |status: &str| reports.iter().filter(|report| report.status() == status).count();
+    table += &format!(
+        "\n\n{} passed, {} failed, {} unknown, {} errors, {} panicked, {} timed out, {} missing",
+        count("pass"), count("FAIL"), count("unknown"), count("ERROR"), count("PANIC"), count("TIMEOUT"), count("missing")
+    );
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solves_simple_inputs() {
+        assert_eq!(solve(1, 2, "1000\n\n2000\n\n3000\n\n4000"), Ok("9000".to_string()));
+        assert_eq!(solve(6, 1, include_str!("day6/test_large.txt")), Ok("1757".to_string()));
+        assert_eq!(solve(7, 1, include_str!("day7/test_simple.txt").trim_end()), Ok("95437".to_string()));
+        assert_eq!(solve(25, 1, include_str!("day25/test_simple.txt")), Ok("2=-1=0".to_string()));
+    }
+
+    #[test]
+    fn unknown_day_or_part() {
+        assert_eq!(solve(26, 1, ""), Err(RunError::UnknownDay(26)));
+        assert_eq!(solve(25, 2, ""), Err(RunError::UnknownPart(25, 2)));
+        assert_eq!(solve(18, 3, ""), Err(RunError::UnknownPart(18, 3)));
+    }
+
+    #[test]
+    fn malformed_input_is_reported() {
+        let error = solve(10, 1, "noop\naddx 3\njump 4").unwrap_err();
+
+        assert_eq!(error.to_string(), "invalid input: day 10, line 3, column 1: unknown instruction (found \"jump\")");
+    }
+
+    fn job(day: usize, part: usize, input: &str, expected: Option<&str>) -> Job {
+        Job { day, part, input: input.into(), expected: expected.map(str::to_string) }
+    }
+
+    /// Panics or takes its time when the input asks it to, and solves any other input.
+    fn staged_solve(day: usize, part: usize, input: &str) -> Result<String, RunError> {
+        match input {
+            "panic" => panic!("asked to panic"),
+            "sleep" => {
+                thread::sleep(Duration::from_millis(100));
+                Ok("slept".to_string())
+            }
+            _ => solve(day, part, input),
+        }
+    }
+
+    #[test]
+    fn solves_jobs_in_parallel() {
+        let jobs = vec![
+            job(6, 1, include_str!("day6/test_large.txt"), Some("1757")),
+            job(6, 2, include_str!("day6/test_large.txt"), Some("1")),
+            job(25, 1, include_str!("day25/test_simple.txt"), None),
+            job(26, 1, "", None),
+            job(0, 1, "panic", None),
+            job(17, 2, include_str!("day17/test_large.txt"), None),
+        ];
+        let reports = solve_all_with(jobs, 3, Duration::from_secs(60), staged_solve);
+
+        assert_eq!(reports.iter().map(|report| (report.day, report.part)).collect::<Vec<_>>(), vec![(6, 1), (6, 2), (25, 1), (26, 1), (0, 1), (17, 2)]);
+        assert_eq!(reports.iter().map(JobReport::is_correct).collect::<Vec<_>>(), vec![Some(true), Some(false), None, Some(false), Some(false), None]);
+        assert_eq!(reports[3].outcome, Outcome::Error(RunError::UnknownDay(26)));
+        assert_eq!(reports[4].outcome, Outcome::Panic("asked to panic".to_string()));
+        assert!(summary(&reports).ends_with("1 passed, 1 failed, 2 unknown, 1 errors, 1 panicked, 0 timed out, 0 missing"));
+    }
+
+    #[test]
+    fn missing_inputs_are_listed() {
+        let reports = [JobReport::no_input(1, 1, "no fixture at src/day1/test_large.txt")];
+
+        assert_eq!(reports[0].is_correct(), None);
+        assert!(summary(&reports).contains("  missing  no fixture at src/day1/test_large.txt"));
+        assert!(summary(&reports).ends_with("0 passed, 0 failed, 0 unknown, 0 errors, 0 panicked, 0 timed out, 1 missing"));
+    }
+
+    #[test]
+    fn reuses_cached_answers() {
+        let cache = Cache::new(std::env::temp_dir().join(format!("aoc-runner-cache-{}", std::process::id())));
+        let input = include_str!("day6/test_large.txt");
+        cache.clear(None).unwrap();
+        cache.put(6, 2, input, "1", Duration::from_secs(3)).unwrap();
+
+        let reports = solve_all_cached(vec![job(6, 1, input, Some("1757")), job(6, 2, input, None)], 1, Duration::from_secs(60), &cache);
+        assert_eq!((reports[0].cached, reports[1].cached), (false, true));
+        assert_eq!((&reports[1].outcome, reports[1].elapsed), (&Outcome::Answer("1".to_string()), Duration::from_secs(3)));
+        assert_eq!(cache.get(6, 1, input).map(|entry| entry.answer), Some("1757".to_string()));
+
+        cache.clear(None).unwrap();
+    }
+
+    #[test]
+    fn slow_jobs_time_out() {
+        let reports = solve_all_with(vec![job(0, 1, "sleep", None)], 1, Duration::ZERO, staged_solve);
+
+        assert_eq!(reports[0].outcome, Outcome::Timeout);
+    }
+}
@@ -0,0 +1,177 @@
+use std::cmp::Reverse;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use priority_queue::PriorityQueue;
+use crate::vector2::Num;
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct SearchResult<S, C> {
+    pub cost: C,
+    pub path: Vec<S>,
+}
+
+fn reconstruct_path<S: Clone>(goal: S, mut parent: impl FnMut(&S) -> Option<S>) -> Vec<S> {
+    let mut path = vec![goal];
+
+    while let Some(previous) = parent(path.last().unwrap()) {
+        path.push(previous);
+    }
+
+    path.reverse();
+    path
+}
+
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<SearchResult<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut parents: HashMap<S, Option<S>> = HashMap::new();
+    let mut frontier = VecDeque::new();
+
+    for start in starts {
+        if !parents.contains_key(&start) {
+            parents.insert(start.clone(), None);
+            frontier.push_back(start);
+        }
+    }
+
+    while let Some(state) = frontier.pop_front() {
+        if is_goal(&state) {
+            let path = reconstruct_path(state, |state| parents[state].clone());
+
+            return Some(SearchResult { cost: path.len() - 1, path });
+        }
+
+        for next in successors(&state) {
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some(state.clone()));
+                frontier.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Step counts from `start` to every state reachable from it, including `start` itself.
+pub fn bfs_distances<S, I>(start: S, mut successors: impl FnMut(&S) -> I) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut frontier = VecDeque::from([start]);
+
+    while let Some(state) = frontier.pop_front() {
+        let distance = distances[&state] + 1;
+
+        for next in successors(&state) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance);
+                frontier.push_back(next);
+            }
+        }
+    }
+
+    distances
+}
+
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<SearchResult<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Num,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, successors, |_| C::ZERO, is_goal)
+}
+
+/// `heuristic` must never overestimate the remaining cost, otherwise the returned path may not be the cheapest.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<SearchResult<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Num,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut best: HashMap<S, (C, Option<S>)> = HashMap::new();
+    let mut frontier = PriorityQueue::new();
+
+    for start in starts {
+        best.insert(start.clone(), (C::ZERO, None));
+        frontier.push(start.clone(), Reverse(heuristic(&start)));
+    }
+
+    while let Some((state, _)) = frontier.pop() {
+        let cost = best[&state].0;
+
+        if is_goal(&state) {
+            let path = reconstruct_path(state, |state| best[state].1.clone());
+
+            return Some(SearchResult { cost, path });
+        }
+
+        for (next, step_cost) in successors(&state) {
+            let next_cost = cost + step_cost;
+
+            if best.get(&next).is_none_or(|(known_cost, _)| next_cost < *known_cost) {
+                frontier.push(next.clone(), Reverse(next_cost + heuristic(&next)));
+                best.insert(next, (next_cost, Some(state.clone())));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 3 is the shortest path by steps, 0 -> 2 -> 3 the cheapest by cost.
+    fn edges(node: &u32) -> Vec<(u32, u32)> {
+        match node {
+            0 => vec![(1, 5), (2, 1)],
+            1 => vec![(3, 1)],
+            2 => vec![(4, 1)],
+            4 => vec![(3, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn bfs_finds_fewest_steps() {
+        let result = bfs([0], |node| edges(node).into_iter().map(|(next, _)| next), |node| *node == 3);
+
+        assert_eq!(result, Some(SearchResult { cost: 2, path: vec![0, 1, 3] }));
+        assert_eq!(bfs([0], |node| edges(node).into_iter().map(|(next, _)| next), |node| *node == 5), None);
+        assert_eq!(bfs_distances(0, |node| edges(node).into_iter().map(|(next, _)| next))[&4], 2);
+    }
+
+    #[test]
+    fn dijkstra_finds_cheapest_path() {
+        let result = dijkstra([0], edges, |node| *node == 3);
+
+        assert_eq!(result, Some(SearchResult { cost: 3, path: vec![0, 2, 4, 3] }));
+    }
+
+    #[test]
+    fn astar_agrees_with_dijkstra() {
+        let heuristic = |node: &u32| if *node == 3 { 0 } else { 1 };
+
+        assert_eq!(astar([0], edges, heuristic, |node| *node == 3), dijkstra([0], edges, |node| *node == 3));
+        assert_eq!(astar([1, 2], edges, heuristic, |node| *node == 3).map(|result| result.cost), Some(1));
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{day1, day2, day3, day4, day5, day6, day7, day8, day9, day10, day11, day12, day13, day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25};
+use crate::bench::{timed, DayTimings};
+use crate::error::{ParseError, SolveError};
+use crate::observer::{NoOp, Observer};
+use crate::runner::RunError;
+
+/// What a part answers with. A day with a single part answers its missing part 2 with `()`, and a
+/// part that may find no answer for a well-formed input answers with a `Result`.
+pub trait Answer {
+    const PRESENT: bool = true;
+
+    fn into_answer(self) -> Result<String, SolveError>;
+}
+
+macro_rules! display_answers {
+    ($($answer:ty),*) => {
+        $(impl Answer for $answer {
+            fn into_answer(self) -> Result<String, SolveError> {
+                Ok(self.to_string())
+            }
+        })*
+    };
+}
+
+display_answers!(usize, i32, i64, u64, f64, String);
+
+impl Answer for () {
+    const PRESENT: bool = false;
+
+    fn into_answer(self) -> Result<String, SolveError> {
+        Ok(String::new())
+    }
+}
+
+impl<T: Answer> Answer for Result<T, SolveError> {
+    fn into_answer(self) -> Result<String, SolveError> {
+        self.and_then(Answer::into_answer)
+    }
+}
+
+pub trait Solution {
+    const DAY: usize;
+    const PARTS: usize = if <Self::Answer2 as Answer>::PRESENT { 2 } else { 1 };
+    /// Bumped whenever a change can alter the answers, so that cached answers are not reused.
+    const VERSION: u32 = 1;
+
+    type Parsed;
+    type Answer1: Answer;
+    type Answer2: Answer;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError>;
+    fn part1(parsed: Self::Parsed, observer: &mut dyn Observer) -> Self::Answer1;
+    fn part2(parsed: Self::Parsed, observer: &mut dyn Observer) -> Self::Answer2;
+}
+
+pub trait Solver: Sync {
+    fn day(&self) -> usize;
+    fn parts(&self) -> usize;
+    fn version(&self) -> u32;
+    fn solve(&self, part: usize, input: &str, observer: &mut dyn Observer) -> Result<String, RunError>;
+    fn bench(&self, input: &str) -> Result<DayTimings, RunError>;
+}
+
+impl<S: Solution + Sync> Solver for S {
+    fn day(&self) -> usize {
+        S::DAY
+    }
+
+    fn parts(&self) -> usize {
+        S::PARTS
+    }
+
+    fn version(&self) -> u32 {
+        S::VERSION
+    }
+
+    fn solve(&self, part: usize, input: &str, observer: &mut dyn Observer) -> Result<String, RunError> {
+        if part == 0 || part > S::PARTS {
+            return Err(RunError::UnknownPart(S::DAY, part));
+        }
+
+        let parsed = S::parse(input.trim_end()).map_err(RunError::Parse)?;
+
+        let answer = match part {
+            1 => S::part1(parsed, observer).into_answer(),
+            _ => S::part2(parsed, observer).into_answer(),
+        };
+
+        observer.finished();
+        answer.map_err(RunError::Solve)
+    }
+
+    fn bench(&self, input: &str) -> Result<DayTimings, RunError> {
+        let input = input.trim_end();
+
+        let (parsed, parse) = timed(|| S::parse(input));
+        let parsed = parsed.map_err(RunError::Parse)?;
+        let (_, part1) = timed(|| S::part1(parsed, &mut NoOp));
+
+        // Each part consumes its parsed input, so part 2 parses again outside of the timing.
+        let part2 = match S::PARTS {
+            1 => None,
+            _ => {
+                let parsed = S::parse(input).map_err(RunError::Parse)?;
+
+                Some(timed(|| S::part2(parsed, &mut NoOp)).1)
+            }
+        };
+
+        Ok(DayTimings::new(S::DAY, parse, part1, part2))
+    }
+}
+
+static SOLVERS: [&dyn Solver; 25] = [
+    &day1::Day1, &day2::Day2, &day3::Day3, &day4::Day4, &day5::Day5,
+    &day6::Day6, &day7::Day7, &day8::Day8, &day9::Day9, &day10::Day10,
+    &day11::Day11, &day12::Day12, &day13::Day13, &day14::Day14, &day15::Day15,
+    &day16::Day16, &day17::Day17, &day18::Day18, &day19::Day19, &day20::Day20,
+    &day21::Day21, &day22::Day22, &day23::Day23, &day24::Day24, &day25::Day25,
+];
+
+pub fn get_solver(day: usize) -> Option<&'static dyn Solver> {
+    day.checked_sub(1).and_then(|index| SOLVERS.get(index)).copied()
+}
+
+pub fn get_solvers() -> &'static [&'static dyn Solver] {
+    &SOLVERS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_is_indexed_by_day() {
+        for day in 1..=25 {
+            assert_eq!(get_solver(day).unwrap().day(), day);
+        }
+
+        assert!(get_solver(0).is_none());
+        assert!(get_solver(26).is_none());
+    }
+
+    #[test]
+    fn days_without_part_2_have_one_part() {
+        assert_eq!(get_solver(24).unwrap().parts(), 2);
+        assert_eq!(get_solver(25).unwrap().parts(), 1);
+        assert_eq!(get_solver(25).unwrap().solve(2, "1", &mut NoOp), Err(RunError::UnknownPart(25, 2)));
+    }
+}
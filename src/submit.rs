@@ -0,0 +1,132 @@
+//! Submits answers over HTTP the way the puzzle site expects them, as a form posted to
+//! `<url>/day/<day>/answer`, and reads the verdict out of the page that comes back.
+
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    AlreadySolved,
+    TooRecent,
+}
+
+impl Verdict {
+    /// The verdict in a response page, by the sentences the site uses.
+    pub fn from_page(page: &str) -> Option<Verdict> {
+        let verdict = if page.contains("That's the right answer") {
+            Verdict::Correct
+        } else if page.contains("answer is too high") {
+            Verdict::TooHigh
+        } else if page.contains("answer is too low") {
+            Verdict::TooLow
+        } else if page.contains("That's not the right answer") {
+            Verdict::Wrong
+        } else if page.contains("Did you already complete it") {
+            Verdict::AlreadySolved
+        } else if page.contains("gave an answer too recently") {
+            Verdict::TooRecent
+        } else {
+            return None;
+        };
+
+        Some(verdict)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::Wrong => "wrong",
+            Verdict::AlreadySolved => "already solved",
+            Verdict::TooRecent => "answered too recently, wait a bit",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    Http(ureq::Error),
+    UnexpectedResponse(String),
+}
+
+impl Display for SubmitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitError::Http(error) => write!(f, "could not submit: {}", error),
+            SubmitError::UnexpectedResponse(page) => write!(f, "could not find a verdict in the response ({} bytes)", page.len()),
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
+pub struct Client {
+    url: String,
+    session: Option<String>,
+}
+
+impl Client {
+    /// A client for the event at `url`, such as `https://adventofcode.com/2022`.
+    pub fn new(url: &str) -> Self {
+        Client { url: url.trim_end_matches('/').to_string(), session: None }
+    }
+
+    /// Sends the `session` cookie that identifies the user.
+    pub fn with_session(self, session: impl Into<String>) -> Self {
+        Client { session: Some(session.into()), ..self }
+    }
+
+    pub fn submit(&self, day: usize, part: usize, answer: &str) -> Result<Verdict, SubmitError> {
+        let mut request = ureq::post(format!("{}/day/{}/answer", self.url, day));
+        if let Some(session) = &self.session {
+            request = request.header("Cookie", format!("session={}", session));
+        }
+
+        let mut response = request
+            .send_form([("level", part.to_string()), ("answer", answer.to_string())])
+            .map_err(SubmitError::Http)?;
+        let page = response.body_mut().read_to_string().map_err(SubmitError::Http)?;
+
+        Verdict::from_page(&page).ok_or(SubmitError::UnexpectedResponse(page))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use crate::mock_server::{MockServer, Submission};
+    use super::*;
+
+    #[test]
+    fn submits_to_the_mock_server() {
+        let server = MockServer::start("127.0.0.1:0", BTreeMap::from([((1, 1), "24000".to_string()), ((25, 1), "2=-1=0".to_string())])).unwrap();
+        let client = Client::new(&format!("{}/", server.url())).with_session("abc");
+
+        assert_eq!(client.submit(1, 1, "30000").unwrap(), Verdict::TooHigh);
+        assert_eq!(client.submit(1, 1, "100").unwrap(), Verdict::TooLow);
+        assert_eq!(client.submit(25, 1, "2=-1=1").unwrap(), Verdict::Wrong);
+        assert_eq!(client.submit(25, 1, "2=-1=0").unwrap(), Verdict::Correct);
+        assert_eq!(client.submit(25, 1, "2=-1=0").unwrap(), Verdict::AlreadySolved);
+        assert!(matches!(client.submit(2, 1, "15"), Err(SubmitError::Http(_))));
+
+        let submissions = server.submissions();
+        assert_eq!(submissions.len(), 6);
+        assert_eq!(submissions[2], Submission { day: 25, part: 1, answer: "2=-1=1".to_string(), session: Some("abc".to_string()) });
+    }
+
+    #[test]
+    fn reads_verdicts_from_pages() {
+        assert_eq!(Verdict::from_page("<p>That's not the right answer; your answer is too low.</p>"), Some(Verdict::TooLow));
+        assert_eq!(Verdict::from_page("<p>That's not the right answer. If you're stuck, ...</p>"), Some(Verdict::Wrong));
+        assert_eq!(Verdict::from_page("<p>You gave an answer too recently; you have to wait</p>"), Some(Verdict::TooRecent));
+        assert_eq!(Verdict::from_page("<html>Log in</html>"), None);
+    }
+}
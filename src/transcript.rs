@@ -0,0 +1,181 @@
+//! The inverse of `FileSystem::replay`: a day 7 terminal session of `cd` and `ls` commands that
+//! lists every directory of a file system, in a chosen order.
+
+use std::collections::VecDeque;
+use std::str::FromStr;
+use crate::generate::{self, Rng};
+use crate::vfs::{FileSystem, NodeId};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Traversal {
+    DepthFirst,
+    BreadthFirst,
+    /// Any directory already seen in a listing may come next, entries are listed in any order and
+    /// the session wanders into directories and straight back out on the way.
+    Random(u64),
+}
+
+impl FromStr for Traversal {
+    type Err = String;
+
+    /// `dfs`, `bfs`, or `random` with an optional seed as in `random:7`.
+    fn from_str(traversal: &str) -> Result<Self, Self::Err> {
+        match traversal.split_once(':') {
+            None if traversal == "dfs" => Ok(Traversal::DepthFirst),
+            None if traversal == "bfs" => Ok(Traversal::BreadthFirst),
+            None if traversal == "random" => Ok(Traversal::Random(0)),
+            Some(("random", seed)) => seed.parse().map(Traversal::Random).map_err(|_| format!("invalid seed '{}'", seed)),
+            _ => Err(format!("unknown order '{}', expected dfs, bfs or random", traversal)),
+        }
+    }
+}
+
+/// The file system of a generated day 7 input.
+pub fn generated(seed: u64) -> FileSystem {
+    let input = generate::input(7, &mut Rng::new(seed)).expect("day 7 has a generator");
+
+    FileSystem::replay(&input).expect("generated inputs replay")
+}
+
+/// A session that starts with `cd /` and lists every directory once, only ever changing into a
+/// directory that an earlier listing showed.
+pub fn synthesize(fs: &FileSystem, traversal: Traversal) -> String {
+    let mut session = Session { fs, lines: vec!["$ cd /".to_string()], cwd: FileSystem::ROOT };
+
+    match traversal {
+        Traversal::DepthFirst => {
+            let mut stack = vec![FileSystem::ROOT];
+            while let Some(id) = stack.pop() {
+                session.list(id, |entries| entries);
+                stack.extend(fs.children(id).filter(|child| fs.is_dir(*child)).collect::<Vec<_>>().into_iter().rev());
+            }
+        }
+        Traversal::BreadthFirst => {
+            let mut queue = VecDeque::from([FileSystem::ROOT]);
+            while let Some(id) = queue.pop_front() {
+                session.list(id, |entries| entries);
+                queue.extend(fs.children(id).filter(|child| fs.is_dir(*child)));
+            }
+        }
+        Traversal::Random(seed) => {
+            let mut rng = Rng::new(seed);
+            let mut seen = vec![FileSystem::ROOT];
+            let mut listed = vec![];
+
+            while !seen.is_empty() {
+                let id = seen.swap_remove(rng.below(seen.len()));
+
+                // Stepping out and back in, or into a directory listed before and back out, changes nothing.
+                let detours: Vec<NodeId> = fs.children(session.cwd).filter(|child| listed.contains(child)).collect();
+                if rng.chance(30) {
+                    match fs.parent(session.cwd) {
+                        Some(_) if detours.is_empty() || rng.chance(50) => {
+                            let name = fs.name(session.cwd).to_string();
+                            session.cd("..");
+                            session.cd(&name);
+                        }
+                        _ if !detours.is_empty() => {
+                            session.cd(fs.name(*rng.pick(&detours)));
+                            session.cd("..");
+                        }
+                        _ => {}
+                    }
+                }
+
+                session.list(id, |mut entries| {
+                    rng.shuffle(&mut entries);
+                    entries
+                });
+                listed.push(id);
+                seen.extend(fs.children(id).filter(|child| fs.is_dir(*child)));
+            }
+        }
+    }
+
+    session.lines.join("\n")
+}
+
+struct Session<'a> {
+    fs: &'a FileSystem,
+    lines: Vec<String>,
+    cwd: NodeId,
+}
+
+impl Session<'_> {
+    fn cd(&mut self, path: &str) {
+        self.lines.push(format!("$ cd {}", path));
+        self.cwd = self.fs.resolve(self.cwd, path).expect("only listed directories are entered");
+    }
+
+    /// Changes to `id` one directory at a time, up to the closest common ancestor and down again,
+    /// or from the root when that is no further.
+    fn go_to(&mut self, id: NodeId) {
+        let ancestors = |id: NodeId| -> Vec<NodeId> {
+            let mut ancestors: Vec<NodeId> = std::iter::successors(Some(id), |id| self.fs.parent(*id)).collect();
+            ancestors.reverse();
+            ancestors
+        };
+        let (from, to) = (ancestors(self.cwd), ancestors(id));
+        let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+        if common == 1 && from.len() > 2 {
+            self.cd("/");
+        } else {
+            (common..from.len()).for_each(|_| self.cd(".."));
+        }
+        let fs = self.fs;
+        for id in &to[common..] {
+            self.cd(fs.name(*id));
+        }
+    }
+
+    fn list(&mut self, id: NodeId, order: impl FnOnce(Vec<String>) -> Vec<String>) {
+        self.go_to(id);
+        self.lines.push("$ ls".to_string());
+
+        let entries = self.fs.children(id)
+            .map(|child| match self.fs.is_dir(child) {
+                true => format!("dir {}", self.fs.name(child)),
+                false => format!("{} {}", self.fs.size(child), self.fs.name(child)),
+            })
+            .collect();
+        self.lines.extend(order(entries));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn du(fs: &FileSystem) -> Vec<(String, usize)> {
+        let mut du: Vec<_> = fs.walk(FileSystem::ROOT).map(|id| (fs.path(id), fs.size(id))).collect();
+        du.sort();
+        du
+    }
+
+    #[test]
+    fn round_trips_through_the_parser() {
+        let trees = [FileSystem::replay(include_str!("day7/test_large.txt")).unwrap(), generated(3), generated(4)];
+
+        for fs in &trees {
+            for traversal in [Traversal::DepthFirst, Traversal::BreadthFirst, Traversal::Random(1), Traversal::Random(2)] {
+                let transcript = synthesize(fs, traversal);
+
+                assert_eq!(du(&FileSystem::replay(&transcript).unwrap()), du(fs), "{:?}", traversal);
+                assert_eq!(transcript.matches("$ ls").count(), fs.directories(FileSystem::ROOT).count());
+            }
+        }
+    }
+
+    #[test]
+    fn orders_the_visits() {
+        let fs = FileSystem::replay("$ ls\ndir a\ndir b\n$ cd a\n$ ls\ndir c\n1 x\n$ cd c\n$ ls\n$ cd /b\n$ ls\n2 y").unwrap();
+
+        assert_eq!(synthesize(&fs, Traversal::DepthFirst), "$ cd /\n$ ls\ndir a\ndir b\n$ cd a\n$ ls\ndir c\n1 x\n$ cd c\n$ ls\n$ cd /\n$ cd b\n$ ls\n2 y");
+        assert_eq!(synthesize(&fs, Traversal::BreadthFirst), "$ cd /\n$ ls\ndir a\ndir b\n$ cd a\n$ ls\ndir c\n1 x\n$ cd ..\n$ cd b\n$ ls\n2 y\n$ cd ..\n$ cd a\n$ cd c\n$ ls");
+        assert_eq!(synthesize(&fs, Traversal::Random(5)), synthesize(&fs, Traversal::Random(5)));
+        assert!((0..10).any(|seed| synthesize(&fs, Traversal::Random(seed)).contains("$ cd ..\n$ cd a\n$ cd ..")));
+        assert_eq!("random:9".parse(), Ok(Traversal::Random(9)));
+        assert!("sideways".parse::<Traversal>().is_err());
+    }
+}
@@ -1,181 +1,197 @@
 use std::fmt::{Display, Formatter};
+use std::hash::Hash;
 use std::ops;
 
-#[derive(Eq, PartialEq, Hash, Clone, Copy)]
-pub struct Vector2 {
-    pub(crate) x: usize,
-    pub(crate) y: usize
+pub trait Num: Copy + Ord + Hash + Display + Default
+    + ops::Add<Output = Self> + ops::Sub<Output = Self> + ops::Mul<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
 }
 
-impl Vector2 {
-    // fn get_manhattan_distance(self, other: Vector2) -> usize {
-    //     let absolute = (self - other).abs();
-    //
-    //     let mut distance = absolute.x + absolute.y;
-    //
-    //
-    //     if(absolute.x != 0 && absolute.y != 0){
-    //         distance -= 1;
-    //     }
-    //
-    //     return distance;
-    // }
-
-    // fn abs(&self) -> Vector2 {
-    //     return Vector2 {
-    //         x: self.x.abs(),
-    //         y: self.y.abs()
-    //     }
-    // }
+pub trait Signed: Num + ops::Neg<Output = Self> {
+    fn abs(self) -> Self;
+    fn signum(self) -> Self;
+}
 
+macro_rules! impl_num {
+    ($($t:ty),*) => {$(
+        impl Num for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+        }
+    )*}
 }
 
-impl ops::Add<Vector2> for Vector2 {
-    type Output = Vector2;
+macro_rules! impl_signed {
+    ($($t:ty),*) => {$(
+        impl Signed for $t {
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
 
-    fn add(self, other: Vector2) -> Vector2 {
-        return Vector2 {
-            x: self.x + other.x,
-            y: self.y + other.y,
+            fn signum(self) -> Self {
+                <$t>::signum(self)
+            }
         }
-    }
+    )*}
 }
 
-impl ops::Sub<Vector2> for Vector2 {
-    type Output = Vector2;
+impl_num!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+impl_signed!(i8, i16, i32, i64, isize);
 
-    fn sub(self, other: Vector2) -> Vector2 {
-        return Vector2 {
-            x: self.x - other.x,
-            y: self.y - other.y,
-        }
-    }
+#[derive(Eq, PartialEq, Hash, Clone, Copy, Default, PartialOrd, Ord, Debug)]
+pub struct Vector2<T> {
+    pub x: T,
+    pub y: T
 }
 
-impl ops::Mul<usize> for Vector2 {
-    type Output = Vector2;
+pub type Vector2i = Vector2<i32>;
+pub type Vector2i64 = Vector2<i64>;
 
-    fn mul(self, other: usize) -> Vector2 {
-        return Vector2 {
-            x: self.x * other,
-            y: self.y * other,
-        }
+impl<T: Num> Vector2<T> {
+    pub fn get_manhattan_distance(self, other: Vector2<T>) -> T {
+        let difference = self.abs_diff(other);
+
+        difference.x + difference.y
     }
-}
 
-#[derive(Eq, PartialEq, Hash, Clone, Copy)]
-pub struct Vector2i {
-    pub(crate) x: i32,
-    pub(crate) y: i32
-}
+    pub fn get_chebyshev_distance(self, other: Vector2<T>) -> T {
+        let difference = self.abs_diff(other);
 
-impl Vector2i {
-    pub fn get_manhattan_distance(self, other: Vector2i) -> i32 {
-        let absolute = (self - other).abs();
+        difference.x.max(difference.y)
+    }
 
-        absolute.x + absolute.y
+    fn abs_diff(self, other: Vector2<T>) -> Vector2<T> {
+        Vector2 {
+            x: self.x.max(other.x) - self.x.min(other.x),
+            y: self.y.max(other.y) - self.y.min(other.y),
+        }
+    }
+
+    pub fn cast<U: From<T>>(self) -> Vector2<U> {
+        Vector2 {
+            x: U::from(self.x),
+            y: U::from(self.y),
+        }
     }
 
-    fn abs(&self) -> Vector2i {
-        return Vector2i {
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Vector2<U>> {
+        Some(Vector2 {
+            x: U::try_from(self.x).ok()?,
+            y: U::try_from(self.y).ok()?,
+        })
+    }
+}
+
+impl<T: Signed> Vector2<T> {
+    pub fn abs(self) -> Vector2<T> {
+        Vector2 {
             x: self.x.abs(),
             y: self.y.abs()
         }
     }
 
+    pub fn signum(self) -> Vector2<T> {
+        Vector2 {
+            x: self.x.signum(),
+            y: self.y.signum()
+        }
+    }
 }
 
-impl Display for Vector2i {
+impl<T: Display> Display for Vector2<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&("(" .to_owned() + self.x.to_string().as_str() + ", " + self.y.to_string().as_str() + ")"))
+        write!(f, "({}, {})", self.x, self.y)
     }
 }
 
-impl ops::Add<Vector2i> for Vector2i {
-    type Output = Vector2i;
+impl<T: Num> ops::Add<Vector2<T>> for Vector2<T> {
+    type Output = Vector2<T>;
 
-    fn add(self, other: Vector2i) -> Vector2i {
-        return Vector2i {
+    fn add(self, other: Vector2<T>) -> Vector2<T> {
+        Vector2 {
             x: self.x + other.x,
             y: self.y + other.y,
         }
     }
 }
 
-impl ops::Sub<Vector2i> for Vector2i {
-    type Output = Vector2i;
+impl<T: Num> ops::AddAssign<Vector2<T>> for Vector2<T> {
+    fn add_assign(&mut self, other: Vector2<T>) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Num> ops::Sub<Vector2<T>> for Vector2<T> {
+    type Output = Vector2<T>;
 
-    fn sub(self, other: Vector2i) -> Vector2i {
-        return Vector2i {
+    fn sub(self, other: Vector2<T>) -> Vector2<T> {
+        Vector2 {
             x: self.x - other.x,
             y: self.y - other.y,
         }
     }
 }
 
-impl ops::Mul<i32> for Vector2i {
-    type Output = Vector2i;
+impl<T: Num> ops::SubAssign<Vector2<T>> for Vector2<T> {
+    fn sub_assign(&mut self, other: Vector2<T>) {
+        *self = *self - other;
+    }
+}
 
-    fn mul(self, other: i32) -> Vector2i {
-        return Vector2i {
+impl<T: Num> ops::Mul<T> for Vector2<T> {
+    type Output = Vector2<T>;
+
+    fn mul(self, other: T) -> Vector2<T> {
+        Vector2 {
             x: self.x * other,
             y: self.y * other,
         }
     }
 }
 
+impl<T: Signed> ops::Neg for Vector2<T> {
+    type Output = Vector2<T>;
 
-#[derive(Eq, PartialEq, Hash, Clone, Copy)]
-pub struct Vector2i64 {
-    pub(crate) x: i64,
-    pub(crate) y: i64
-}
-
-impl Vector2i64 {
-    pub fn get_manhattan_distance(self, other: Vector2i64) -> i64 {
-        let absolute = (self - other).abs();
-
-        absolute.x + absolute.y
-    }
-
-    fn abs(&self) -> Vector2i64 {
-        return Vector2i64 {
-            x: self.x.abs(),
-            y: self.y.abs()
+    fn neg(self) -> Vector2<T> {
+        Vector2 {
+            x: -self.x,
+            y: -self.y,
         }
     }
-
 }
 
-impl ops::Add<Vector2i64> for Vector2i64 {
-    type Output = Vector2i64;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    fn add(self, other: Vector2i64) -> Vector2i64 {
-        return Vector2i64 {
-            x: self.x + other.x,
-            y: self.y + other.y,
-        }
+    #[test]
+    fn distances() {
+        let a = Vector2i{x: 1, y: -2};
+        let b = Vector2i{x: -3, y: 1};
+
+        assert_eq!(a.get_manhattan_distance(b), 7);
+        assert_eq!(a.get_chebyshev_distance(b), 4);
+        assert_eq!(Vector2::<usize>{x: 2, y: 5}.get_manhattan_distance(Vector2{x: 4, y: 1}), 6);
     }
-}
 
-impl ops::Sub<Vector2i64> for Vector2i64 {
-    type Output = Vector2i64;
+    #[test]
+    fn arithmetic() {
+        let mut a = Vector2i{x: 3, y: -4};
+        a += Vector2i{x: 1, y: 1};
 
-    fn sub(self, other: Vector2i64) -> Vector2i64 {
-        return Vector2i64 {
-            x: self.x - other.x,
-            y: self.y - other.y,
-        }
+        assert_eq!(a, Vector2i{x: 4, y: -3});
+        assert_eq!(-a * 2, Vector2i{x: -8, y: 6});
+        assert_eq!(a.signum(), Vector2i{x: 1, y: -1});
+        assert_eq!(a.abs().to_string(), "(4, 3)");
     }
-}
 
-impl ops::Mul<i64> for Vector2i64 {
-    type Output = Vector2i64;
+    #[test]
+    fn conversions() {
+        let a = Vector2i{x: -1, y: 7};
 
-    fn mul(self, other: i64) -> Vector2i64 {
-        return Vector2i64 {
-            x: self.x * other,
-            y: self.y * other,
-        }
+        assert_eq!(a.cast::<i64>(), Vector2i64{x: -1, y: 7});
+        assert_eq!(a.try_cast::<usize>(), None);
+        assert_eq!(Vector2i{x: 1, y: 7}.try_cast::<usize>(), Some(Vector2{x: 1, y: 7}));
     }
-}
\ No newline at end of file
+}
@@ -1,20 +1,177 @@
+use std::fmt::{Display, Formatter};
 use std::ops;
+use crate::vector2::{Num, Signed};
 
-#[derive(Eq, PartialEq, Hash, Clone, Copy)]
+#[derive(Eq, PartialEq, Hash, Clone, Copy, Default, Debug)]
 pub struct Vector3<T> {
-    pub(crate) x: T,
-    pub(crate) y: T,
-    pub(crate) z: T,
+    pub x: T,
+    pub y: T,
+    pub z: T,
 }
 
-impl<T: std::ops::Add<Output = T>> ops::Add<Vector3<T>> for Vector3<T> {
+impl<T: Num> Vector3<T> {
+    pub fn get_manhattan_distance(self, other: Vector3<T>) -> T {
+        let difference = self.component_max(other) - self.component_min(other);
+
+        difference.x + difference.y + difference.z
+    }
+
+    pub fn component_min(self, other: Vector3<T>) -> Vector3<T> {
+        Vector3 {
+            x: self.x.min(other.x),
+            y: self.y.min(other.y),
+            z: self.z.min(other.z),
+        }
+    }
+
+    pub fn component_max(self, other: Vector3<T>) -> Vector3<T> {
+        Vector3 {
+            x: self.x.max(other.x),
+            y: self.y.max(other.y),
+            z: self.z.max(other.z),
+        }
+    }
+}
+
+impl<T: Signed> Vector3<T> {
+    pub fn face_neighbours(self) -> impl Iterator<Item = Vector3<T>> {
+        let (zero, one) = (T::ZERO, T::ONE);
+
+        [
+            Vector3 { x: one, y: zero, z: zero },
+            Vector3 { x: -one, y: zero, z: zero },
+            Vector3 { x: zero, y: one, z: zero },
+            Vector3 { x: zero, y: -one, z: zero },
+            Vector3 { x: zero, y: zero, z: one },
+            Vector3 { x: zero, y: zero, z: -one },
+        ].into_iter().map(move |offset| self + offset)
+    }
+
+    pub fn all_neighbours(self) -> impl Iterator<Item = Vector3<T>> {
+        let steps = [-T::ONE, T::ZERO, T::ONE];
+
+        steps.into_iter()
+            .flat_map(move |x| steps.into_iter().flat_map(move |y| steps.into_iter().map(move |z| Vector3 { x, y, z })))
+            .filter(|offset| *offset != Vector3::default())
+            .map(move |offset| self + offset)
+    }
+}
+
+impl<T: Display> Display for Vector3<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+impl<T: Num> ops::Add<Vector3<T>> for Vector3<T> {
     type Output = Vector3<T>;
 
     fn add(self, other: Vector3<T>) -> Vector3<T> {
-        return Vector3 {
+        Vector3 {
             x: self.x + other.x,
             y: self.y + other.y,
             z: self.z + other.z,
         }
     }
-}
\ No newline at end of file
+}
+
+impl<T: Num> ops::Sub<Vector3<T>> for Vector3<T> {
+    type Output = Vector3<T>;
+
+    fn sub(self, other: Vector3<T>) -> Vector3<T> {
+        Vector3 {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+        }
+    }
+}
+
+impl<T: Num> ops::Mul<T> for Vector3<T> {
+    type Output = Vector3<T>;
+
+    fn mul(self, other: T) -> Vector3<T> {
+        Vector3 {
+            x: self.x * other,
+            y: self.y * other,
+            z: self.z * other,
+        }
+    }
+}
+
+impl<T: Signed> ops::Neg for Vector3<T> {
+    type Output = Vector3<T>;
+
+    fn neg(self) -> Vector3<T> {
+        Vector3 {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub struct BoundingBox<T> {
+    pub min: Vector3<T>,
+    pub max: Vector3<T>,
+}
+
+impl<T: Num> BoundingBox<T> {
+    pub fn from_points(points: impl IntoIterator<Item = Vector3<T>>) -> Option<BoundingBox<T>> {
+        points.into_iter().fold(None, |bounds: Option<BoundingBox<T>>, point| Some(match bounds {
+            None => BoundingBox { min: point, max: point },
+            Some(bounds) => BoundingBox { min: bounds.min.component_min(point), max: bounds.max.component_max(point) },
+        }))
+    }
+
+    pub fn expanded(self, amount: T) -> BoundingBox<T> {
+        let offset = Vector3 { x: amount, y: amount, z: amount };
+
+        BoundingBox {
+            min: self.min - offset,
+            max: self.max + offset,
+        }
+    }
+
+    pub fn contains(&self, point: Vector3<T>) -> bool {
+        self.min.component_min(point) == self.min && self.max.component_max(point) == self.max
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_and_distance() {
+        let a = Vector3 { x: 1, y: -2, z: 3 };
+        let b = Vector3 { x: -1, y: 2, z: 0 };
+
+        assert_eq!(a - b, Vector3 { x: 2, y: -4, z: 3 });
+        assert_eq!(-a * 2, Vector3 { x: -2, y: 4, z: -6 });
+        assert_eq!(a.get_manhattan_distance(b), 9);
+        assert_eq!(a.to_string(), "(1, -2, 3)");
+    }
+
+    #[test]
+    fn neighbours() {
+        let origin = Vector3 { x: 0, y: 0, z: 0 };
+
+        assert_eq!(origin.face_neighbours().count(), 6);
+        assert!(origin.face_neighbours().all(|neighbour| neighbour.get_manhattan_distance(origin) == 1));
+        assert_eq!(origin.all_neighbours().count(), 26);
+        assert!(!origin.all_neighbours().any(|neighbour| neighbour == origin));
+    }
+
+    #[test]
+    fn bounding_box() {
+        let bounds = BoundingBox::from_points([Vector3 { x: 1, y: 5, z: 2 }, Vector3 { x: 3, y: 1, z: 2 }]).unwrap();
+
+        assert_eq!(bounds, BoundingBox { min: Vector3 { x: 1, y: 1, z: 2 }, max: Vector3 { x: 3, y: 5, z: 2 } });
+        assert!(bounds.contains(Vector3 { x: 2, y: 3, z: 2 }));
+        assert!(!bounds.contains(Vector3 { x: 2, y: 3, z: 3 }));
+        assert!(bounds.expanded(1).contains(Vector3 { x: 0, y: 0, z: 3 }));
+        assert_eq!(BoundingBox::<i32>::from_points([]), None);
+    }
+}
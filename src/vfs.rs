@@ -0,0 +1,450 @@
+//! The file system a day 7 terminal session reveals, kept as an arena of nodes addressed by index. Every
+//! node knows its parent, directories keep their entries sorted by name, and total sizes are
+//! computed once for the whole tree and cached until the next change.
+
+use std::cell::OnceCell;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use serde::Serialize;
+use crate::day7::Day7;
+use crate::error::{parse_number, ParseError};
+use crate::solution::Solution;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct NodeId(usize);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeType {
+    Directory,
+    File,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Kind {
+    Directory(BTreeMap<String, NodeId>),
+    File(usize),
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    name: String,
+    parent: Option<NodeId>,
+    kind: Kind,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Stat {
+    pub id: NodeId,
+    pub path: String,
+    pub node_type: NodeType,
+    /// The size of a file, or the total size of everything under a directory.
+    pub size: usize,
+    pub entries: usize,
+    pub depth: usize,
+}
+
+/// A node and everything under it, as the JSON document describes the tree.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize)]
+pub struct Listing {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub node_type: NodeType,
+    pub size: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entries: Option<Vec<Listing>>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum VfsError {
+    NotFound(String),
+    NotADirectory(String),
+    /// A file and a directory cannot share a name.
+    Conflict(String),
+    InvalidName(String),
+}
+
+impl Display for VfsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VfsError::NotFound(path) => write!(f, "no such file or directory: {}", path),
+            VfsError::NotADirectory(path) => write!(f, "not a directory: {}", path),
+            VfsError::Conflict(path) => write!(f, "already exists with another type: {}", path),
+            VfsError::InvalidName(name) => write!(f, "invalid name '{}'", name),
+        }
+    }
+}
+
+impl std::error::Error for VfsError {}
+
+#[derive(Clone, Debug)]
+pub struct FileSystem {
+    nodes: Vec<Node>,
+    sizes: OnceCell<Vec<usize>>,
+}
+
+impl Default for FileSystem {
+    fn default() -> Self {
+        FileSystem::new()
+    }
+}
+
+impl FileSystem {
+    pub const ROOT: NodeId = NodeId(0);
+
+    pub fn new() -> Self {
+        let root = Node { name: String::new(), parent: None, kind: Kind::Directory(BTreeMap::new()) };
+
+        FileSystem { nodes: vec![root], sizes: OnceCell::new() }
+    }
+
+    /// Replays a day 7 terminal session, where `cd` takes any absolute or relative path and `ls`
+    /// may list a directory again.
+    pub fn replay(transcript: &str) -> Result<FileSystem, ParseError> {
+        let mut fs = FileSystem::new();
+        let mut cwd = FileSystem::ROOT;
+        let mut listing = false;
+
+        for line in transcript.lines().filter(|line| !line.trim().is_empty()) {
+            if let Some(command) = line.strip_prefix("$ ") {
+                listing = false;
+
+                match command.trim_end().split_once(' ') {
+                    Some(("cd", path)) => {
+                        let error = |message: String| ParseError::new(Day7::DAY, transcript, path, message);
+
+                        cwd = fs.resolve(cwd, path.trim()).map_err(|vfs_error| error(vfs_error.to_string()))?;
+                        if !fs.is_dir(cwd) {
+                            return Err(error(format!("not a directory: {}", fs.path(cwd))));
+                        }
+                    }
+                    None if command.trim_end() == "ls" => listing = true,
+                    _ => return Err(ParseError::new(Day7::DAY, transcript, line, "unknown command")),
+                }
+                continue;
+            }
+
+            if !listing {
+                return Err(ParseError::new(Day7::DAY, transcript, line, "expected a command"));
+            }
+
+            let added = match line.split_once(' ') {
+                Some(("dir", name)) => fs.mkdir(cwd, name),
+                Some((size, name)) => fs.add_file(cwd, name, parse_number(Day7::DAY, transcript, size)?),
+                None => return Err(ParseError::new(Day7::DAY, transcript, line, "expected a directory or a file")),
+            };
+            added.map_err(|error| ParseError::new(Day7::DAY, transcript, line, error.to_string()))?;
+        }
+
+        Ok(fs)
+    }
+
+    /// How many directories and files there are, the root included.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.nodes[id.0].name
+    }
+
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.nodes[id.0].parent
+    }
+
+    pub fn node_type(&self, id: NodeId) -> NodeType {
+        match self.nodes[id.0].kind {
+            Kind::Directory(_) => NodeType::Directory,
+            Kind::File(_) => NodeType::File,
+        }
+    }
+
+    pub fn is_dir(&self, id: NodeId) -> bool {
+        self.node_type(id) == NodeType::Directory
+    }
+
+    /// The entries of a directory by name, nothing for a file.
+    pub fn children(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        let entries = match &self.nodes[id.0].kind {
+            Kind::Directory(entries) => Some(entries.values().copied()),
+            Kind::File(_) => None,
+        };
+
+        entries.into_iter().flatten()
+    }
+
+    pub fn child(&self, id: NodeId, name: &str) -> Option<NodeId> {
+        match &self.nodes[id.0].kind {
+            Kind::Directory(entries) => entries.get(name).copied(),
+            Kind::File(_) => None,
+        }
+    }
+
+    fn add(&mut self, parent: NodeId, name: &str, kind: Kind) -> Result<NodeId, VfsError> {
+        if name.is_empty() || name == "." || name == ".." || name.contains('/') {
+            return Err(VfsError::InvalidName(name.to_string()));
+        }
+
+        let id = NodeId(self.nodes.len());
+        let Kind::Directory(entries) = &mut self.nodes[parent.0].kind else {
+            return Err(VfsError::NotADirectory(self.path(parent)));
+        };
+
+        if let Some(&existing) = entries.get(name) {
+            return Ok(existing);
+        }
+
+        entries.insert(name.to_string(), id);
+        self.nodes.push(Node { name: name.to_string(), parent: Some(parent), kind });
+        self.sizes.take();
+
+        Ok(id)
+    }
+
+    /// The directory `name` in `parent`, created unless it already exists.
+    pub fn mkdir(&mut self, parent: NodeId, name: &str) -> Result<NodeId, VfsError> {
+        let id = self.add(parent, name, Kind::Directory(BTreeMap::new()))?;
+
+        match self.node_type(id) {
+            NodeType::Directory => Ok(id),
+            NodeType::File => Err(VfsError::Conflict(self.path(id))),
+        }
+    }
+
+    /// The file `name` in `parent`, created unless it already exists, with its size updated.
+    pub fn add_file(&mut self, parent: NodeId, name: &str, size: usize) -> Result<NodeId, VfsError> {
+        let id = self.add(parent, name, Kind::File(size))?;
+
+        match &mut self.nodes[id.0].kind {
+            Kind::File(existing) if *existing == size => {}
+            Kind::File(existing) => {
+                *existing = size;
+                self.sizes.take();
+            }
+            Kind::Directory(_) => return Err(VfsError::Conflict(self.path(id))),
+        }
+
+        Ok(id)
+    }
+
+    /// Resolves an absolute path, or one relative to `cwd`, like a shell does. `..` at the root
+    /// stays at the root.
+    pub fn resolve(&self, cwd: NodeId, path: &str) -> Result<NodeId, VfsError> {
+        let mut id = if path.starts_with('/') { FileSystem::ROOT } else { cwd };
+
+        for component in path.split('/').filter(|component| !component.is_empty() && *component != ".") {
+            if !self.is_dir(id) {
+                return Err(VfsError::NotADirectory(self.path(id)));
+            }
+
+            id = match component {
+                ".." => self.parent(id).unwrap_or(FileSystem::ROOT),
+                name => self.child(id, name).ok_or_else(|| VfsError::NotFound(self.join(id, name)))?,
+            };
+        }
+
+        Ok(id)
+    }
+
+    fn join(&self, id: NodeId, name: &str) -> String {
+        match id {
+            FileSystem::ROOT => format!("/{}", name),
+            _ => format!("{}/{}", self.path(id), name),
+        }
+    }
+
+    /// The absolute path of a node, `/` for the root.
+    pub fn path(&self, id: NodeId) -> String {
+        match self.parent(id) {
+            None => "/".to_string(),
+            Some(parent) => self.join(parent, self.name(id)),
+        }
+    }
+
+    pub fn depth(&self, id: NodeId) -> usize {
+        std::iter::successors(self.parent(id), |parent| self.parent(*parent)).count()
+    }
+
+    fn sizes(&self) -> &[usize] {
+        self.sizes.get_or_init(|| {
+            let mut sizes: Vec<usize> = self.nodes.iter()
+                .map(|node| match node.kind {
+                    Kind::File(size) => size,
+                    Kind::Directory(_) => 0,
+                })
+                .collect();
+
+            // Nodes are only ever added under existing ones, so children come after their parents.
+            for (index, node) in self.nodes.iter().enumerate().rev() {
+                if let Some(parent) = node.parent {
+                    sizes[parent.0] += sizes[index];
+                }
+            }
+
+            sizes
+        })
+    }
+
+    /// The size of a file, or the total size of everything under a directory.
+    pub fn size(&self, id: NodeId) -> usize {
+        self.sizes()[id.0]
+    }
+
+    pub fn stat(&self, id: NodeId) -> Stat {
+        Stat {
+            id,
+            path: self.path(id),
+            node_type: self.node_type(id),
+            size: self.size(id),
+            entries: self.children(id).count(),
+            depth: self.depth(id),
+        }
+    }
+
+    /// `id` and everything under it, each directory before its entries, which come by name.
+    pub fn walk(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        let mut stack = vec![id];
+
+        std::iter::from_fn(move || {
+            let next = stack.pop()?;
+            stack.extend(self.children(next).collect::<Vec<_>>().into_iter().rev());
+
+            Some(next)
+        })
+    }
+
+    /// Every directory under `id`, `id` included.
+    pub fn directories(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.walk(id).filter(|id| self.is_dir(*id))
+    }
+
+    /// Like `du`, the total size of every directory under `id`, each after its subdirectories.
+    pub fn du(&self, id: NodeId) -> Vec<(NodeId, usize)> {
+        let mut directories: Vec<_> = self.directories(id).map(|id| (id, self.size(id))).collect();
+        directories.reverse();
+
+        directories
+    }
+
+    /// An indented listing like `tree` prints, with the size of every entry.
+    pub fn render_tree(&self, id: NodeId) -> String {
+        let mut lines = vec![format!("{} ({})", self.display_name(id), self.size(id))];
+        self.render_entries(id, "", &mut lines);
+
+        lines.join("\n")
+    }
+
+    fn render_entries(&self, id: NodeId, indent: &str, lines: &mut Vec<String>) {
+        let children: Vec<_> = self.children(id).collect();
+
+        for (i, child) in children.iter().enumerate() {
+            let last = i + 1 == children.len();
+            lines.push(format!("{}{} {} ({})", indent, if last { "└──" } else { "├──" }, self.display_name(*child), self.size(*child)));
+            self.render_entries(*child, &format!("{}{}", indent, if last { "    " } else { "│   " }), lines);
+        }
+    }
+
+    fn display_name(&self, id: NodeId) -> String {
+        match (id, self.node_type(id)) {
+            (FileSystem::ROOT, _) => "/".to_string(),
+            (_, NodeType::Directory) => format!("{}/", self.name(id)),
+            (_, NodeType::File) => self.name(id).to_string(),
+        }
+    }
+
+    /// Lines like `du -a` prints, for every file and directory under `id`, largest first.
+    pub fn render_du(&self, id: NodeId) -> String {
+        let mut entries: Vec<_> = self.walk(id).map(|id| (self.size(id), self.path(id))).collect();
+        entries.sort_by(|(size, path), (other_size, other_path)| other_size.cmp(size).then_with(|| path.cmp(other_path)));
+
+        entries.into_iter().map(|(size, path)| format!("{}\t{}", size, path)).collect::<Vec<_>>().join("\n")
+    }
+
+    pub fn listing(&self, id: NodeId) -> Listing {
+        Listing {
+            name: if id == FileSystem::ROOT { "/".to_string() } else { self.name(id).to_string() },
+            node_type: self.node_type(id),
+            size: self.size(id),
+            entries: self.is_dir(id).then(|| self.children(id).map(|child| self.listing(child)).collect()),
+        }
+    }
+
+    pub fn render_json(&self, id: NodeId) -> String {
+        serde_json::to_string_pretty(&self.listing(id)).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> FileSystem {
+        let mut fs = FileSystem::new();
+        let a = fs.mkdir(FileSystem::ROOT, "a").unwrap();
+        let e = fs.mkdir(a, "e").unwrap();
+        fs.add_file(e, "i", 584).unwrap();
+        fs.add_file(a, "f", 29116).unwrap();
+        fs.add_file(FileSystem::ROOT, "b.txt", 14848514).unwrap();
+        fs.mkdir(FileSystem::ROOT, "d").unwrap();
+
+        fs
+    }
+
+    #[test]
+    fn resolves_paths() {
+        let fs = sample();
+        let e = fs.resolve(FileSystem::ROOT, "/a/e").unwrap();
+
+        assert_eq!(fs.path(e), "/a/e");
+        assert_eq!(fs.resolve(e, "../../d"), fs.resolve(FileSystem::ROOT, "d"));
+        assert_eq!(fs.resolve(e, "/../a/./e/"), Ok(e));
+        assert_eq!(fs.resolve(e, "i").map(|id| fs.path(id)), Ok("/a/e/i".to_string()));
+        assert_eq!(fs.resolve(e, "x"), Err(VfsError::NotFound("/a/e/x".to_string())));
+        assert_eq!(fs.resolve(FileSystem::ROOT, "b.txt/c"), Err(VfsError::NotADirectory("/b.txt".to_string())));
+    }
+
+    #[test]
+    fn caches_sizes_until_changed() {
+        let mut fs = sample();
+        let a = fs.resolve(FileSystem::ROOT, "a").unwrap();
+
+        assert_eq!(fs.size(FileSystem::ROOT), 14878214);
+        assert_eq!(fs.stat(a), Stat { id: a, path: "/a".to_string(), node_type: NodeType::Directory, size: 29700, entries: 2, depth: 1 });
+
+        assert_eq!(fs.mkdir(FileSystem::ROOT, "a"), Ok(a));
+        fs.add_file(a, "f", 16).unwrap();
+        assert_eq!(fs.size(a), 600);
+        assert_eq!(fs.add_file(FileSystem::ROOT, "a", 1), Err(VfsError::Conflict("/a".to_string())));
+        assert_eq!(fs.mkdir(a, ".."), Err(VfsError::InvalidName("..".to_string())));
+    }
+
+    #[test]
+    fn renders_listings() {
+        let fs = sample();
+
+        assert_eq!(fs.render_tree(FileSystem::ROOT), "\
+/ (14878214)
+├── a/ (29700)
+│   ├── e/ (584)
+│   │   └── i (584)
+│   └── f (29116)
+├── b.txt (14848514)
+└── d/ (0)");
+        assert_eq!(fs.render_du(fs.resolve(FileSystem::ROOT, "a").unwrap()), "29700\t/a\n29116\t/a/f\n584\t/a/e\n584\t/a/e/i");
+
+        let json: serde_json::Value = serde_json::from_str(&fs.render_json(FileSystem::ROOT)).unwrap();
+        assert_eq!(json["name"], "/");
+        assert_eq!(json["entries"][0]["entries"][0], serde_json::json!({"name": "e", "type": "directory", "size": 584, "entries": [{"name": "i", "type": "file", "size": 584}]}));
+        assert_eq!(json["entries"][2]["entries"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn walks_in_order() {
+        let fs = sample();
+        let paths = |ids: Vec<NodeId>| ids.into_iter().map(|id| fs.path(id)).collect::<Vec<_>>();
+
+        assert_eq!(paths(fs.walk(FileSystem::ROOT).collect()), ["/", "/a", "/a/e", "/a/e/i", "/a/f", "/b.txt", "/d"]);
+        assert_eq!(fs.du(FileSystem::ROOT).into_iter().map(|(id, size)| (fs.path(id), size)).collect::<Vec<_>>(),
+                   [("/d".to_string(), 0), ("/a/e".to_string(), 584), ("/a".to_string(), 29700), ("/".to_string(), 14878214)]);
+    }
+}
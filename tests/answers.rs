@@ -0,0 +1,101 @@
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use advent_of_code_2022::answers::Manifest;
+use advent_of_code_2022::runner;
+
+const MANIFEST: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers.toml");
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+
+struct Fixture {
+    day: usize,
+    name: String,
+    path: PathBuf,
+}
+
+fn load_manifest() -> Manifest {
+    Manifest::parse(&fs::read_to_string(MANIFEST).unwrap()).unwrap()
+}
+
+/// Every `src/dayN/*.txt` file.
+fn find_fixtures() -> Vec<Fixture> {
+    let mut fixtures = Vec::new();
+
+    for entry in fs::read_dir(FIXTURES).unwrap().map(Result::unwrap) {
+        let Some(day) = entry.file_name().to_str().and_then(|name| name.strip_prefix("day")?.parse().ok()) else {
+            continue;
+        };
+        if !entry.path().is_dir() {
+            continue;
+        }
+
+        for file in fs::read_dir(entry.path()).unwrap().map(Result::unwrap) {
+            let path = file.path();
+            if path.extension().is_some_and(|extension| extension == "txt") {
+                let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+
+                fixtures.push(Fixture { day, name, path });
+            }
+        }
+    }
+
+    fixtures.sort_by(|a, b| (a.day, &a.name).cmp(&(b.day, &b.name)));
+    fixtures
+}
+
+fn check_answer(day: usize, part: usize, path: &Path, expected: &str) -> Result<(), String> {
+    let input = fs::read_to_string(path).unwrap();
+    let answer = panic::catch_unwind(AssertUnwindSafe(|| runner::solve(day, part, &input)))
+        .map_err(|_| "panicked".to_string())?
+        .map_err(|error| error.to_string())?;
+
+    match answer == expected {
+        true => Ok(()),
+        false => Err(format!("expected {:?}, got {:?}", expected, answer)),
+    }
+}
+
+/// Checks every fixture whose `slow` flag matches, reporting all failures at once.
+fn check_fixtures(slow: bool) {
+    let manifest = load_manifest();
+    let fixtures = find_fixtures();
+    let mut failures = Vec::new();
+
+    for (day, name, _) in manifest.entries() {
+        if !fixtures.iter().any(|fixture| fixture.day == day && fixture.name == name) {
+            failures.push(format!("day{}.{}: no such fixture", day, name));
+        }
+    }
+
+    for fixture in &fixtures {
+        let key = format!("day{}.{}", fixture.day, fixture.name);
+        let Some(answers) = manifest.get(fixture.day, &fixture.name) else {
+            failures.push(format!("{}: missing from answers.toml", key));
+            continue;
+        };
+        if answers.slow != slow {
+            continue;
+        }
+
+        for part in 1..=2 {
+            if let Some(expected) = answers.part(part) {
+                if let Err(message) = check_answer(fixture.day, part, &fixture.path, expected) {
+                    failures.push(format!("{} part {}: {}", key, part, message));
+                }
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{} failure(s):\n{}", failures.len(), failures.join("\n"));
+}
+
+#[test]
+fn fixtures_match_answers() {
+    check_fixtures(false);
+}
+
+#[test]
+#[ignore = "takes minutes, run with --release -- --ignored"]
+fn slow_fixtures_match_answers() {
+    check_fixtures(true);
+}
@@ -0,0 +1,22 @@
+//! Running the solvers on generated inputs, shared by the property and reference tests.
+
+use std::panic::{self, AssertUnwindSafe};
+use advent_of_code_2022::generate::{self, Rng};
+use advent_of_code_2022::runner::{self, RunError};
+
+/// The seeds from 0 to `cases` with the input generated from each.
+pub fn generated(day: usize, cases: u64) -> impl Iterator<Item = (u64, String)> {
+    (0..cases).map(move |seed| (seed, generate::input(day, &mut Rng::new(seed)).unwrap()))
+}
+
+/// Solves a part of the input generated from `seed`, failing with the seed and the input if the
+/// solver panics.
+pub fn solve(day: usize, part: usize, seed: u64, input: &str) -> Result<String, RunError> {
+    panic::catch_unwind(AssertUnwindSafe(|| runner::solve(day, part, input)))
+        .unwrap_or_else(|_| panic!("day {} part {} seed {} panicked on\n{}", day, part, seed, input))
+}
+
+/// Like `solve`, failing on an error as well.
+pub fn answer(day: usize, part: usize, seed: u64, input: &str) -> String {
+    solve(day, part, seed, input).unwrap_or_else(|error| panic!("day {} part {} seed {}: {}\n{}", day, part, seed, error, input))
+}
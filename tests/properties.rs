@@ -0,0 +1,274 @@
+mod common;
+
+use itertools::Itertools;
+use advent_of_code_2022::generate::{self, Rng};
+use advent_of_code_2022::runner::RunError;
+use common::{answer, generated, solve};
+
+const CASES: u64 = 25;
+
+/// Solves `parts` of `cases` generated inputs and checks `property` on the answers, naming the
+/// seed and showing the input of the first case that fails.
+fn check(day: usize, parts: &[usize], cases: u64, property: impl Fn(&str, &[String]) -> bool) {
+    for (seed, input) in generated(day, cases) {
+        let answers: Vec<String> = parts.iter().map(|&part| answer(day, part, seed, &input)).collect();
+
+        assert!(property(&input, &answers), "day {} seed {}: answers {:?} break the property for\n{}", day, seed, answers, input);
+    }
+}
+
+/// Solves `parts` of `cases` broken inputs, each of which has to fail with an error that
+/// `expected` accepts.
+fn check_rejected(day: usize, parts: &[usize], cases: u64, expected: impl Fn(&RunError) -> bool) {
+    for seed in 0..cases {
+        let input = generate::broken_input(day, &mut Rng::new(seed)).unwrap();
+
+        for &part in parts {
+            match solve(day, part, seed, &input) {
+                Err(error) if expected(&error) => {}
+                outcome => panic!("day {} part {} seed {}: expected an error, got {:?} for\n{}", day, part, seed, outcome, input),
+            }
+        }
+    }
+}
+
+fn number(answer: &str) -> i64 {
+    answer.parse().unwrap()
+}
+
+fn from_snafu(digits: &str) -> i64 {
+    digits.chars().fold(0, |value, digit| value * 5 + match digit {
+        '=' => -2,
+        '-' => -1,
+        _ => digit.to_digit(3).unwrap() as i64,
+    })
+}
+
+#[test]
+fn day1_top_three_include_the_top_elf() {
+    check(1, &[1, 2], CASES, |_, answers| number(&answers[0]) <= number(&answers[1]));
+}
+
+#[test]
+fn day2_scores_stay_in_range() {
+    check(2, &[1, 2], CASES, |input, answers| {
+        let rounds = input.lines().count() as i64;
+        answers.iter().all(|answer| (rounds..=9 * rounds).contains(&number(answer)))
+    });
+}
+
+#[test]
+fn day3_every_group_has_a_badge() {
+    check(3, &[1, 2], CASES, |input, answers| {
+        let groups = input.lines().count() as i64 / 3;
+        number(&answers[0]) >= 3 * groups && (groups..=52 * groups).contains(&number(&answers[1]))
+    });
+}
+
+#[test]
+fn day4_contained_pairs_overlap() {
+    check(4, &[1, 2], CASES, |input, answers| {
+        number(&answers[0]) <= number(&answers[1]) && number(&answers[1]) <= input.lines().count() as i64
+    });
+}
+
+#[test]
+fn day5_every_stack_has_a_top() {
+    check(5, &[1, 2], CASES, |input, answers| {
+        let stacks = input.lines().find(|line| line.starts_with(" 1")).unwrap().split_whitespace().count();
+        answers.iter().all(|answer| answer.len() == stacks && answer.chars().all(|crate_| crate_.is_ascii_uppercase()))
+    });
+}
+
+#[test]
+fn day5_taking_from_an_empty_stack_is_a_parse_error() {
+    check_rejected(5, &[1, 2], CASES, |error| matches!(error, RunError::Parse(error) if error.message.ends_with("only holds 0")));
+}
+
+#[test]
+fn day6_message_marker_contains_a_packet_marker() {
+    check(6, &[1, 2], CASES, |_, answers| number(&answers[0]) + 10 <= number(&answers[1]));
+}
+
+#[test]
+fn day6_streams_without_a_message_marker_have_no_answer() {
+    check_rejected(6, &[2], CASES, |error| matches!(error, RunError::Solve(_)));
+
+    for seed in 0..CASES {
+        let input = generate::broken_input(6, &mut Rng::new(seed)).unwrap();
+        let letters: Vec<char> = input.chars().collect();
+        let marker = letters.windows(4).position(|window| window.iter().all_unique()).map(|index| index + 4);
+
+        assert_eq!(marker.map(|index| index.to_string()), Some(answer(6, 1, seed, &input)), "seed {}: {}", seed, input);
+    }
+}
+
+#[test]
+fn day7_deleted_directory_frees_enough() {
+    check(7, &[1, 2], CASES, |input, answers| {
+        let used: i64 = input.lines().filter_map(|line| line.split(' ').next()?.parse::<i64>().ok()).sum();
+        let deleted = number(&answers[1]);
+        deleted <= used && used - deleted <= 40_000_000
+    });
+}
+
+#[test]
+fn day8_edge_trees_are_visible() {
+    check(8, &[1, 2], CASES, |input, answers| {
+        let (width, height) = (input.lines().next().unwrap().len() as i64, input.lines().count() as i64);
+        let edge = if width < 3 || height < 3 { width * height } else { 2 * (width + height) - 4 };
+        (edge..=width * height).contains(&number(&answers[0])) && number(&answers[1]) >= 0
+    });
+}
+
+#[test]
+fn day9_tails_visit_the_start() {
+    check(9, &[1, 2], CASES, |input, answers| {
+        let steps: i64 = input.lines().map(|line| number(&line[2..])).sum();
+        answers.iter().all(|answer| (1..=steps + 1).contains(&number(answer)))
+    });
+}
+
+#[test]
+fn day10_screen_has_six_rows() {
+    check(10, &[2], CASES, |_, answers| {
+        let rows: Vec<_> = answers[0].lines().take(6).collect();
+        rows.len() == 6 && rows.iter().all(|row| row.len() == 40 && row.chars().all(|pixel| pixel == '#' || pixel == '.'))
+    });
+}
+
+#[test]
+fn day11_monkey_business_is_bounded_by_inspections() {
+    check(11, &[1, 2], 10, |input, answers| {
+        let items = input.lines()
+            .filter_map(|line| line.trim().strip_prefix("Starting items:"))
+            .map(|items| items.split(',').filter(|item| !item.trim().is_empty()).count() as i64)
+            .sum::<i64>();
+        number(&answers[0]) <= (20 * items).pow(2) && number(&answers[1]) <= (10_000 * items).pow(2)
+    });
+}
+
+#[test]
+fn day12_any_a_is_no_further_than_s() {
+    check(12, &[1, 2], CASES, |_, answers| {
+        (1..=number(&answers[0])).contains(&number(&answers[1])) && number(&answers[0]) >= 25
+    });
+}
+
+#[test]
+fn day12_walled_off_signal_is_a_solve_error() {
+    check_rejected(12, &[1, 2], CASES, |error| matches!(error, RunError::Solve(_)));
+}
+
+#[test]
+fn day13_dividers_are_ordered() {
+    check(13, &[1, 2], CASES, |input, answers| {
+        let pairs = (input.lines().count() as i64 + 1) / 3;
+        let packets = 2 * pairs + 2;
+        number(&answers[0]) <= pairs * (pairs + 1) / 2 && (2..=(packets - 1) * packets).contains(&number(&answers[1]))
+    });
+}
+
+#[test]
+fn day14_floor_holds_more_sand() {
+    check(14, &[1, 2], CASES, |_, answers| number(&answers[0]) < number(&answers[1]));
+}
+
+#[test]
+fn day15_covered_positions_are_bounded_by_sensor_ranges() {
+    check(15, &[1], CASES, |input, answers| {
+        let reach: i64 = input.lines()
+            .map(|line| {
+                let numbers: Vec<i64> = line.split(|c: char| c != '-' && !c.is_ascii_digit())
+                    .filter(|number| !number.is_empty())
+                    .map(number)
+                    .collect();
+                let range = (numbers[0] - numbers[2]).abs() + (numbers[1] - numbers[3]).abs();
+                (2 * (range - (numbers[1] - 2_000_000).abs()) + 1).max(0)
+            })
+            .sum();
+        (0..=reach).contains(&number(&answers[0]))
+    });
+}
+
+#[test]
+fn day16_pressure_is_bounded_by_flow() {
+    check(16, &[1, 2], 10, |input, answers| {
+        let flow: i64 = input.lines().map(|line| number(line.split(['=', ';']).nth(1).unwrap())).sum();
+        number(&answers[0]) <= 28 * flow && number(&answers[1]) <= 2 * 24 * flow
+    });
+}
+
+#[test]
+fn day17_tower_grows_with_rocks() {
+    check(17, &[1, 2], 10, |_, answers| {
+        let (short, tall) = (number(&answers[0]), number(&answers[1]));
+        (2022..=4 * 2022).contains(&short) && short < tall
+    });
+}
+
+#[test]
+fn day18_exterior_is_part_of_the_surface() {
+    check(18, &[1, 2], CASES, |input, answers| {
+        let (surface, exterior) = (number(&answers[0]), number(&answers[1]));
+        exterior <= surface && surface <= 6 * input.lines().count() as i64 && surface % 2 == 0
+    });
+}
+
+#[test]
+fn day19_quality_levels_are_multiples_of_ids() {
+    check(19, &[1], 3, |input, answers| {
+        let ids = input.lines().count() as i64;
+        number(&answers[0]) >= 0 && number(&answers[0]) <= ids * (ids + 1) / 2 * 24 * 24
+    });
+}
+
+#[test]
+fn day20_coordinates_come_from_the_file() {
+    check(20, &[1, 2], CASES, |input, answers| {
+        let largest = input.lines().map(|line| number(line).abs()).max().unwrap();
+        number(&answers[0]).abs() <= 3 * largest && number(&answers[1]).abs() <= 3 * largest * 811_589_153
+    });
+}
+
+#[test]
+fn day21_humn_already_yells_the_answer() {
+    check(21, &[2], CASES, |input, answers| {
+        let humn = input.lines().find_map(|line| line.strip_prefix("humn: ")).unwrap();
+        answers[0] == humn
+    });
+}
+
+#[test]
+fn day22_password_is_on_an_open_tile() {
+    check(22, &[1, 2], CASES, |input, answers| {
+        let map: Vec<&[u8]> = input.lines().take_while(|line| !line.is_empty()).map(str::as_bytes).collect();
+        answers.iter().all(|answer| {
+            let password = number(answer) as usize;
+            let (row, column) = (password / 1000, password % 1000 / 4);
+            map.get(row.wrapping_sub(1)).and_then(|line| line.get(column.wrapping_sub(1))) == Some(&b'.')
+        })
+    });
+}
+
+#[test]
+fn day23_elves_spread_out() {
+    check(23, &[1, 2], 10, |_, answers| number(&answers[0]) >= 0 && number(&answers[1]) >= 1);
+}
+
+#[test]
+fn day24_trips_take_at_least_the_distance() {
+    check(24, &[1, 2], 10, |input, answers| {
+        let (width, height) = (input.lines().next().unwrap().len() as i64 - 2, input.lines().count() as i64 - 2);
+        let (there, back_again) = (number(&answers[0]), number(&answers[1]));
+        there >= width + height && back_again >= there + 2 * (width + height)
+    });
+}
+
+#[test]
+fn day25_sum_round_trips() {
+    check(25, &[1], CASES, |input, answers| {
+        let sum: i64 = input.lines().map(from_snafu).sum();
+        from_snafu(&answers[0]) == sum && !answers[0].starts_with('0')
+    });
+}
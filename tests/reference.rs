@@ -0,0 +1,42 @@
+mod common;
+
+use advent_of_code_2022::reference;
+use common::{answer, generated};
+
+/// Solves `cases` generated inputs with both the real and the reference solver, naming the seed
+/// and showing the input of the first case where they disagree.
+fn compare(day: usize, part: usize, cases: u64) {
+    for (seed, input) in generated(day, cases) {
+        let expected = reference::solve(day, part, &input).unwrap();
+
+        assert_eq!(answer(day, part, seed, &input), expected, "day {} part {} seed {} disagrees with the reference on\n{}", day, part, seed, input);
+    }
+}
+
+#[test]
+fn day15_matches_reference() {
+    compare(15, 1, 25);
+}
+
+#[test]
+fn day16_matches_reference() {
+    compare(16, 1, 10);
+    compare(16, 2, 5);
+}
+
+#[test]
+fn day19_matches_reference() {
+    compare(19, 1, 3);
+}
+
+#[test]
+#[ignore = "takes minutes, run with --release -- --ignored"]
+fn day19_part2_matches_reference() {
+    compare(19, 2, 3);
+}
+
+#[test]
+fn day20_matches_reference() {
+    compare(20, 1, 25);
+    compare(20, 2, 10);
+}